@@ -1,4 +1,4 @@
-use std::{marker::PhantomData, net::SocketAddr};
+use std::{marker::PhantomData, net::SocketAddr, time::Duration};
 
 use bevy_ecs::{
     entity::Entity,
@@ -8,7 +8,7 @@ use bevy_ecs::{
 
 use naia_client::{
     shared::{ChannelIndex, Protocolize, ReplicateSafe},
-    Client as NaiaClient, EntityRef,
+    BlobCache, Client as NaiaClient, EntityRef, NaiaClientError,
 };
 
 use naia_bevy_shared::{WorldProxy, WorldRef};
@@ -55,6 +55,34 @@ impl<'a, P: Protocolize, C: ChannelIndex> Client<'a, P, C> {
         self.client.disconnect();
     }
 
+    pub fn connect_playback(&mut self, capture: &[u8]) -> Result<(), NaiaClientError> {
+        self.client.connect_playback(capture)
+    }
+
+    pub fn advance_playback(&mut self, duration: Duration) {
+        self.client.advance_playback(duration);
+    }
+
+    pub fn is_playback(&self) -> bool {
+        self.client.is_playback()
+    }
+
+    pub fn is_playback_finished(&self) -> bool {
+        self.client.is_playback_finished()
+    }
+
+    pub fn start_packet_capture(&mut self) {
+        self.client.start_packet_capture();
+    }
+
+    pub fn stop_packet_capture(&mut self) -> Option<Vec<u8>> {
+        self.client.stop_packet_capture()
+    }
+
+    pub fn is_capturing_packets(&self) -> bool {
+        self.client.is_capturing_packets()
+    }
+
     pub fn is_connected(&self) -> bool {
         self.client.is_connected()
     }
@@ -75,6 +103,30 @@ impl<'a, P: Protocolize, C: ChannelIndex> Client<'a, P, C> {
         self.client.jitter()
     }
 
+    // Replays
+
+    pub fn start_recording(&mut self) {
+        self.client.start_recording(self.world.proxy());
+    }
+
+    pub fn stop_recording(&mut self) -> Option<Vec<u8>> {
+        self.client.stop_recording()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.client.is_recording()
+    }
+
+    // Blobs
+
+    pub fn blob_cache(&self) -> &BlobCache {
+        self.client.blob_cache()
+    }
+
+    pub fn blob_cache_mut(&mut self) -> &mut BlobCache {
+        self.client.blob_cache_mut()
+    }
+
     // Interpolation
 
     pub fn interpolation(&self) -> Option<f32> {
@@ -96,6 +148,36 @@ impl<'a, P: Protocolize, C: ChannelIndex> Client<'a, P, C> {
         return self.client.entities(&self.world.proxy());
     }
 
+    //// Authority ////
+
+    pub fn request_authority(&mut self, entity: &Entity) {
+        self.client.request_authority(entity);
+    }
+
+    pub fn release_authority(&mut self, entity: &Entity) {
+        self.client.release_authority(entity);
+    }
+
+    pub fn has_authority(&self, entity: &Entity) -> bool {
+        self.client.has_authority(entity)
+    }
+
+    pub fn has_component_authority<R: ReplicateSafe<P>>(&self, entity: &Entity) -> bool {
+        self.client.has_component_authority::<R>(entity)
+    }
+
+    //// Ownership ////
+
+    pub fn is_owner(&self, entity: &Entity) -> bool {
+        self.client.is_owner(entity)
+    }
+
+    //// Caching ////
+
+    pub fn entity_is_hidden(&self, entity: &Entity) -> bool {
+        self.client.entity_is_hidden(entity)
+    }
+
     //// Ticks ////
 
     pub fn client_tick(&self) -> Option<u16> {
@@ -1,6 +1,6 @@
 use bevy_ecs::entity::Entity;
 
-use naia_client::shared::{ChannelIndex, ProtocolKindType, Protocolize, Tick};
+use naia_client::shared::{BlobId, ChannelIndex, ProtocolKindType, Protocolize, Tick};
 
 pub struct SpawnEntityEvent(pub Entity);
 pub struct DespawnEntityEvent(pub Entity);
@@ -8,3 +8,15 @@ pub struct InsertComponentEvent<K: ProtocolKindType>(pub Entity, pub K);
 pub struct UpdateComponentEvent<K: ProtocolKindType>(pub Tick, pub Entity, pub K);
 pub struct RemoveComponentEvent<P: Protocolize>(pub Entity, pub P);
 pub struct MessageEvent<P: Protocolize, C: ChannelIndex>(pub C, pub P);
+pub struct AuthorityGrantedEvent(pub Entity);
+pub struct AuthorityRevokedEvent(pub Entity);
+pub struct AuthorityDeniedEvent(pub Entity);
+pub struct ComponentAuthorityGrantedEvent<K: ProtocolKindType>(pub Entity, pub K);
+pub struct ComponentAuthorityRevokedEvent<K: ProtocolKindType>(pub Entity, pub K);
+pub struct OwnershipGrantedEvent(pub Entity);
+pub struct OwnershipRevokedEvent(pub Entity);
+pub struct EntityHiddenEvent(pub Entity);
+pub struct EntityRevealedEvent(pub Entity);
+pub struct BlobProgressEvent(pub BlobId, pub String, pub usize, pub usize);
+pub struct BlobReceivedEvent(pub BlobId, pub String, pub Vec<u8>);
+pub struct BlobCancelledEvent(pub BlobId, pub String);
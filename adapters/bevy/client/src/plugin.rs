@@ -14,8 +14,11 @@ use crate::systems::should_receive;
 
 use super::{
     events::{
-        DespawnEntityEvent, InsertComponentEvent, MessageEvent, RemoveComponentEvent,
-        SpawnEntityEvent, UpdateComponentEvent,
+        AuthorityDeniedEvent, AuthorityGrantedEvent, AuthorityRevokedEvent, BlobCancelledEvent,
+        BlobProgressEvent, BlobReceivedEvent, ComponentAuthorityGrantedEvent,
+        ComponentAuthorityRevokedEvent, DespawnEntityEvent, EntityHiddenEvent, EntityRevealedEvent,
+        InsertComponentEvent, MessageEvent, OwnershipGrantedEvent, OwnershipRevokedEvent,
+        RemoveComponentEvent, SpawnEntityEvent, UpdateComponentEvent,
     },
     resource::ClientResource,
     stage::{PrivateStage, Stage},
@@ -71,6 +74,18 @@ impl<P: Protocolize, C: ChannelIndex> PluginType for Plugin<P, C> {
             .add_event::<UpdateComponentEvent<P::Kind>>()
             .add_event::<RemoveComponentEvent<P>>()
             .add_event::<MessageEvent<P, C>>()
+            .add_event::<AuthorityGrantedEvent>()
+            .add_event::<AuthorityRevokedEvent>()
+            .add_event::<AuthorityDeniedEvent>()
+            .add_event::<ComponentAuthorityGrantedEvent<P::Kind>>()
+            .add_event::<ComponentAuthorityRevokedEvent<P::Kind>>()
+            .add_event::<OwnershipGrantedEvent>()
+            .add_event::<OwnershipRevokedEvent>()
+            .add_event::<EntityHiddenEvent>()
+            .add_event::<EntityRevealedEvent>()
+            .add_event::<BlobProgressEvent>()
+            .add_event::<BlobReceivedEvent>()
+            .add_event::<BlobCancelledEvent>()
             // STAGES //
             // events //
             .add_stage_before(
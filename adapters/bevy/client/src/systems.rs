@@ -14,8 +14,11 @@ use naia_client::{
 use naia_bevy_shared::WorldProxyMut;
 
 use crate::events::{
-    DespawnEntityEvent, InsertComponentEvent, MessageEvent, RemoveComponentEvent, SpawnEntityEvent,
-    UpdateComponentEvent,
+    AuthorityDeniedEvent, AuthorityGrantedEvent, AuthorityRevokedEvent, BlobCancelledEvent,
+    BlobProgressEvent, BlobReceivedEvent, ComponentAuthorityGrantedEvent,
+    ComponentAuthorityRevokedEvent, DespawnEntityEvent, EntityHiddenEvent, EntityRevealedEvent,
+    InsertComponentEvent, MessageEvent, OwnershipGrantedEvent, OwnershipRevokedEvent,
+    RemoveComponentEvent, SpawnEntityEvent, UpdateComponentEvent,
 };
 
 use super::resource::ClientResource;
@@ -44,6 +47,42 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
                 let mut message_event_writer = world
                     .get_resource_unchecked_mut::<Events<MessageEvent<P, C>>>()
                     .unwrap();
+                let mut authority_granted_event_writer = world
+                    .get_resource_unchecked_mut::<Events<AuthorityGrantedEvent>>()
+                    .unwrap();
+                let mut authority_revoked_event_writer = world
+                    .get_resource_unchecked_mut::<Events<AuthorityRevokedEvent>>()
+                    .unwrap();
+                let mut authority_denied_event_writer = world
+                    .get_resource_unchecked_mut::<Events<AuthorityDeniedEvent>>()
+                    .unwrap();
+                let mut component_authority_granted_event_writer = world
+                    .get_resource_unchecked_mut::<Events<ComponentAuthorityGrantedEvent<P::Kind>>>()
+                    .unwrap();
+                let mut component_authority_revoked_event_writer = world
+                    .get_resource_unchecked_mut::<Events<ComponentAuthorityRevokedEvent<P::Kind>>>()
+                    .unwrap();
+                let mut ownership_granted_event_writer = world
+                    .get_resource_unchecked_mut::<Events<OwnershipGrantedEvent>>()
+                    .unwrap();
+                let mut ownership_revoked_event_writer = world
+                    .get_resource_unchecked_mut::<Events<OwnershipRevokedEvent>>()
+                    .unwrap();
+                let mut entity_hidden_event_writer = world
+                    .get_resource_unchecked_mut::<Events<EntityHiddenEvent>>()
+                    .unwrap();
+                let mut entity_revealed_event_writer = world
+                    .get_resource_unchecked_mut::<Events<EntityRevealedEvent>>()
+                    .unwrap();
+                let mut blob_progress_event_writer = world
+                    .get_resource_unchecked_mut::<Events<BlobProgressEvent>>()
+                    .unwrap();
+                let mut blob_received_event_writer = world
+                    .get_resource_unchecked_mut::<Events<BlobReceivedEvent>>()
+                    .unwrap();
+                let mut blob_cancelled_event_writer = world
+                    .get_resource_unchecked_mut::<Events<BlobCancelledEvent>>()
+                    .unwrap();
 
                 for event_result in event_results {
                     match event_result {
@@ -80,6 +119,46 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
                             update_component_event_writer
                                 .send(UpdateComponentEvent(tick, entity, component));
                         }
+                        Ok(Event::AuthorityGranted(entity)) => {
+                            authority_granted_event_writer.send(AuthorityGrantedEvent(entity));
+                        }
+                        Ok(Event::AuthorityRevoked(entity)) => {
+                            authority_revoked_event_writer.send(AuthorityRevokedEvent(entity));
+                        }
+                        Ok(Event::AuthorityDenied(entity)) => {
+                            authority_denied_event_writer.send(AuthorityDeniedEvent(entity));
+                        }
+                        Ok(Event::ComponentAuthorityGranted(entity, component_kind)) => {
+                            component_authority_granted_event_writer
+                                .send(ComponentAuthorityGrantedEvent(entity, component_kind));
+                        }
+                        Ok(Event::ComponentAuthorityRevoked(entity, component_kind)) => {
+                            component_authority_revoked_event_writer
+                                .send(ComponentAuthorityRevokedEvent(entity, component_kind));
+                        }
+                        Ok(Event::OwnershipGranted(entity)) => {
+                            ownership_granted_event_writer.send(OwnershipGrantedEvent(entity));
+                        }
+                        Ok(Event::OwnershipRevoked(entity)) => {
+                            ownership_revoked_event_writer.send(OwnershipRevokedEvent(entity));
+                        }
+                        Ok(Event::EntityHidden(entity)) => {
+                            entity_hidden_event_writer.send(EntityHiddenEvent(entity));
+                        }
+                        Ok(Event::EntityRevealed(entity)) => {
+                            entity_revealed_event_writer.send(EntityRevealedEvent(entity));
+                        }
+                        Ok(Event::BlobProgress(blob_id, name, received, size)) => {
+                            blob_progress_event_writer
+                                .send(BlobProgressEvent(blob_id, name, received, size));
+                        }
+                        Ok(Event::BlobReceived(blob_id, name, bytes)) => {
+                            blob_received_event_writer
+                                .send(BlobReceivedEvent(blob_id, name, bytes));
+                        }
+                        Ok(Event::BlobCancelled(blob_id, name)) => {
+                            blob_cancelled_event_writer.send(BlobCancelledEvent(blob_id, name));
+                        }
                         Err(_) => {}
                     }
                 }
@@ -4,7 +4,7 @@ use bevy_ecs::entity::Entity;
 
 use naia_server::{
     shared::{ChannelIndex, Protocolize, Replicate, ReplicateSafe},
-    Server,
+    Server, UserKey,
 };
 
 use naia_bevy_shared::WorldMut;
@@ -84,3 +84,222 @@ impl<P: Protocolize, R: Replicate<P>, C: ChannelIndex> Command<P, C> for RemoveC
             .remove_component::<R>();
     }
 }
+
+//// Set Position ////
+
+pub(crate) struct SetPosition {
+    entity: Entity,
+    x: f32,
+    y: f32,
+}
+
+impl SetPosition {
+    pub fn new(entity: &Entity, x: f32, y: f32) -> Self {
+        SetPosition {
+            entity: *entity,
+            x,
+            y,
+        }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for SetPosition {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server
+            .entity_mut(world, &self.entity)
+            .set_position(self.x, self.y);
+    }
+}
+
+//// Give Authority ////
+
+pub(crate) struct GiveAuthority {
+    entity: Entity,
+    user_key: UserKey,
+}
+
+impl GiveAuthority {
+    pub fn new(entity: &Entity, user_key: &UserKey) -> Self {
+        GiveAuthority {
+            entity: *entity,
+            user_key: *user_key,
+        }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for GiveAuthority {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server
+            .entity_mut(world, &self.entity)
+            .give_authority(&self.user_key);
+    }
+}
+
+//// Take Authority ////
+
+pub(crate) struct TakeAuthority {
+    entity: Entity,
+}
+
+impl TakeAuthority {
+    pub fn new(entity: &Entity) -> Self {
+        TakeAuthority { entity: *entity }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for TakeAuthority {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server.entity_mut(world, &self.entity).take_authority();
+    }
+}
+
+//// Give Component Authority ////
+
+pub(crate) struct GiveComponentAuthority<P: Protocolize, R: ReplicateSafe<P>> {
+    entity: Entity,
+    user_key: UserKey,
+    phantom_p: PhantomData<P>,
+    phantom_r: PhantomData<R>,
+}
+
+impl<P: Protocolize, R: ReplicateSafe<P>> GiveComponentAuthority<P, R> {
+    pub fn new(entity: &Entity, user_key: &UserKey) -> Self {
+        GiveComponentAuthority {
+            entity: *entity,
+            user_key: *user_key,
+            phantom_p: PhantomData,
+            phantom_r: PhantomData,
+        }
+    }
+}
+
+impl<P: Protocolize, R: ReplicateSafe<P>, C: ChannelIndex> Command<P, C>
+    for GiveComponentAuthority<P, R>
+{
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server
+            .entity_mut(world, &self.entity)
+            .give_component_authority::<R>(&self.user_key);
+    }
+}
+
+//// Take Component Authority ////
+
+pub(crate) struct TakeComponentAuthority<P: Protocolize, R: ReplicateSafe<P>> {
+    entity: Entity,
+    phantom_p: PhantomData<P>,
+    phantom_r: PhantomData<R>,
+}
+
+impl<P: Protocolize, R: ReplicateSafe<P>> TakeComponentAuthority<P, R> {
+    pub fn new(entity: &Entity) -> Self {
+        TakeComponentAuthority {
+            entity: *entity,
+            phantom_p: PhantomData,
+            phantom_r: PhantomData,
+        }
+    }
+}
+
+impl<P: Protocolize, R: ReplicateSafe<P>, C: ChannelIndex> Command<P, C>
+    for TakeComponentAuthority<P, R>
+{
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server
+            .entity_mut(world, &self.entity)
+            .take_component_authority::<R>();
+    }
+}
+
+//// Set Delegation ////
+
+pub(crate) struct SetDelegation {
+    entity: Entity,
+    delegated: bool,
+}
+
+impl SetDelegation {
+    pub fn new(entity: &Entity, delegated: bool) -> Self {
+        SetDelegation {
+            entity: *entity,
+            delegated,
+        }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for SetDelegation {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        let mut entity_mut = server.entity_mut(world, &self.entity);
+        if self.delegated {
+            entity_mut.enable_delegation();
+        } else {
+            entity_mut.disable_delegation();
+        }
+    }
+}
+
+//// Set Dormant ////
+
+pub(crate) struct SetDormant {
+    entity: Entity,
+    is_dormant: bool,
+}
+
+impl SetDormant {
+    pub fn new(entity: &Entity, is_dormant: bool) -> Self {
+        SetDormant {
+            entity: *entity,
+            is_dormant,
+        }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for SetDormant {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server
+            .entity_mut(world, &self.entity)
+            .set_dormant(self.is_dormant);
+    }
+}
+
+//// Set Owner ////
+
+pub(crate) struct SetOwner {
+    entity: Entity,
+    user_key: UserKey,
+}
+
+impl SetOwner {
+    pub fn new(entity: &Entity, user_key: &UserKey) -> Self {
+        SetOwner {
+            entity: *entity,
+            user_key: *user_key,
+        }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for SetOwner {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server
+            .entity_mut(world, &self.entity)
+            .set_owner(&self.user_key);
+    }
+}
+
+//// Remove Owner ////
+
+pub(crate) struct RemoveOwner {
+    entity: Entity,
+}
+
+impl RemoveOwner {
+    pub fn new(entity: &Entity) -> Self {
+        RemoveOwner { entity: *entity }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for RemoveOwner {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server.entity_mut(world, &self.entity).remove_owner();
+    }
+}
@@ -2,11 +2,15 @@ use bevy_ecs::entity::Entity;
 
 use naia_server::{
     shared::{ChannelIndex, Protocolize, Replicate, ReplicateSafe},
-    RoomKey,
+    RoomKey, UserKey,
 };
 
 use super::{
-    commands::{DespawnEntity, InsertComponent, RemoveComponent},
+    commands::{
+        DespawnEntity, GiveAuthority, GiveComponentAuthority, InsertComponent, RemoveComponent,
+        RemoveOwner, SetDelegation, SetDormant, SetOwner, SetPosition, TakeAuthority,
+        TakeComponentAuthority,
+    },
     server::Server,
 };
 
@@ -49,6 +53,12 @@ impl<'s, 'world, 'state, P: Protocolize, C: ChannelIndex> EntityMut<'s, 'world,
 
     // Rooms
 
+    pub fn set_position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.server
+            .queue_command(SetPosition::new(&self.entity, x, y));
+        self
+    }
+
     pub fn enter_room(&mut self, room_key: &RoomKey) -> &mut Self {
         self.server.room_add_entity(room_key, &self.entity);
 
@@ -61,6 +71,67 @@ impl<'s, 'world, 'state, P: Protocolize, C: ChannelIndex> EntityMut<'s, 'world,
         self
     }
 
+    // Authority
+
+    pub fn give_authority(&mut self, user_key: &UserKey) -> &mut Self {
+        self.server
+            .queue_command(GiveAuthority::new(&self.entity, user_key));
+        self
+    }
+
+    pub fn take_authority(&mut self) -> &mut Self {
+        self.server.queue_command(TakeAuthority::new(&self.entity));
+        self
+    }
+
+    pub fn give_component_authority<R: ReplicateSafe<P>>(
+        &mut self,
+        user_key: &UserKey,
+    ) -> &mut Self {
+        self.server
+            .queue_command(GiveComponentAuthority::<P, R>::new(&self.entity, user_key));
+        self
+    }
+
+    pub fn take_component_authority<R: ReplicateSafe<P>>(&mut self) -> &mut Self {
+        self.server
+            .queue_command(TakeComponentAuthority::<P, R>::new(&self.entity));
+        self
+    }
+
+    pub fn enable_delegation(&mut self) -> &mut Self {
+        self.server
+            .queue_command(SetDelegation::new(&self.entity, true));
+        self
+    }
+
+    pub fn disable_delegation(&mut self) -> &mut Self {
+        self.server
+            .queue_command(SetDelegation::new(&self.entity, false));
+        self
+    }
+
+    // Dormancy
+
+    pub fn set_dormant(&mut self, is_dormant: bool) -> &mut Self {
+        self.server
+            .queue_command(SetDormant::new(&self.entity, is_dormant));
+        self
+    }
+
+    // Ownership
+
+    pub fn set_owner(&mut self, user_key: &UserKey) -> &mut Self {
+        self.server
+            .queue_command(SetOwner::new(&self.entity, user_key));
+        self
+    }
+
+    pub fn remove_owner(&mut self) -> &mut Self {
+        self.server.queue_command(RemoveOwner::new(&self.entity));
+        self
+    }
+
     // Exit
 
     pub fn server(&mut self) -> &mut Server<'world, 'state, P, C> {
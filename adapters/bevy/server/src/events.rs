@@ -1,5 +1,7 @@
+use bevy_ecs::entity::Entity;
+
 use naia_server::{
-    shared::{ChannelIndex, Protocolize},
+    shared::{BlobId, ChannelIndex, ProtocolKindType, Protocolize},
     User, UserKey,
 };
 
@@ -7,3 +9,18 @@ pub struct AuthorizationEvent<P: Protocolize>(pub UserKey, pub P);
 pub struct ConnectionEvent(pub UserKey);
 pub struct DisconnectionEvent(pub UserKey, pub User);
 pub struct MessageEvent<P: Protocolize, C: ChannelIndex>(pub UserKey, pub C, pub P);
+pub struct SpawnEntityEvent(pub UserKey, pub Entity);
+pub struct DespawnEntityEvent(pub UserKey, pub Entity);
+pub struct EntityOrphanedEvent(pub UserKey, pub Entity);
+pub struct InsertComponentEvent<K: ProtocolKindType>(pub UserKey, pub Entity, pub K);
+pub struct RemoveComponentEvent<P: Protocolize>(pub UserKey, pub Entity, pub P);
+pub struct UpdateComponentEvent<K: ProtocolKindType>(pub UserKey, pub Entity, pub K);
+pub struct AuthorityGrantedEvent(pub UserKey, pub Entity);
+pub struct AuthorityRevokedEvent(pub UserKey, pub Entity);
+pub struct AuthorityDeniedEvent(pub UserKey, pub Entity);
+pub struct ComponentAuthorityGrantedEvent<K: ProtocolKindType>(pub UserKey, pub Entity, pub K);
+pub struct ComponentAuthorityRevokedEvent<K: ProtocolKindType>(pub UserKey, pub Entity, pub K);
+pub struct CongestionDetectedEvent(pub UserKey);
+pub struct CongestionResolvedEvent(pub UserKey);
+pub struct BlobDeliveredEvent(pub UserKey, pub BlobId);
+pub struct BlobRejectedEvent(pub UserKey, pub BlobId);
@@ -11,7 +11,14 @@ use naia_server::{
 use naia_bevy_shared::WorldData;
 
 use super::{
-    events::{AuthorizationEvent, ConnectionEvent, DisconnectionEvent, MessageEvent},
+    events::{
+        AuthorityDeniedEvent, AuthorityGrantedEvent, AuthorityRevokedEvent, AuthorizationEvent,
+        BlobDeliveredEvent, BlobRejectedEvent, ComponentAuthorityGrantedEvent,
+        ComponentAuthorityRevokedEvent, CongestionDetectedEvent, CongestionResolvedEvent,
+        ConnectionEvent, DespawnEntityEvent, DisconnectionEvent, EntityOrphanedEvent,
+        InsertComponentEvent, MessageEvent, RemoveComponentEvent, SpawnEntityEvent,
+        UpdateComponentEvent,
+    },
     resource::ServerResource,
     stage::{PrivateStage, Stage},
     systems::{before_receive_events, finish_tick, should_receive, should_tick},
@@ -64,6 +71,21 @@ impl<P: Protocolize, C: ChannelIndex> PluginType for Plugin<P, C> {
             .add_event::<ConnectionEvent>()
             .add_event::<DisconnectionEvent>()
             .add_event::<MessageEvent<P, C>>()
+            .add_event::<SpawnEntityEvent>()
+            .add_event::<DespawnEntityEvent>()
+            .add_event::<EntityOrphanedEvent>()
+            .add_event::<InsertComponentEvent<P::Kind>>()
+            .add_event::<RemoveComponentEvent<P>>()
+            .add_event::<UpdateComponentEvent<P::Kind>>()
+            .add_event::<AuthorityGrantedEvent>()
+            .add_event::<AuthorityRevokedEvent>()
+            .add_event::<AuthorityDeniedEvent>()
+            .add_event::<ComponentAuthorityGrantedEvent<P::Kind>>()
+            .add_event::<ComponentAuthorityRevokedEvent<P::Kind>>()
+            .add_event::<CongestionDetectedEvent>()
+            .add_event::<CongestionResolvedEvent>()
+            .add_event::<BlobDeliveredEvent>()
+            .add_event::<BlobRejectedEvent>()
             // STAGES //
             .add_stage_before(
                 CoreStage::PreUpdate,
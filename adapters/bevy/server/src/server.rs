@@ -1,4 +1,7 @@
-use std::{collections::VecDeque, marker::PhantomData};
+use std::{
+    io::{self, Read, Seek},
+    marker::PhantomData,
+};
 
 use bevy_ecs::{
     entity::Entity,
@@ -7,9 +10,9 @@ use bevy_ecs::{
 };
 
 use naia_server::{
-    shared::{ChannelIndex, EntityHandleConverter, Protocolize, ReplicateSafe},
-    EntityRef, Event, NaiaServerError, RoomKey, RoomMut, RoomRef, Server as NaiaServer,
-    ServerAddrs, UserKey, UserMut, UserRef, UserScopeMut,
+    shared::{BlobId, ChannelIndex, EntityHandleConverter, Protocolize, ReplicateSafe},
+    EntityRef, RoomKey, RoomMut, RoomRef, Server as NaiaServer, ServerAddrs, UserKey, UserMut,
+    UserRef, UserScopeMut,
 };
 
 use crate::shared::EntityHandle;
@@ -44,10 +47,6 @@ impl<'world, 'state, P: Protocolize, C: ChannelIndex> Server<'world, 'state, P,
         }
     }
 
-    pub fn receive(&mut self) -> VecDeque<Result<Event<P, C>, NaiaServerError>> {
-        self.server.receive()
-    }
-
     //// Connections ////
 
     pub fn listen(&mut self, server_addrs: &ServerAddrs) {
@@ -72,12 +71,35 @@ impl<'world, 'state, P: Protocolize, C: ChannelIndex> Server<'world, 'state, P,
         self.server.send_message(user_key, channel, message)
     }
 
+    //// Blobs ////
+
+    pub fn send_blob(&mut self, user_key: &UserKey, name: &str, bytes: Vec<u8>) -> Option<BlobId> {
+        self.server.send_blob(user_key, name, bytes)
+    }
+
+    pub fn send_blob_source<R: Read + Seek + Send + Sync + 'static>(
+        &mut self,
+        user_key: &UserKey,
+        name: &str,
+        source: R,
+    ) -> io::Result<BlobId> {
+        self.server.send_blob_source(user_key, name, source)
+    }
+
     //// Updates ////
 
     pub fn scope_checks(&self) -> Vec<(RoomKey, UserKey, Entity)> {
         self.server.scope_checks()
     }
 
+    pub fn recheck_entity_scope(&mut self, entity: &Entity) {
+        self.server.recheck_entity_scope(entity);
+    }
+
+    pub fn recheck_user_scope(&mut self, user_key: &UserKey) {
+        self.server.recheck_user_scope(user_key);
+    }
+
     pub fn send_all_updates(&mut self) {
         return self.server.send_all_updates(self.world.proxy());
     }
@@ -102,6 +124,22 @@ impl<'world, 'state, P: Protocolize, C: ChannelIndex> Server<'world, 'state, P,
         return self.server.entities(self.world.proxy());
     }
 
+    pub fn entity_authority(&self, entity: &Entity) -> Option<UserKey> {
+        self.server.entity_authority(entity)
+    }
+
+    pub fn entity_owning_user(&self, entity: &Entity) -> Option<UserKey> {
+        self.server.entity_owning_user(entity)
+    }
+
+    pub fn entity_is_dormant(&self, entity: &Entity) -> bool {
+        self.server.entity_is_dormant(entity)
+    }
+
+    pub fn set_component_priority<R: ReplicateSafe<P>>(&mut self, priority: f32) {
+        self.server.set_component_priority::<R>(priority);
+    }
+
     //// Users ////
 
     pub fn user_exists(&self, user_key: &UserKey) -> bool {
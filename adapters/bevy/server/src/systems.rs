@@ -10,15 +10,24 @@ use naia_server::{
     Event, Server,
 };
 
+use naia_bevy_shared::WorldProxyMut;
+
 use super::{
-    events::{AuthorizationEvent, ConnectionEvent, DisconnectionEvent, MessageEvent},
+    events::{
+        AuthorityDeniedEvent, AuthorityGrantedEvent, AuthorityRevokedEvent, AuthorizationEvent,
+        BlobDeliveredEvent, BlobRejectedEvent, ComponentAuthorityGrantedEvent,
+        ComponentAuthorityRevokedEvent, CongestionDetectedEvent, CongestionResolvedEvent,
+        ConnectionEvent, DespawnEntityEvent, DisconnectionEvent, EntityOrphanedEvent,
+        InsertComponentEvent, MessageEvent, RemoveComponentEvent, SpawnEntityEvent,
+        UpdateComponentEvent,
+    },
     resource::ServerResource,
 };
 
 pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World) {
     world.resource_scope(|world, mut server: Mut<Server<P, Entity, C>>| {
         world.resource_scope(|world, mut server_resource: Mut<ServerResource>| {
-            let event_results = server.receive();
+            let event_results = server.receive(world.proxy_mut());
 
             unsafe {
                 let mut authorize_event_writer = world
@@ -33,6 +42,51 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
                 let mut message_event_writer = world
                     .get_resource_unchecked_mut::<Events<MessageEvent<P, C>>>()
                     .unwrap();
+                let mut spawn_entity_event_writer = world
+                    .get_resource_unchecked_mut::<Events<SpawnEntityEvent>>()
+                    .unwrap();
+                let mut despawn_entity_event_writer = world
+                    .get_resource_unchecked_mut::<Events<DespawnEntityEvent>>()
+                    .unwrap();
+                let mut entity_orphaned_event_writer = world
+                    .get_resource_unchecked_mut::<Events<EntityOrphanedEvent>>()
+                    .unwrap();
+                let mut insert_component_event_writer = world
+                    .get_resource_unchecked_mut::<Events<InsertComponentEvent<P::Kind>>>()
+                    .unwrap();
+                let mut remove_component_event_writer = world
+                    .get_resource_unchecked_mut::<Events<RemoveComponentEvent<P>>>()
+                    .unwrap();
+                let mut update_component_event_writer = world
+                    .get_resource_unchecked_mut::<Events<UpdateComponentEvent<P::Kind>>>()
+                    .unwrap();
+                let mut authority_granted_event_writer = world
+                    .get_resource_unchecked_mut::<Events<AuthorityGrantedEvent>>()
+                    .unwrap();
+                let mut authority_revoked_event_writer = world
+                    .get_resource_unchecked_mut::<Events<AuthorityRevokedEvent>>()
+                    .unwrap();
+                let mut authority_denied_event_writer = world
+                    .get_resource_unchecked_mut::<Events<AuthorityDeniedEvent>>()
+                    .unwrap();
+                let mut component_authority_granted_event_writer = world
+                    .get_resource_unchecked_mut::<Events<ComponentAuthorityGrantedEvent<P::Kind>>>()
+                    .unwrap();
+                let mut component_authority_revoked_event_writer = world
+                    .get_resource_unchecked_mut::<Events<ComponentAuthorityRevokedEvent<P::Kind>>>()
+                    .unwrap();
+                let mut congestion_detected_event_writer = world
+                    .get_resource_unchecked_mut::<Events<CongestionDetectedEvent>>()
+                    .unwrap();
+                let mut congestion_resolved_event_writer = world
+                    .get_resource_unchecked_mut::<Events<CongestionResolvedEvent>>()
+                    .unwrap();
+                let mut blob_delivered_event_writer = world
+                    .get_resource_unchecked_mut::<Events<BlobDeliveredEvent>>()
+                    .unwrap();
+                let mut blob_rejected_event_writer = world
+                    .get_resource_unchecked_mut::<Events<BlobRejectedEvent>>()
+                    .unwrap();
 
                 for event_result in event_results {
                     match event_result {
@@ -52,6 +106,70 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
                         Ok(Event::Message(user_key, channel, message)) => {
                             message_event_writer.send(MessageEvent(user_key, channel, message));
                         }
+                        Ok(Event::SpawnEntity(user_key, entity)) => {
+                            spawn_entity_event_writer.send(SpawnEntityEvent(user_key, entity));
+                        }
+                        Ok(Event::DespawnEntity(user_key, entity)) => {
+                            despawn_entity_event_writer.send(DespawnEntityEvent(user_key, entity));
+                        }
+                        Ok(Event::EntityOrphaned(user_key, entity)) => {
+                            entity_orphaned_event_writer
+                                .send(EntityOrphanedEvent(user_key, entity));
+                        }
+                        Ok(Event::InsertComponent(user_key, entity, component_kind)) => {
+                            insert_component_event_writer.send(InsertComponentEvent(
+                                user_key,
+                                entity,
+                                component_kind,
+                            ));
+                        }
+                        Ok(Event::RemoveComponent(user_key, entity, component)) => {
+                            remove_component_event_writer
+                                .send(RemoveComponentEvent(user_key, entity, component));
+                        }
+                        Ok(Event::UpdateComponent(user_key, entity, component_kind)) => {
+                            update_component_event_writer.send(UpdateComponentEvent(
+                                user_key,
+                                entity,
+                                component_kind,
+                            ));
+                        }
+                        Ok(Event::AuthorityGranted(user_key, entity)) => {
+                            authority_granted_event_writer
+                                .send(AuthorityGrantedEvent(user_key, entity));
+                        }
+                        Ok(Event::AuthorityRevoked(user_key, entity)) => {
+                            authority_revoked_event_writer
+                                .send(AuthorityRevokedEvent(user_key, entity));
+                        }
+                        Ok(Event::AuthorityDenied(user_key, entity)) => {
+                            authority_denied_event_writer
+                                .send(AuthorityDeniedEvent(user_key, entity));
+                        }
+                        Ok(Event::ComponentAuthorityGranted(user_key, entity, component_kind)) => {
+                            component_authority_granted_event_writer.send(
+                                ComponentAuthorityGrantedEvent(user_key, entity, component_kind),
+                            );
+                        }
+                        Ok(Event::ComponentAuthorityRevoked(user_key, entity, component_kind)) => {
+                            component_authority_revoked_event_writer.send(
+                                ComponentAuthorityRevokedEvent(user_key, entity, component_kind),
+                            );
+                        }
+                        Ok(Event::CongestionDetected(user_key)) => {
+                            congestion_detected_event_writer
+                                .send(CongestionDetectedEvent(user_key));
+                        }
+                        Ok(Event::CongestionResolved(user_key)) => {
+                            congestion_resolved_event_writer
+                                .send(CongestionResolvedEvent(user_key));
+                        }
+                        Ok(Event::BlobDelivered(user_key, blob_id)) => {
+                            blob_delivered_event_writer.send(BlobDeliveredEvent(user_key, blob_id));
+                        }
+                        Ok(Event::BlobRejected(user_key, blob_id)) => {
+                            blob_rejected_event_writer.send(BlobRejectedEvent(user_key, blob_id));
+                        }
                         Err(_) => {}
                     }
                 }
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use naia_shared::{blob_hash, BlobHash};
+
+/// Holds blobs by the hash of their contents, so that a blob the Server
+/// offers which is already here does not need to be downloaded. Every blob
+/// received from the Server is added, and it can be filled ahead of time
+/// with assets loaded from disk.
+#[derive(Default)]
+pub struct BlobCache {
+    blobs: HashMap<BlobHash, Vec<u8>>,
+}
+
+impl BlobCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a blob to the cache, returning the hash it is stored under
+    pub fn insert(&mut self, bytes: Vec<u8>) -> BlobHash {
+        let hash = blob_hash(&bytes);
+        self.blobs.insert(hash, bytes);
+        hash
+    }
+
+    pub fn get(&self, hash: &BlobHash) -> Option<&[u8]> {
+        self.blobs.get(hash).map(|bytes| bytes.as_slice())
+    }
+
+    pub fn contains(&self, hash: &BlobHash) -> bool {
+        self.blobs.contains_key(hash)
+    }
+
+    pub fn remove(&mut self, hash: &BlobHash) -> Option<Vec<u8>> {
+        self.blobs.remove(hash)
+    }
+
+    pub fn clear(&mut self) {
+        self.blobs.clear();
+    }
+
+    /// Gets the number of blobs in the cache
+    pub fn len(&self) -> usize {
+        self.blobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blobs.is_empty()
+    }
+}
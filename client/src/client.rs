@@ -1,17 +1,25 @@
-use std::{collections::VecDeque, hash::Hash, marker::PhantomData, net::SocketAddr};
+use std::{
+    collections::VecDeque, hash::Hash, marker::PhantomData, net::SocketAddr, time::Duration,
+};
 
 use naia_client_socket::Socket;
 
+use naia_shared::message_size_bytes;
 pub use naia_shared::{
     serde::{BitReader, BitWriter, Serde},
     ChannelIndex, ConnectionConfig, EntityHandle, EntityHandleConverter, PacketType, PingConfig,
-    PingIndex, ProtocolKindType, Protocolize, ReplicateSafe, SharedConfig, SocketConfig,
+    PingIndex, ProtocolKindType, Protocolize, Replicate, ReplicateSafe, SharedConfig, SocketConfig,
     StandardHeader, Tick, Timer, Timestamp, WorldMutType, WorldRefType,
 };
 
 use crate::{
+    blob_cache::BlobCache,
     connection::{connection::Connection, handshake_manager::HandshakeManager, io::Io},
-    protocol::entity_ref::EntityRef,
+    protocol::{
+        entity_ref::{EntityMut, EntityRef},
+        host_entity_manager::HostEntityManager,
+    },
+    replay::replay_recorder::ReplayRecorder,
     tick::tick_manager::TickManager,
 };
 
@@ -31,6 +39,10 @@ pub struct Client<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> {
     incoming_events: VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
     // Ticks
     tick_manager: Option<TickManager>,
+    // Replays
+    replay_recorder: Option<ReplayRecorder>,
+    // Blobs
+    blob_cache: BlobCache,
     // Phantom
     phantom_k: PhantomData<E>,
 }
@@ -59,6 +71,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
             incoming_events: VecDeque::new(),
             // Ticks
             tick_manager,
+            // Replays
+            replay_recorder: None,
+            // Blobs
+            blob_cache: BlobCache::new(),
             // Phantom
             phantom_k: PhantomData,
         }
@@ -84,6 +100,60 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
             .load(socket.packet_sender(), socket.packet_receiver());
     }
 
+    /// Connect to a capture made with `start_packet_capture` instead of a
+    /// Server. Packets are received at the same pace they originally arrived,
+    /// measured by the time passed to `advance_playback`. Nothing is sent
+    /// while playing back, and once the capture runs out the connection times
+    /// out as if the Server had gone silent. Returns an error if the capture
+    /// is truncated or corrupt.
+    pub fn connect_playback(&mut self, capture: &[u8]) -> Result<(), NaiaClientError> {
+        if !self.is_disconnected() {
+            panic!("Client has already initiated a connection, cannot initiate a new one. TIP: Check client.is_disconnected() before calling client.connect_playback()");
+        }
+        self.io
+            .load_playback(capture)
+            .map_err(|_| NaiaClientError::CorruptCapture)
+    }
+
+    /// Moves the playback clock forward, so that any captured packets which
+    /// arrived within the given time are received on the next call to
+    /// `receive`
+    pub fn advance_playback(&mut self, duration: Duration) {
+        self.io.advance_playback(duration);
+    }
+
+    /// Returns whether or not the client is playing back a capture, rather
+    /// than connected to a Server
+    pub fn is_playback(&self) -> bool {
+        self.io.is_playback()
+    }
+
+    /// Returns whether every packet of the capture being played back has been
+    /// received
+    pub fn is_playback_finished(&self) -> bool {
+        self.io.is_playback_finished()
+    }
+
+    /// Start capturing every packet received from the Server, along with when
+    /// it arrived, so that the session can be played back later with
+    /// `connect_playback`
+    pub fn start_packet_capture(&mut self) {
+        if !self.is_disconnected() {
+            panic!("Must call client.start_packet_capture() BEFORE calling client.connect(..)");
+        }
+        self.io.start_capture();
+    }
+
+    /// Stops capturing packets, returning the capture. Returns None if not
+    /// capturing.
+    pub fn stop_packet_capture(&mut self) -> Option<Vec<u8>> {
+        self.io.stop_capture()
+    }
+
+    pub fn is_capturing_packets(&self) -> bool {
+        self.io.is_capturing()
+    }
+
     /// Returns whether or not the client is disconnected
     pub fn is_disconnected(&self) -> bool {
         !self.io.is_loaded()
@@ -149,6 +219,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
                         &mut world,
                         receiving_tick,
                         &mut self.incoming_events,
+                        &mut self.replay_recorder,
                     );
                 }
             } else {
@@ -156,18 +227,29 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
                     &mut world,
                     0,
                     &mut self.incoming_events,
+                    &mut self.replay_recorder,
                 );
             }
 
             // receive messages
             let messages = server_connection.base.message_manager.receive_messages();
             for (channel, message) in messages {
+                if let Some(recorder) = &mut self.replay_recorder {
+                    recorder.record_message(&channel, &message, &server_connection.entity_manager);
+                }
                 self.incoming_events
                     .push_back(Ok(Event::Message(channel, message)));
             }
 
+            // receive blobs
+            server_connection.blob_receiver.receive_blobs(
+                &mut self.blob_cache,
+                self.client_config.max_blob_size,
+                &mut self.incoming_events,
+            );
+
             // send outgoing packets
-            server_connection.send_outgoing_packets(&mut self.io, &self.tick_manager);
+            server_connection.send_outgoing_packets(&mut self.io, &world, &self.tick_manager);
 
             // tick event
             if did_tick {
@@ -182,7 +264,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
 
     // Messages
 
-    /// Queues up an Message to be sent to the Server
+    /// Queues up an Message to be sent to the Server.
+    /// Panics if the Message is larger than the Channel's `max_message_size`.
     pub fn send_message<R: ReplicateSafe<P>>(&mut self, channel: C, message: &R) {
         let channel_settings = self.shared_config.channel.channel(&channel);
 
@@ -190,6 +273,12 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
             panic!("Cannot send message to Server on this Channel");
         }
 
+        if let Some(max_message_size) = channel_settings.max_message_size() {
+            if message_size_bytes(message) > max_message_size {
+                panic!("Message is too large for this Channel");
+            }
+        }
+
         let tick_buffered = channel_settings.tick_buffered();
 
         if tick_buffered {
@@ -214,22 +303,69 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
 
     // Entities
 
-    // /// Duplicates an Entity & all of it's Components
-    // pub fn duplicate_entity<W: WorldMutType<P, E>>(&self, mut world: W, entity: &E) -> EntityRef<P, E, W> {
-    //     let new_entity = world.duplicate_entity(entity);
-    //     return EntityRef::new(world, &new_entity);
-    // }
-
-    ///// Syncs the state of two entities
-    // pub fn mirror_entities<W: WorldMutType<P, E>>(&self, mut world: W, first_entity: &E, second_entity: &E) {
-    //     for component_kind in world.component_kinds(&first_entity) {
-    //         world.mirror_components(
-    //             &first_entity,
-    //             &second_entity,
-    //             &component_kind,
-    //         );
-    //     }
-    // }
+    /// Creates a new Entity, owned by the Client, and returns an EntityMut
+    /// which can be used for further operations on the Entity.
+    /// The Entity and its Components will be replicated to the Server.
+    /// Panics if a connection has not been established with the Server, or
+    /// if every NetEntity is in use. See `try_spawn_entity`.
+    pub fn spawn_entity<W: WorldMutType<P, E>>(&mut self, world: W) -> EntityMut<'_, P, E, W, C> {
+        self.try_spawn_entity(world)
+            .expect("cannot spawn any more Entities")
+    }
+
+    /// Creates a new Entity like `spawn_entity`, but returns an error rather
+    /// than panicking if every NetEntity is in use. Raise
+    /// `SharedConfig::net_entity_width` to address more Entities.
+    /// Panics if a connection has not been established with the Server.
+    pub fn try_spawn_entity<W: WorldMutType<P, E>>(
+        &mut self,
+        mut world: W,
+    ) -> Result<EntityMut<'_, P, E, W, C>, NaiaClientError> {
+        if !self
+            .server_connection
+            .as_ref()
+            .expect("cannot spawn entities unless connection is established")
+            .host_entity_manager
+            .has_net_entity_available()
+        {
+            return Err(NaiaClientError::NetEntitiesExhausted);
+        }
+
+        let entity = world.spawn_entity();
+        self.spawn_entity_init(&entity);
+
+        Ok(EntityMut::new(self, world, &entity))
+    }
+
+    /// Duplicates an Entity & all of it's Components into a new Entity owned
+    /// by the Client, which will be replicated to the Server.
+    /// Panics if a connection has not been established with the Server.
+    pub fn duplicate_entity<W: WorldMutType<P, E>>(
+        &mut self,
+        mut world: W,
+        entity: &E,
+    ) -> EntityMut<'_, P, E, W, C> {
+        let new_entity = world.duplicate_entity(entity);
+        self.spawn_entity_init(&new_entity);
+
+        let host_entity_manager = &mut self.server_connection.as_mut().unwrap().host_entity_manager;
+        for component_kind in world.component_kinds(&new_entity) {
+            host_entity_manager.insert_component(&new_entity, &component_kind);
+        }
+
+        EntityMut::new(self, world, &new_entity)
+    }
+
+    /// Syncs the state of two entities, setting the 1st Entity's Components
+    /// to the state of the 2nd Entity's Components
+    pub fn mirror_entities<W: WorldMutType<P, E>>(
+        &self,
+        mut world: W,
+        mutable_entity: &E,
+        immutable_entity: &E,
+    ) {
+        world.mirror_entities(mutable_entity, immutable_entity);
+    }
 
     /// Retrieves an EntityRef that exposes read-only operations for the
     /// given Entity.
@@ -238,19 +374,99 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
         EntityRef::new(world, entity)
     }
 
-    // /// Retrieves an EntityMut that exposes read and write operations for the
-    // /// Entity.
-    // /// Panics if the Entity does not exist.
-    // pub fn entity_mut<W: WorldMutType<P, E>>(
-    //     &mut self,
-    //     world: W,
-    //     entity: &E,
-    // ) -> EntityMut<P, E, W> {
-    //     if world.has_entity(entity) {
-    //         return EntityMut::new(world, &entity);
-    //     }
-    //     panic!("No Entity exists for given Key!");
-    // }
+    /// Retrieves an EntityMut that exposes read and write operations for the
+    /// Entity.
+    /// Panics if the Entity does not exist.
+    pub fn entity_mut<W: WorldMutType<P, E>>(
+        &mut self,
+        world: W,
+        entity: &E,
+    ) -> EntityMut<'_, P, E, W, C> {
+        if world.has_entity(entity) {
+            return EntityMut::new(self, world, entity);
+        }
+        panic!("No Entity exists for given Key!");
+    }
+
+    /// Returns whether or not the given Entity was spawned by the Client, and
+    /// is therefore replicated to the Server
+    pub fn entity_is_owned(&self, entity: &E) -> bool {
+        if let Some(connection) = &self.server_connection {
+            return connection.host_entity_manager.has_entity(entity);
+        }
+        false
+    }
+
+    /// Requests authority over an Entity owned by the Server. The Server will
+    /// respond with either an AuthorityGranted or AuthorityDenied Event.
+    /// Panics if a connection has not been established with the Server.
+    pub fn request_authority(&mut self, entity: &E) {
+        let connection = self
+            .server_connection
+            .as_mut()
+            .expect("Client must be connected to the Server to request authority");
+        let net_entity = connection
+            .entity_manager
+            .net_entity(entity)
+            .expect("cannot request authority over an Entity not owned by the Server");
+        connection
+            .host_entity_manager
+            .request_authority(&net_entity);
+    }
+
+    /// Gives up the Client's authority over an Entity, returning it to the
+    /// Server. Changes made to the Entity's Components will no longer be
+    /// replicated.
+    pub fn release_authority(&mut self, entity: &E) {
+        if let Some(connection) = &mut self.server_connection {
+            if !connection.entity_manager.has_authority(entity) {
+                return;
+            }
+            let net_entity = connection.entity_manager.net_entity(entity).unwrap();
+            connection.entity_manager.release_authority(entity);
+            connection
+                .host_entity_manager
+                .release_authority(&net_entity);
+        }
+    }
+
+    /// Returns whether or not the Client currently has authority over the
+    /// given Entity
+    pub fn has_authority(&self, entity: &E) -> bool {
+        if let Some(connection) = &self.server_connection {
+            return connection.entity_manager.has_authority(entity);
+        }
+        false
+    }
+
+    /// Returns whether or not the Client currently has authority over the
+    /// given Entity's Component, either over it alone or over the whole Entity
+    pub fn has_component_authority<R: ReplicateSafe<P>>(&self, entity: &E) -> bool {
+        if let Some(connection) = &self.server_connection {
+            return connection
+                .entity_manager
+                .has_component_authority(entity, &P::kind_of::<R>());
+        }
+        false
+    }
+
+    /// Returns whether or not the Client is currently the owner of the given
+    /// Entity
+    pub fn is_owner(&self, entity: &E) -> bool {
+        if let Some(connection) = &self.server_connection {
+            return connection.entity_manager.is_owner(entity);
+        }
+        false
+    }
+
+    /// Returns whether or not the given Entity has left the Client's scope
+    /// and is being kept hidden in case it comes back
+    pub fn entity_is_hidden(&self, entity: &E) -> bool {
+        if let Some(connection) = &self.server_connection {
+            return connection.entity_manager.entity_is_hidden(entity);
+        }
+        false
+    }
 
     /// Return a list of all Entities
     pub fn entities<W: WorldRefType<P, E>>(&self, world: &W) -> Vec<E> {
@@ -302,8 +518,135 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
         self.io.incoming_bandwidth()
     }
 
+    // Replays
+
+    /// Starts recording every Entity action, update & Message received from
+    /// the Server, replacing any recording already in progress. If connected,
+    /// the recording begins with every Entity received so far, so playback
+    /// starts from the current state of the World.
+    pub fn start_recording<W: WorldRefType<P, E>>(&mut self, world: W) {
+        let mut recorder = ReplayRecorder::new();
+        if let Some(server_connection) = &self.server_connection {
+            let server_tick = self
+                .tick_manager
+                .as_ref()
+                .map(|tick_manager| tick_manager.client_receiving_tick())
+                .unwrap_or(0);
+            recorder.record_keyframe(server_tick, &server_connection.entity_manager, &world);
+        }
+        self.replay_recorder = Some(recorder);
+    }
+
+    /// Stops recording, returning the replay so it can be saved & later
+    /// played back with a `ReplayPlayer`. Returns None if not recording.
+    pub fn stop_recording(&mut self) -> Option<Vec<u8>> {
+        self.replay_recorder
+            .take()
+            .map(|recorder| recorder.into_bytes())
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.replay_recorder.is_some()
+    }
+
+    // Blobs
+
+    /// Gets the cache of blobs received from the Server. Offered blobs which
+    /// are already in the cache are not downloaded again.
+    pub fn blob_cache(&self) -> &BlobCache {
+        &self.blob_cache
+    }
+
+    /// Gets the cache of blobs received from the Server, so that it can be
+    /// filled ahead of time or cleared out
+    pub fn blob_cache_mut(&mut self) -> &mut BlobCache {
+        &mut self.blob_cache
+    }
+
+    // Crate-Public methods
+
+    //// Entities
+
+    /// Despawns an Entity owned by the Client, and notifies the Server
+    pub(crate) fn despawn_entity<W: WorldMutType<P, E>>(&mut self, world: &mut W, entity: &E) {
+        if !world.has_entity(entity) {
+            panic!("attempted to de-spawn nonexistent entity");
+        }
+
+        self.host_entity_manager_mut(entity).despawn_entity(entity);
+
+        world.despawn_entity(entity);
+    }
+
+    //// Components
+
+    /// Adds a Component to an Entity owned by the Client
+    pub(crate) fn insert_component<R: ReplicateSafe<P>, W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        entity: &E,
+        component_ref: R,
+    ) {
+        if !world.has_entity(entity) {
+            panic!("attempted to add component to non-existent entity");
+        }
+
+        let component_kind = component_ref.kind();
+
+        if world.has_component_of_kind(entity, &component_kind) {
+            panic!(
+                "attempted to add component to entity which already has one of that type! \
+                   an entity is not allowed to have more than 1 type of component at a time."
+            )
+        }
+
+        self.host_entity_manager_mut(entity)
+            .insert_component(entity, &component_kind);
+
+        // actually insert component into world
+        world.insert_component(entity, component_ref);
+    }
+
+    /// Removes a Component from an Entity owned by the Client
+    pub(crate) fn remove_component<R: Replicate<P>, W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        entity: &E,
+    ) -> Option<R> {
+        let component_kind = P::kind_of::<R>();
+
+        self.host_entity_manager_mut(entity)
+            .remove_component(entity, &component_kind);
+
+        world.remove_component::<R>(entity)
+    }
+
     // internal functions
 
+    fn spawn_entity_init(&mut self, entity: &E) {
+        if !self
+            .server_connection
+            .as_mut()
+            .expect("cannot spawn entities unless connection is established")
+            .host_entity_manager
+            .spawn_entity(entity)
+        {
+            panic!("ran out of NetEntities, cannot spawn any more Entities");
+        }
+    }
+
+    fn host_entity_manager_mut(&mut self, entity: &E) -> &mut HostEntityManager<P, E> {
+        let host_entity_manager = &mut self
+            .server_connection
+            .as_mut()
+            .expect("cannot modify entities unless connection is established")
+            .host_entity_manager;
+        if !host_entity_manager.has_entity(entity) {
+            panic!("attempted to modify an Entity which is owned by the Server");
+        }
+        host_entity_manager
+    }
+
     fn maintain_socket(&mut self) {
         // get current tick
         if let Some(server_connection) = self.server_connection.as_mut() {
@@ -452,6 +795,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
                                     &self.client_config.connection,
                                     &self.shared_config.channel,
                                     &self.shared_config.tick_interval,
+                                    self.shared_config.net_entity_width,
                                 ));
                                 self.incoming_events
                                     .push_back(Ok(Event::Connection(server_addr)));
@@ -494,10 +838,12 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
             }
         };
 
-        self.io = Io::new(
+        let mut io = Io::new(
             &self.client_config.connection.bandwidth_measure_duration,
             &self.shared_config.compression,
         );
+        io.take_capture(&mut self.io);
+        self.io = io;
         self.server_connection = None;
         self.handshake_manager = HandshakeManager::new(self.client_config.send_handshake_interval);
         self.tick_manager = tick_manager;
@@ -16,6 +16,9 @@ pub struct ClientConfig {
     /// helpful early on in the connection, when estimates of latency are
     /// less accurate.
     pub minimum_latency: Option<Duration>,
+    /// The largest blob, in bytes, the Client will accept from the Server.
+    /// Larger blobs are refused before any of them is downloaded.
+    pub max_blob_size: usize,
 }
 
 impl Default for ClientConfig {
@@ -24,6 +27,7 @@ impl Default for ClientConfig {
             connection: ConnectionConfig::default(),
             send_handshake_interval: Duration::from_millis(250),
             minimum_latency: None,
+            max_blob_size: 64 * 1024 * 1024,
         }
     }
 }
@@ -0,0 +1,168 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use naia_shared::{
+    blob_hash,
+    serde::{BitReader, BitWriter},
+    BlobHash, BlobId, BlobMessage, BulkChannel, ChannelIndex, Instant, PacketIndex,
+    PacketNotifiable, Protocolize,
+};
+
+use crate::{blob_cache::BlobCache, error::NaiaClientError, event::Event};
+
+struct IncomingBlob {
+    name: String,
+    hash: BlobHash,
+    bytes: Vec<u8>,
+    received_bytes: usize,
+}
+
+/// Receives blobs from the Server over the BulkChannel, answering offers for
+/// blobs which are already cached without downloading them
+pub struct BlobReceiver {
+    bulk_channel: BulkChannel,
+    incoming_blobs: HashMap<BlobId, IncomingBlob>,
+}
+
+impl Default for BlobReceiver {
+    fn default() -> Self {
+        Self {
+            bulk_channel: BulkChannel::new(),
+            incoming_blobs: HashMap::new(),
+        }
+    }
+}
+
+impl BlobReceiver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Incoming
+
+    pub fn read_messages(&mut self, bit_reader: &mut BitReader) {
+        self.bulk_channel.read_messages(bit_reader);
+    }
+
+    /// Handles offers and chunks from the Server, emitting an Event for each
+    /// blob which progressed, was completed, or was cancelled
+    pub fn receive_blobs<P: Protocolize, E: Copy, C: ChannelIndex>(
+        &mut self,
+        cache: &mut BlobCache,
+        max_blob_size: usize,
+        incoming_events: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
+    ) {
+        let mut progressed_blobs = HashSet::new();
+
+        for message in self.bulk_channel.receive_messages() {
+            match message {
+                BlobMessage::Offer {
+                    blob_id,
+                    name,
+                    size,
+                    hash,
+                } => {
+                    if let Some(bytes) = cache.get(&hash) {
+                        self.bulk_channel
+                            .send_message(BlobMessage::Cached { blob_id });
+                        incoming_events.push_back(Ok(Event::BlobReceived(
+                            blob_id,
+                            name,
+                            bytes.to_vec(),
+                        )));
+                    } else if size as usize > max_blob_size {
+                        self.bulk_channel
+                            .send_message(BlobMessage::Cancel { blob_id });
+                        incoming_events.push_back(Ok(Event::BlobCancelled(blob_id, name)));
+                    } else if size == 0 {
+                        self.bulk_channel
+                            .send_message(BlobMessage::Cached { blob_id });
+                        incoming_events.push_back(Ok(Event::BlobReceived(
+                            blob_id,
+                            name,
+                            Vec::new(),
+                        )));
+                    } else {
+                        self.bulk_channel
+                            .send_message(BlobMessage::Accept { blob_id });
+                        self.incoming_blobs.insert(
+                            blob_id,
+                            IncomingBlob {
+                                name,
+                                hash,
+                                bytes: vec![0; size as usize],
+                                received_bytes: 0,
+                            },
+                        );
+                    }
+                }
+                BlobMessage::Chunk {
+                    blob_id,
+                    offset,
+                    bytes,
+                } => {
+                    if let Some(blob) = self.incoming_blobs.get_mut(&blob_id) {
+                        let start = offset as usize;
+                        let end = start + bytes.len();
+                        if end <= blob.bytes.len() {
+                            blob.bytes[start..end].copy_from_slice(&bytes);
+                            blob.received_bytes += bytes.len();
+                            progressed_blobs.insert(blob_id);
+                        }
+                    }
+                }
+                BlobMessage::Cancel { blob_id } => {
+                    if let Some(blob) = self.incoming_blobs.remove(&blob_id) {
+                        progressed_blobs.remove(&blob_id);
+                        incoming_events.push_back(Ok(Event::BlobCancelled(blob_id, blob.name)));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for blob_id in progressed_blobs {
+            let blob = self.incoming_blobs.get(&blob_id).unwrap();
+            if blob.received_bytes < blob.bytes.len() {
+                incoming_events.push_back(Ok(Event::BlobProgress(
+                    blob_id,
+                    blob.name.clone(),
+                    blob.received_bytes,
+                    blob.bytes.len(),
+                )));
+                continue;
+            }
+
+            let blob = self.incoming_blobs.remove(&blob_id).unwrap();
+            if blob_hash(&blob.bytes) != blob.hash {
+                incoming_events.push_back(Err(NaiaClientError::Message(format!(
+                    "blob `{}` does not match its hash",
+                    blob.name
+                ))));
+                continue;
+            }
+
+            cache.insert(blob.bytes.clone());
+            incoming_events.push_back(Ok(Event::BlobReceived(blob_id, blob.name, blob.bytes)));
+        }
+    }
+
+    // Outgoing
+
+    pub fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+        self.bulk_channel.collect_messages(now, rtt_millis);
+    }
+
+    pub fn has_outgoing_messages(&self) -> bool {
+        self.bulk_channel.has_outgoing_messages()
+    }
+
+    pub fn write_messages(&mut self, bit_writer: &mut BitWriter, packet_index: PacketIndex) {
+        self.bulk_channel.write_messages(bit_writer, packet_index);
+    }
+}
+
+impl PacketNotifiable for BlobReceiver {
+    fn notify_packet_delivered(&mut self, packet_index: PacketIndex) {
+        self.bulk_channel.notify_packet_delivered(packet_index);
+    }
+}
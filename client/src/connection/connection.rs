@@ -2,26 +2,30 @@ use std::{collections::VecDeque, hash::Hash, net::SocketAddr, time::Duration};
 
 use naia_shared::{
     serde::{BitReader, BitWriter, OwnedBitReader},
-    BaseConnection, ChannelConfig, ChannelIndex, ConnectionConfig, HostType, Instant, PacketType,
-    PingManager, ProtocolIo, Protocolize, StandardHeader, Tick, WorldMutType,
+    BaseConnection, ChannelConfig, ChannelIndex, ConnectionConfig, HostType, Instant,
+    NetEntityWidth, PacketNotifiable, PacketType, PingManager, ProtocolIo, Protocolize,
+    StandardHeader, Tick, WorldMutType, WorldRefType,
 };
 
 use crate::{
     error::NaiaClientError,
     event::Event,
-    protocol::entity_manager::EntityManager,
+    protocol::{entity_manager::EntityManager, host_entity_manager::HostEntityManager},
+    replay::replay_recorder::ReplayRecorder,
     tick::{
         tick_buffer_sender::TickBufferSender, tick_manager::TickManager, tick_queue::TickQueue,
     },
 };
 
-use super::io::Io;
+use super::{blob_receiver::BlobReceiver, io::Io};
 
 pub struct Connection<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> {
     pub base: BaseConnection<P, C>,
     pub entity_manager: EntityManager<P, E>,
+    pub host_entity_manager: HostEntityManager<P, E>,
     pub ping_manager: PingManager,
     pub tick_buffer: Option<TickBufferSender<P, C>>,
+    pub blob_receiver: BlobReceiver,
     jitter_buffer: TickQueue<OwnedBitReader>,
 }
 
@@ -31,6 +35,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
         connection_config: &ConnectionConfig,
         channel_config: &ChannelConfig<C>,
         tick_duration: &Option<Duration>,
+        net_entity_width: NetEntityWidth,
     ) -> Self {
         let tick_buffer = tick_duration
             .as_ref()
@@ -39,8 +44,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
         Connection {
             base: BaseConnection::new(address, HostType::Client, connection_config, channel_config),
             entity_manager: EntityManager::default(),
+            host_entity_manager: HostEntityManager::new(net_entity_width),
             ping_manager: PingManager::new(&connection_config.ping),
             tick_buffer,
+            blob_receiver: BlobReceiver::new(),
             jitter_buffer: TickQueue::new(),
         }
     }
@@ -48,12 +55,16 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
     // Incoming data
 
     pub fn process_incoming_header(&mut self, header: &StandardHeader) {
-        match &mut self.tick_buffer {
-            Some(tick_buffer) => self
-                .base
-                .process_incoming_header(header, &mut Some(tick_buffer)),
-            None => self.base.process_incoming_header(header, &mut None),
+        let mut packet_notifiables: Vec<&mut dyn PacketNotifiable> = vec![
+            &mut self.host_entity_manager,
+            &mut self.entity_manager,
+            &mut self.blob_receiver,
+        ];
+        if let Some(tick_buffer) = &mut self.tick_buffer {
+            packet_notifiables.push(tick_buffer);
         }
+        self.base
+            .process_incoming_header(header, &mut packet_notifiables);
     }
 
     pub fn buffer_data_packet(&mut self, incoming_tick: Tick, reader: &mut BitReader) {
@@ -66,6 +77,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
         world: &mut W,
         receiving_tick: Tick,
         incoming_events: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
+        replay_recorder: &mut Option<ReplayRecorder>,
     ) {
         while let Some((server_tick, owned_reader)) = self.jitter_buffer.pop_item(receiving_tick) {
             let mut bit_reader = owned_reader.borrow();
@@ -77,20 +89,34 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
                 .message_manager
                 .read_messages(&channel_reader, &mut bit_reader);
 
+            if let Some(recorder) = replay_recorder {
+                recorder.record_packet(server_tick, &bit_reader);
+            }
+
             // Read Entity Actions
             self.entity_manager
                 .read_all(world, server_tick, &mut bit_reader, incoming_events);
+
+            // Read Blobs
+            self.blob_receiver.read_messages(&mut bit_reader);
         }
     }
 
     // Outgoing data
 
-    pub fn send_outgoing_packets(&mut self, io: &mut Io, tick_manager_opt: &Option<TickManager>) {
-        self.collect_outgoing_messages(tick_manager_opt);
+    pub fn send_outgoing_packets<W: WorldRefType<P, E>>(
+        &mut self,
+        io: &mut Io,
+        world: &W,
+        tick_manager_opt: &Option<TickManager>,
+    ) {
+        let now = Instant::now();
+
+        self.collect_outgoing_messages(&now, tick_manager_opt);
 
         let mut any_sent = false;
         loop {
-            if self.send_outgoing_packet(io, tick_manager_opt) {
+            if self.send_outgoing_packet(&now, io, world, tick_manager_opt) {
                 any_sent = true;
             } else {
                 break;
@@ -101,12 +127,19 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
         }
     }
 
-    fn collect_outgoing_messages(&mut self, tick_manager_opt: &Option<TickManager>) {
-        let now = Instant::now();
-
+    fn collect_outgoing_messages(&mut self, now: &Instant, tick_manager_opt: &Option<TickManager>) {
         self.base
             .message_manager
-            .collect_outgoing_messages(&now, &self.ping_manager.rtt);
+            .collect_outgoing_messages(now, &self.ping_manager.rtt);
+
+        self.host_entity_manager
+            .collect_outgoing_actions(now, &self.ping_manager.rtt);
+
+        self.entity_manager
+            .collect_dropped_update_packets(&self.ping_manager.rtt);
+
+        self.blob_receiver
+            .collect_messages(now, &self.ping_manager.rtt);
 
         if let Some(tick_manager) = tick_manager_opt {
             self.tick_buffer
@@ -120,9 +153,11 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
     }
 
     // Sends packet and returns whether or not a packet was sent
-    fn send_outgoing_packet(
+    fn send_outgoing_packet<W: WorldRefType<P, E>>(
         &mut self,
+        now: &Instant,
         io: &mut Io,
+        world: &W,
         tick_manager_opt: &Option<TickManager>,
     ) -> bool {
         let tick_buffer_has_outgoing_messages = match &self.tick_buffer {
@@ -130,7 +165,12 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
             None => false,
         };
 
-        if self.base.message_manager.has_outgoing_messages() || tick_buffer_has_outgoing_messages {
+        if self.base.message_manager.has_outgoing_messages()
+            || tick_buffer_has_outgoing_messages
+            || self.host_entity_manager.has_outgoing_actions()
+            || self.entity_manager.has_outgoing_updates()
+            || self.blob_receiver.has_outgoing_messages()
+        {
             let next_packet_index = self.base.next_packet_index();
 
             let mut bit_writer = BitWriter::default();
@@ -162,6 +202,23 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
                 next_packet_index,
             );
 
+            // write entity actions
+            self.host_entity_manager.write_actions(
+                now,
+                &mut bit_writer,
+                &next_packet_index,
+                world,
+                &self.entity_manager,
+            );
+
+            // write entity updates
+            self.entity_manager
+                .write_updates(now, &mut bit_writer, &next_packet_index, world);
+
+            // write blob replies
+            self.blob_receiver
+                .write_messages(&mut bit_writer, next_packet_index);
+
             // send packet
             io.send_writer(&mut bit_writer);
 
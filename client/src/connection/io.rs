@@ -1,6 +1,7 @@
 use std::{net::SocketAddr, time::Duration};
 
 use naia_client_socket::{NaiaClientSocketError, PacketReceiver, PacketSender, ServerAddr};
+use naia_shared::serde::SerdeErr;
 pub use naia_shared::{
     serde::{BitReader, BitWriter},
     BandwidthMonitor, CompressionConfig, ConnectionConfig, Decoder, Encoder, PacketType,
@@ -8,6 +9,8 @@ pub use naia_shared::{
     WorldMutType, WorldRefType,
 };
 
+use super::packet_capture::{PacketCapture, PacketPlayback};
+
 pub struct Io {
     packet_sender: Option<PacketSender>,
     packet_receiver: Option<PacketReceiver>,
@@ -15,6 +18,8 @@ pub struct Io {
     incoming_bandwidth_monitor: Option<BandwidthMonitor>,
     outgoing_encoder: Option<Encoder>,
     incoming_decoder: Option<Decoder>,
+    packet_capture: Option<PacketCapture>,
+    packet_playback: Option<PacketPlayback>,
 }
 
 impl Io {
@@ -45,6 +50,8 @@ impl Io {
             incoming_bandwidth_monitor,
             outgoing_encoder,
             incoming_decoder,
+            packet_capture: None,
+            packet_playback: None,
         }
     }
 
@@ -57,8 +64,60 @@ impl Io {
         self.packet_receiver = Some(packet_receiver);
     }
 
+    /// Receives packets from a capture instead of a socket. Outgoing packets
+    /// are dropped.
+    pub fn load_playback(&mut self, capture: &[u8]) -> Result<(), SerdeErr> {
+        if self.is_loaded() {
+            panic!("Packet sender/receiver already loaded! Cannot do this twice!");
+        }
+
+        self.packet_playback = Some(PacketPlayback::new(capture)?);
+        Ok(())
+    }
+
     pub fn is_loaded(&self) -> bool {
-        self.packet_sender.is_some()
+        self.packet_sender.is_some() || self.packet_playback.is_some()
+    }
+
+    pub fn is_playback(&self) -> bool {
+        self.packet_playback.is_some()
+    }
+
+    pub fn is_playback_finished(&self) -> bool {
+        self.packet_playback
+            .as_ref()
+            .map(|playback| playback.is_finished())
+            .unwrap_or(false)
+    }
+
+    pub fn advance_playback(&mut self, duration: Duration) {
+        self.packet_playback
+            .as_mut()
+            .expect(
+                "Cannot call Client.advance_playback() until you call Client.connect_playback()!",
+            )
+            .advance(duration);
+    }
+
+    // Packet capture
+
+    pub fn start_capture(&mut self) {
+        self.packet_capture = Some(PacketCapture::new());
+    }
+
+    pub fn stop_capture(&mut self) -> Option<Vec<u8>> {
+        self.packet_capture
+            .take()
+            .map(|capture| capture.into_bytes())
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.packet_capture.is_some()
+    }
+
+    /// Continues a capture started on another Io, which is being replaced
+    pub fn take_capture(&mut self, other: &mut Io) {
+        self.packet_capture = other.packet_capture.take();
     }
 
     pub fn send_writer(&mut self, writer: &mut BitWriter) {
@@ -66,6 +125,11 @@ impl Io {
         let (length, buffer) = writer.flush();
         let mut payload = &buffer[0..length];
 
+        // there is nobody to send to during playback
+        if self.packet_playback.is_some() {
+            return;
+        }
+
         // Compression
         if let Some(encoder) = &mut self.outgoing_encoder {
             payload = encoder.encode(payload);
@@ -83,6 +147,23 @@ impl Io {
     }
 
     pub fn recv_reader(&mut self) -> Result<Option<BitReader>, NaiaClientSocketError> {
+        if let Some(playback) = &mut self.packet_playback {
+            let payload_opt = playback.receive().map(|mut payload| {
+                // Bandwidth monitoring
+                if let Some(monitor) = &mut self.incoming_bandwidth_monitor {
+                    monitor.record_packet(payload.len());
+                }
+
+                // Decompression
+                if let Some(decoder) = &mut self.incoming_decoder {
+                    payload = decoder.decode(payload);
+                }
+
+                BitReader::new(payload)
+            });
+            return Ok(payload_opt);
+        }
+
         let receive_result = self
             .packet_receiver
             .as_mut()
@@ -90,6 +171,12 @@ impl Io {
             .receive();
 
         if let Ok(Some(mut payload)) = receive_result {
+            // Packet capture
+            if let Some(capture) = &mut self.packet_capture {
+                let server_addr = self.packet_sender.as_ref().unwrap().server_addr();
+                capture.record(server_addr, payload);
+            }
+
             // Bandwidth monitoring
             if let Some(monitor) = &mut self.incoming_bandwidth_monitor {
                 monitor.record_packet(payload.len());
@@ -107,6 +194,10 @@ impl Io {
     }
 
     pub fn server_addr_unwrapped(&self) -> SocketAddr {
+        if let Some(playback) = &self.packet_playback {
+            return playback.server_addr();
+        }
+
         if let ServerAddr::Found(server_addr) = self
             .packet_sender
             .as_ref()
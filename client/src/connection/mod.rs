@@ -1,4 +1,6 @@
+pub mod blob_receiver;
 #[allow(clippy::module_inception)]
 pub mod connection;
 pub mod handshake_manager;
 pub mod io;
+pub mod packet_capture;
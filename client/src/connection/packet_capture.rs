@@ -0,0 +1,176 @@
+use std::{collections::VecDeque, net::SocketAddr, time::Duration};
+
+use naia_client_socket::ServerAddr;
+use naia_shared::{
+    serde::{BitReader, BitVecWriter, BitWrite, Serde, SerdeErr, UnsignedVariableInteger},
+    Instant,
+};
+
+/// Records every packet received from the Server, along with when it arrived
+pub struct PacketCapture {
+    writer: BitVecWriter,
+    start: Instant,
+    last_elapsed_millis: u64,
+    server_addr: Option<SocketAddr>,
+}
+
+impl Default for PacketCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PacketCapture {
+    pub fn new() -> Self {
+        Self {
+            writer: BitVecWriter::new(),
+            start: Instant::now(),
+            last_elapsed_millis: 0,
+            server_addr: None,
+        }
+    }
+
+    /// Records a packet exactly as it came off the socket, before it is
+    /// decompressed
+    pub fn record(&mut self, server_addr: ServerAddr, payload: &[u8]) {
+        if let ServerAddr::Found(server_addr) = server_addr {
+            self.server_addr = Some(server_addr);
+        }
+
+        let elapsed_millis = self.start.elapsed().as_millis() as u64;
+        let elapsed_millis_diff = elapsed_millis - self.last_elapsed_millis;
+        self.last_elapsed_millis = elapsed_millis;
+
+        // continue bit
+        true.ser(&mut self.writer);
+
+        UnsignedVariableInteger::<7>::new(elapsed_millis_diff).ser(&mut self.writer);
+        UnsignedVariableInteger::<7>::new(payload.len() as u64).ser(&mut self.writer);
+        for byte in payload {
+            self.writer.write_byte(*byte);
+        }
+    }
+
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        // finish packets
+        false.ser(&mut self.writer);
+
+        self.server_addr
+            .map(|server_addr| server_addr.to_string())
+            .ser(&mut self.writer);
+
+        self.writer.to_bytes()
+    }
+}
+
+/// Hands out packets from a `PacketCapture`, each one no earlier than it
+/// originally arrived relative to the start of playback. Playback time only
+/// moves forward when `advance` is called.
+pub struct PacketPlayback {
+    packets: VecDeque<(Duration, Box<[u8]>)>,
+    current_packet: Option<Box<[u8]>>,
+    server_addr: SocketAddr,
+    elapsed: Duration,
+}
+
+impl PacketPlayback {
+    pub fn new(capture: &[u8]) -> Result<Self, SerdeErr> {
+        let mut reader = BitReader::new(capture);
+
+        let mut packets = VecDeque::new();
+        let mut arrival = Duration::ZERO;
+        while bool::de(&mut reader)? {
+            let elapsed_millis_diff = UnsignedVariableInteger::<7>::de(&mut reader)?.get();
+            arrival += Duration::from_millis(elapsed_millis_diff as u64);
+
+            let length = UnsignedVariableInteger::<7>::de(&mut reader)?.get() as usize;
+            if length.saturating_mul(8) > reader.bits_remaining() {
+                return Err(SerdeErr {});
+            }
+            let mut payload = Vec::with_capacity(length);
+            for _ in 0..length {
+                payload.push(u8::de(&mut reader)?);
+            }
+
+            packets.push_back((arrival, payload.into_boxed_slice()));
+        }
+
+        let server_addr = Option::<String>::de(&mut reader)?
+            .and_then(|server_addr| server_addr.parse().ok())
+            .unwrap_or_else(|| SocketAddr::from(([0, 0, 0, 0], 0)));
+
+        Ok(Self {
+            packets,
+            current_packet: None,
+            server_addr,
+            elapsed: Duration::ZERO,
+        })
+    }
+
+    /// Moves playback time forward, making due any packets which arrived
+    /// within that time
+    pub fn advance(&mut self, duration: Duration) {
+        self.elapsed += duration;
+    }
+
+    pub fn server_addr(&self) -> SocketAddr {
+        self.server_addr
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.packets.is_empty()
+    }
+
+    /// Returns the next packet, if it is due
+    pub fn receive(&mut self) -> Option<&[u8]> {
+        let (arrival, _) = self.packets.front()?;
+        if *arrival > self.elapsed {
+            return None;
+        }
+
+        let (_, payload) = self.packets.pop_front().unwrap();
+        self.current_packet = Some(payload);
+        self.current_packet.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread::sleep, time::Duration};
+
+    use naia_client_socket::ServerAddr;
+
+    use super::{PacketCapture, PacketPlayback};
+
+    fn capture() -> Vec<u8> {
+        let server_addr = ServerAddr::Found("127.0.0.1:14191".parse().unwrap());
+        let mut capture = PacketCapture::new();
+        capture.record(server_addr, &[1, 2, 3]);
+        sleep(Duration::from_millis(50));
+        capture.record(server_addr, &[4, 5]);
+        capture.into_bytes()
+    }
+
+    #[test]
+    fn packets_are_due_as_playback_advances() {
+        let mut playback = PacketPlayback::new(&capture()).unwrap();
+        assert_eq!(playback.server_addr(), "127.0.0.1:14191".parse().unwrap());
+
+        assert_eq!(playback.receive(), Some(&[1, 2, 3][..]));
+        assert_eq!(playback.receive(), None);
+
+        // however long it really takes, only advancing makes the packet due
+        sleep(Duration::from_millis(60));
+        assert_eq!(playback.receive(), None);
+
+        playback.advance(Duration::from_millis(60));
+        assert_eq!(playback.receive(), Some(&[4, 5][..]));
+        assert!(playback.is_finished());
+    }
+
+    #[test]
+    fn truncated_capture_is_rejected() {
+        let capture = capture();
+        assert!(PacketPlayback::new(&capture[..capture.len() - 4]).is_err());
+    }
+}
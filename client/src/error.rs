@@ -4,6 +4,13 @@ use std::{error::Error, fmt};
 pub enum NaiaClientError {
     Message(String),
     Wrapped(Box<dyn Error + Send>),
+    /// Every NetEntity is in use, so no more Entities can be spawned until
+    /// others are despawned
+    NetEntitiesExhausted,
+    /// A replay could not be read, because it is truncated or corrupt
+    CorruptReplay,
+    /// A packet capture could not be read, because it is truncated or corrupt
+    CorruptCapture,
 }
 
 impl fmt::Display for NaiaClientError {
@@ -11,6 +18,18 @@ impl fmt::Display for NaiaClientError {
         match self {
             NaiaClientError::Message(msg) => write!(f, "Naia Client Error: {}", msg),
             NaiaClientError::Wrapped(boxed_err) => fmt::Display::fmt(boxed_err.as_ref(), f),
+            NaiaClientError::NetEntitiesExhausted => {
+                write!(f, "Naia Client Error: ran out of NetEntities")
+            }
+            NaiaClientError::CorruptReplay => {
+                write!(f, "Naia Client Error: replay is truncated or corrupt")
+            }
+            NaiaClientError::CorruptCapture => {
+                write!(
+                    f,
+                    "Naia Client Error: packet capture is truncated or corrupt"
+                )
+            }
         }
     }
 }
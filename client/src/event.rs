@@ -1,6 +1,6 @@
 use std::net::SocketAddr;
 
-use naia_shared::{ChannelIndex, Protocolize, Tick};
+use naia_shared::{BlobId, ChannelIndex, Protocolize, Tick};
 
 /// An Event that is be emitted by the Client, usually as a result of some
 /// communication with the Server
@@ -28,4 +28,43 @@ pub enum Event<P: Protocolize, E: Copy, C: ChannelIndex> {
     RemoveComponent(E, P),
     /// A Message emitted to the Client from the Server
     Message(C, P),
+    /// Occurs when the Server has given the Client authority over an Entity.
+    /// Changes the Client makes to the Entity's Components will be replicated
+    /// to the Server until authority is revoked.
+    AuthorityGranted(E),
+    /// Occurs when the Client's authority over an Entity has been taken away
+    /// by the Server
+    AuthorityRevoked(E),
+    /// Occurs when the Server has denied the Client's request for authority
+    /// over an Entity
+    AuthorityDenied(E),
+    /// Occurs when the Server has given the Client authority over a single
+    /// Component of an Entity. Changes the Client makes to it will be
+    /// replicated to the Server until authority is revoked.
+    ComponentAuthorityGranted(E, P::Kind),
+    /// Occurs when the Client's authority over a single Component of an
+    /// Entity has been taken away by the Server
+    ComponentAuthorityRevoked(E, P::Kind),
+    /// Occurs when the Server has made the Client the owner of an Entity.
+    /// Properties only replicated to the owner are sent from then on.
+    OwnershipGranted(E),
+    /// Occurs when the Client is no longer the owner of an Entity
+    OwnershipRevoked(E),
+    /// Occurs when an Entity has left the Client's scope, but is being kept
+    /// in case it comes back soon. The Entity stays in the World, without
+    /// receiving updates, until it is either revealed or despawned.
+    EntityHidden(E),
+    /// Occurs when a hidden Entity has come back into the Client's scope.
+    /// Any changes made while it was hidden arrive as regular updates.
+    EntityRevealed(E),
+    /// Occurs as a blob sent by the Server is downloaded, with its name, the
+    /// number of bytes received so far, and its total size in bytes
+    BlobProgress(BlobId, String, usize, usize),
+    /// Occurs when a blob sent by the Server has been received in full, with
+    /// its name and contents. The contents come from the blob cache if a
+    /// matching blob was already there.
+    BlobReceived(BlobId, String, Vec<u8>),
+    /// Occurs when a blob transfer has been cancelled, either by the Server
+    /// or because the blob is larger than `ClientConfig::max_blob_size`
+    BlobCancelled(BlobId, String),
 }
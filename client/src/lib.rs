@@ -12,6 +12,7 @@
 
 pub use naia_shared as shared;
 
+mod blob_cache;
 mod client;
 mod client_config;
 mod command_history;
@@ -20,14 +21,17 @@ mod constants;
 mod error;
 mod event;
 mod protocol;
+mod replay;
 mod tick;
 
+pub use blob_cache::BlobCache;
 pub use client::Client;
 pub use client_config::ClientConfig;
 pub use command_history::CommandHistory;
 pub use error::NaiaClientError;
 pub use event::Event;
-pub use protocol::entity_ref::EntityRef;
+pub use protocol::entity_ref::{EntityMut, EntityRef};
+pub use replay::replay_player::ReplayPlayer;
 
 pub mod internal {
     pub use crate::connection::handshake_manager::{HandshakeManager, HandshakeState};
@@ -0,0 +1,27 @@
+use std::sync::{Arc, RwLock};
+
+use naia_shared::{DiffMask, PropertyMutate};
+
+/// Records which Properties of a Component have changed on the Client, so that
+/// they can be sent to the Server while the Client has authority over the
+/// Component's Entity
+#[derive(Clone)]
+pub struct ComponentMutator {
+    diff_mask: Arc<RwLock<DiffMask>>,
+}
+
+impl ComponentMutator {
+    pub fn new(diff_mask: &Arc<RwLock<DiffMask>>) -> Self {
+        Self {
+            diff_mask: diff_mask.clone(),
+        }
+    }
+}
+
+impl PropertyMutate for ComponentMutator {
+    fn mutate(&mut self, property_index: u8) {
+        if let Ok(mut diff_mask) = self.diff_mask.write() {
+            diff_mask.set_bit(property_index, true);
+        }
+    }
+}
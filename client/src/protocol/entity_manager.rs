@@ -1,19 +1,24 @@
 use std::{
     collections::{HashMap, VecDeque},
     hash::Hash,
+    sync::{Arc, RwLock},
+    time::Duration,
 };
 
 use naia_shared::{
     message_list_header,
-    serde::{BitReader, Serde, UnsignedVariableInteger},
-    BigMap, ChannelIndex, EntityAction, EntityActionReceiver, EntityActionType, EntityHandle,
-    EntityHandleConverter, MessageId, NetEntity, NetEntityHandleConverter, Protocolize, Tick,
-    WorldMutType,
+    serde::{BitCounter, BitReader, BitWrite, BitWriter, Serde, SerdeErr, UnsignedVariableInteger},
+    BigMap, ChannelIndex, DiffMask, EntityAction, EntityActionReceiver, EntityActionType,
+    EntityHandle, EntityHandleConverter, Instant, MessageId, NetEntity, NetEntityHandleConverter,
+    PacketIndex, PacketNotifiable, PropertyMutator, ProtocolInserter, Protocolize, ReplicateSafe,
+    StaticMutator, Tick, WorldMutType, WorldRefType, MTU_SIZE_BITS,
 };
 
 use crate::{error::NaiaClientError, event::Event};
 
-use super::entity_record::EntityRecord;
+use super::{component_mutator::ComponentMutator, entity_record::EntityRecord};
+
+const DROP_UPDATE_RTT_FACTOR: f32 = 1.5;
 
 pub struct EntityManager<P: Protocolize, E: Copy + Eq + Hash> {
     entity_records: HashMap<E, EntityRecord<P::Kind>>,
@@ -21,6 +26,9 @@ pub struct EntityManager<P: Protocolize, E: Copy + Eq + Hash> {
     pub handle_entity_map: BigMap<EntityHandle, E>,
     receiver: EntityActionReceiver<NetEntity, P::Kind>,
     received_components: HashMap<(NetEntity, P::Kind), P>,
+    #[allow(clippy::type_complexity)]
+    sent_updates: HashMap<PacketIndex, (Instant, HashMap<(E, P::Kind), DiffMask>)>,
+    last_update_packet_index: PacketIndex,
 }
 
 impl<P: Protocolize, E: Copy + Eq + Hash> Default for EntityManager<P, E> {
@@ -31,6 +39,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash> Default for EntityManager<P, E> {
             handle_entity_map: BigMap::default(),
             receiver: EntityActionReceiver::default(),
             received_components: HashMap::default(),
+            sent_updates: HashMap::default(),
+            last_update_packet_index: 0,
         }
     }
 }
@@ -72,7 +82,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
         event_stream: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
     ) {
         let mut last_read_id: Option<MessageId> = None;
-        let action_count = message_list_header::read(reader);
+        let action_count = message_list_header::read(reader).unwrap();
         for _ in 0..action_count {
             self.read_action(reader, &mut last_read_id);
         }
@@ -94,7 +104,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
                 let components_num = UnsignedVariableInteger::<3>::de(reader).unwrap().get();
                 let mut component_kinds = Vec::new();
                 for _ in 0..components_num {
-                    let new_component = P::read(reader, self);
+                    let new_component = P::read(reader, self).unwrap();
                     let new_component_kind = new_component.dyn_ref().kind();
                     self.received_components
                         .insert((net_entity, new_component_kind), new_component);
@@ -118,7 +128,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
             EntityActionType::InsertComponent => {
                 // read all data
                 let net_entity = NetEntity::de(reader).unwrap();
-                let new_component = P::read(reader, self);
+                let new_component = P::read(reader, self).unwrap();
                 let new_component_kind = new_component.dyn_ref().kind();
 
                 self.receiver.buffer_action(
@@ -139,7 +149,79 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
                     EntityAction::RemoveComponent(net_entity, component_kind),
                 );
             }
-            EntityActionType::Noop => {
+            // Authority over Entity granted to Client
+            EntityActionType::GrantAuthority => {
+                let net_entity = NetEntity::de(reader).unwrap();
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::GrantAuthority(net_entity));
+            }
+            // Authority over Entity taken back by Server
+            EntityActionType::RevokeAuthority => {
+                let net_entity = NetEntity::de(reader).unwrap();
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::RevokeAuthority(net_entity));
+            }
+            // Request for authority over Entity denied
+            EntityActionType::DenyAuthority => {
+                let net_entity = NetEntity::de(reader).unwrap();
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::DenyAuthority(net_entity));
+            }
+            // Authority over one Component of Entity granted to Client
+            EntityActionType::GrantComponentAuthority => {
+                let net_entity = NetEntity::de(reader).unwrap();
+                let component_kind = P::Kind::de(reader).unwrap();
+
+                self.receiver.buffer_action(
+                    action_id,
+                    EntityAction::GrantComponentAuthority(net_entity, component_kind),
+                );
+            }
+            // Authority over one Component of Entity taken back by Server
+            EntityActionType::RevokeComponentAuthority => {
+                let net_entity = NetEntity::de(reader).unwrap();
+                let component_kind = P::Kind::de(reader).unwrap();
+
+                self.receiver.buffer_action(
+                    action_id,
+                    EntityAction::RevokeComponentAuthority(net_entity, component_kind),
+                );
+            }
+            // Ownership of Entity given to Client
+            EntityActionType::GrantOwnership => {
+                let net_entity = NetEntity::de(reader).unwrap();
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::GrantOwnership(net_entity));
+            }
+            // Ownership of Entity taken away from Client
+            EntityActionType::RevokeOwnership => {
+                let net_entity = NetEntity::de(reader).unwrap();
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::RevokeOwnership(net_entity));
+            }
+            // Entity left scope, but is kept by the Client
+            EntityActionType::HideEntity => {
+                let net_entity = NetEntity::de(reader).unwrap();
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::HideEntity(net_entity));
+            }
+            // Hidden Entity came back into scope
+            EntityActionType::RevealEntity => {
+                let net_entity = NetEntity::de(reader).unwrap();
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::RevealEntity(net_entity));
+            }
+            // Only ever sent by the Client
+            EntityActionType::RequestAuthority
+            | EntityActionType::ReleaseAuthority
+            | EntityActionType::Noop => {
                 self.receiver.buffer_action(action_id, EntityAction::Noop);
             }
         }
@@ -179,7 +261,13 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
 
                         entity_record.component_kinds.insert(component_kind);
 
-                        component.extract_and_insert(&world_entity, world);
+                        component.extract_and_insert(
+                            &world_entity,
+                            &mut ComponentInserter {
+                                world,
+                                entity_record: &mut entity_record,
+                            },
+                        );
 
                         event_stream
                             .push_back(Ok(Event::InsertComponent(world_entity, component_kind)));
@@ -192,26 +280,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
                     //let e_u16: u16 = net_entity.into();
                     //info!("despawn entity: {}", e_u16);
 
-                    if let Some(world_entity) = self.local_to_world_entity.remove(&net_entity) {
-                        if self.entity_records.remove(&world_entity).is_none() {
-                            panic!("despawning an uninitialized entity");
-                        }
-
-                        // Generate event for each component, handing references off just in
-                        // case
-                        for component_kind in world.component_kinds(&world_entity) {
-                            if let Some(component) =
-                                world.remove_component_of_kind(&world_entity, &component_kind)
-                            {
-                                event_stream
-                                    .push_back(Ok(Event::RemoveComponent(world_entity, component)));
-                            }
-                        }
-
-                        world.despawn_entity(&world_entity);
-
-                        event_stream.push_back(Ok(Event::DespawnEntity(world_entity)));
-                    } else {
+                    if !self.despawn_entity(world, &net_entity, event_stream) {
                         panic!("received message attempting to delete nonexistent entity");
                     }
                 }
@@ -227,7 +296,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
                     if !self.local_to_world_entity.contains_key(&net_entity) {
                         panic!(
                             "attempting to add a component to nonexistent entity: {}",
-                            Into::<u16>::into(net_entity)
+                            Into::<u32>::into(net_entity)
                         );
                     } else {
                         let world_entity = self.local_to_world_entity.get(&net_entity).unwrap();
@@ -236,7 +305,13 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
 
                         entity_record.component_kinds.insert(component_kind);
 
-                        component.extract_and_insert(world_entity, world);
+                        component.extract_and_insert(
+                            world_entity,
+                            &mut ComponentInserter {
+                                world,
+                                entity_record,
+                            },
+                        );
 
                         event_stream
                             .push_back(Ok(Event::InsertComponent(*world_entity, component_kind)));
@@ -255,6 +330,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
                         .get_mut(world_entity)
                         .expect("attempting to delete component of nonexistent entity");
                     if entity_record.component_kinds.remove(&component_kind) {
+                        entity_record.diff_masks.remove(&component_kind);
+
                         // Get component for last change
                         let component = world
                             .remove_component_of_kind(world_entity, &component_kind)
@@ -267,13 +344,293 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
                         panic!("attempting to delete nonexistent component of entity");
                     }
                 }
-                EntityAction::Noop => {
+                EntityAction::GrantAuthority(net_entity) => {
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        let entity_record = self.entity_records.get_mut(world_entity).unwrap();
+                        if !entity_record.has_authority {
+                            // changes made before authority was granted are not sent
+                            entity_record.has_authority = true;
+                            entity_record.clear_diff_masks();
+
+                            event_stream.push_back(Ok(Event::AuthorityGranted(*world_entity)));
+                        }
+                    }
+                }
+                EntityAction::RevokeAuthority(net_entity) => {
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        let entity_record = self.entity_records.get_mut(world_entity).unwrap();
+                        entity_record.has_authority = false;
+
+                        event_stream.push_back(Ok(Event::AuthorityRevoked(*world_entity)));
+                    }
+                }
+                EntityAction::DenyAuthority(net_entity) => {
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        event_stream.push_back(Ok(Event::AuthorityDenied(*world_entity)));
+                    }
+                }
+                EntityAction::GrantComponentAuthority(net_entity, component_kind) => {
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        let entity_record = self.entity_records.get_mut(world_entity).unwrap();
+                        if entity_record.component_authority.insert(component_kind) {
+                            // changes made before authority was granted are not sent
+                            if let Some(diff_mask) = entity_record.diff_masks.get(&component_kind) {
+                                diff_mask
+                                    .write()
+                                    .expect("DiffMask should be initialized")
+                                    .clear();
+                            }
+
+                            event_stream.push_back(Ok(Event::ComponentAuthorityGranted(
+                                *world_entity,
+                                component_kind,
+                            )));
+                        }
+                    }
+                }
+                EntityAction::RevokeComponentAuthority(net_entity, component_kind) => {
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        let entity_record = self.entity_records.get_mut(world_entity).unwrap();
+                        if entity_record.component_authority.remove(&component_kind) {
+                            event_stream.push_back(Ok(Event::ComponentAuthorityRevoked(
+                                *world_entity,
+                                component_kind,
+                            )));
+                        }
+                    }
+                }
+                EntityAction::GrantOwnership(net_entity) => {
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        let entity_record = self.entity_records.get_mut(world_entity).unwrap();
+                        if !entity_record.is_owned {
+                            entity_record.is_owned = true;
+
+                            event_stream.push_back(Ok(Event::OwnershipGranted(*world_entity)));
+                        }
+                    }
+                }
+                EntityAction::RevokeOwnership(net_entity) => {
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        let entity_record = self.entity_records.get_mut(world_entity).unwrap();
+                        if entity_record.is_owned {
+                            entity_record.is_owned = false;
+
+                            event_stream.push_back(Ok(Event::OwnershipRevoked(*world_entity)));
+                        }
+                    }
+                }
+                EntityAction::HideEntity(net_entity) => {
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        let entity_record = self.entity_records.get_mut(world_entity).unwrap();
+                        if !entity_record.is_hidden {
+                            entity_record.is_hidden = true;
+
+                            event_stream.push_back(Ok(Event::EntityHidden(*world_entity)));
+                        }
+                    }
+                }
+                EntityAction::RevealEntity(net_entity) => {
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        let entity_record = self.entity_records.get_mut(world_entity).unwrap();
+                        if entity_record.is_hidden {
+                            entity_record.is_hidden = false;
+
+                            event_stream.push_back(Ok(Event::EntityRevealed(*world_entity)));
+                        }
+                    }
+                }
+                EntityAction::RequestAuthority(_)
+                | EntityAction::ReleaseAuthority(_)
+                | EntityAction::Noop => {
                     // do nothing
                 }
             }
         }
     }
 
+    // Returns whether the Entity existed
+    fn despawn_entity<W: WorldMutType<P, E>, C: ChannelIndex>(
+        &mut self,
+        world: &mut W,
+        net_entity: &NetEntity,
+        event_stream: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
+    ) -> bool {
+        if let Some(world_entity) = self.local_to_world_entity.remove(net_entity) {
+            if self.entity_records.remove(&world_entity).is_none() {
+                panic!("despawning an uninitialized entity");
+            }
+
+            // Generate event for each component, handing references off just in
+            // case
+            for component_kind in world.component_kinds(&world_entity) {
+                if let Some(component) =
+                    world.remove_component_of_kind(&world_entity, &component_kind)
+                {
+                    event_stream.push_back(Ok(Event::RemoveComponent(world_entity, component)));
+                }
+            }
+
+            world.despawn_entity(&world_entity);
+
+            event_stream.push_back(Ok(Event::DespawnEntity(world_entity)));
+
+            return true;
+        }
+        false
+    }
+
+    /// Despawns every Entity received from the Server, generating the same
+    /// events as if the Server had despawned each of them
+    pub fn despawn_all_entities<W: WorldMutType<P, E>, C: ChannelIndex>(
+        &mut self,
+        world: &mut W,
+        event_stream: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
+    ) {
+        let net_entities: Vec<NetEntity> = self.local_to_world_entity.keys().copied().collect();
+        for net_entity in net_entities {
+            self.despawn_entity(world, &net_entity, event_stream);
+        }
+    }
+
+    // Keyframes
+
+    /// Writes every Entity received from the Server & its Components, along
+    /// with everything known about the actions received so far, so that a
+    /// fresh EntityManager can pick up where this one is with `read_keyframe`
+    pub fn write_keyframe<W: WorldRefType<P, E>>(&self, world: &W, writer: &mut dyn BitWrite) {
+        self.receiver.write_state(writer);
+
+        // Entities are all written first, so that Components can refer to any of them
+        let entity_records: Vec<(&E, &EntityRecord<P::Kind>)> =
+            self.entity_records.iter().collect();
+        UnsignedVariableInteger::<7>::new(entity_records.len() as u64).ser(writer);
+        for (_, entity_record) in &entity_records {
+            entity_record.net_entity.ser(writer);
+            entity_record.has_authority.ser(writer);
+            entity_record.is_owned.ser(writer);
+            entity_record.is_hidden.ser(writer);
+
+            UnsignedVariableInteger::<3>::new(entity_record.component_authority.len() as u64)
+                .ser(writer);
+            for component_kind in &entity_record.component_authority {
+                component_kind.ser(writer);
+            }
+        }
+
+        for (entity, entity_record) in &entity_records {
+            let component_kinds: Vec<&P::Kind> = entity_record
+                .component_kinds
+                .iter()
+                .filter(|component_kind| world.has_component_of_kind(entity, component_kind))
+                .collect();
+            UnsignedVariableInteger::<3>::new(component_kinds.len() as u64).ser(writer);
+            for component_kind in component_kinds {
+                world
+                    .component_of_kind(entity, component_kind)
+                    .unwrap()
+                    .write(writer, self);
+            }
+        }
+
+        // Components of actions which are waiting on earlier actions
+        UnsignedVariableInteger::<7>::new(self.received_components.len() as u64).ser(writer);
+        for ((net_entity, _), component) in &self.received_components {
+            net_entity.ser(writer);
+            component.write(writer, self);
+        }
+    }
+
+    /// Spawns the Entities written by `write_keyframe` into the World,
+    /// generating the same events as if they had just been received from the
+    /// Server. Must be called before anything else is read.
+    pub fn read_keyframe<W: WorldMutType<P, E>, C: ChannelIndex>(
+        &mut self,
+        world: &mut W,
+        reader: &mut BitReader,
+        event_stream: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
+    ) -> Result<(), SerdeErr> {
+        self.receiver = EntityActionReceiver::read_state(reader)?;
+
+        let entity_count = UnsignedVariableInteger::<7>::de(reader)?.get() as usize;
+        if entity_count > reader.bits_remaining() {
+            return Err(SerdeErr {});
+        }
+        let mut world_entities = Vec::with_capacity(entity_count);
+        for _ in 0..entity_count {
+            let net_entity = NetEntity::de(reader)?;
+            if self.local_to_world_entity.contains_key(&net_entity) {
+                return Err(SerdeErr {});
+            }
+
+            let world_entity = world.spawn_entity();
+            self.local_to_world_entity.insert(net_entity, world_entity);
+            let entity_handle = self.handle_entity_map.insert(world_entity);
+            let mut entity_record = EntityRecord::new(net_entity, entity_handle);
+            entity_record.has_authority = bool::de(reader)?;
+            entity_record.is_owned = bool::de(reader)?;
+            entity_record.is_hidden = bool::de(reader)?;
+
+            let authority_count = UnsignedVariableInteger::<3>::de(reader)?.get();
+            for _ in 0..authority_count {
+                entity_record
+                    .component_authority
+                    .insert(P::Kind::de(reader)?);
+            }
+            self.entity_records.insert(world_entity, entity_record);
+            world_entities.push(world_entity);
+
+            event_stream.push_back(Ok(Event::SpawnEntity(world_entity)));
+        }
+
+        for world_entity in &world_entities {
+            let component_count = UnsignedVariableInteger::<3>::de(reader)?.get();
+            for _ in 0..component_count {
+                let component = P::read(reader, self)?;
+                let component_kind = component.dyn_ref().kind();
+
+                let entity_record = self.entity_records.get_mut(world_entity).unwrap();
+                entity_record.component_kinds.insert(component_kind);
+                component.extract_and_insert(
+                    world_entity,
+                    &mut ComponentInserter {
+                        world,
+                        entity_record,
+                    },
+                );
+
+                event_stream.push_back(Ok(Event::InsertComponent(*world_entity, component_kind)));
+            }
+
+            let entity_record = self.entity_records.get(world_entity).unwrap();
+            if entity_record.has_authority {
+                event_stream.push_back(Ok(Event::AuthorityGranted(*world_entity)));
+            }
+            for component_kind in &entity_record.component_authority {
+                event_stream.push_back(Ok(Event::ComponentAuthorityGranted(
+                    *world_entity,
+                    *component_kind,
+                )));
+            }
+            if entity_record.is_owned {
+                event_stream.push_back(Ok(Event::OwnershipGranted(*world_entity)));
+            }
+            if entity_record.is_hidden {
+                event_stream.push_back(Ok(Event::EntityHidden(*world_entity)));
+            }
+        }
+
+        let received_count = UnsignedVariableInteger::<7>::de(reader)?.get();
+        for _ in 0..received_count {
+            let net_entity = NetEntity::de(reader)?;
+            let component = P::read(reader, self)?;
+            let component_kind = component.dyn_ref().kind();
+            self.received_components
+                .insert((net_entity, component_kind), component);
+        }
+
+        Ok(())
+    }
+
     fn read_updates<W: WorldMutType<P, E>, C: ChannelIndex>(
         &mut self,
         world: &mut W,
@@ -281,7 +638,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
         reader: &mut BitReader,
         event_stream: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
     ) {
-        let update_count = message_list_header::read(reader);
+        let update_count = message_list_header::read(reader).unwrap();
         for _ in 0..update_count {
             self.read_update(world, server_tick, reader, event_stream);
         }
@@ -300,10 +657,15 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
 
         for _ in 0..components_number {
             // read incoming update
-            let component_update = P::read_create_update(reader);
+            let component_update = P::read_create_update(reader).unwrap();
             let component_kind = component_update.kind;
 
             if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                // the Client's own changes take precedence while it has authority
+                if self.has_component_authority(world_entity, &component_kind) {
+                    continue;
+                }
+
                 world.component_apply_update(self, world_entity, &component_kind, component_update);
 
                 event_stream.push_back(Ok(Event::UpdateComponent(
@@ -316,6 +678,253 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
     }
 }
 
+impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
+    // Authority
+
+    pub fn net_entity(&self, entity: &E) -> Option<NetEntity> {
+        self.entity_records
+            .get(entity)
+            .map(|entity_record| entity_record.net_entity)
+    }
+
+    pub fn has_authority(&self, entity: &E) -> bool {
+        if let Some(entity_record) = self.entity_records.get(entity) {
+            return entity_record.has_authority;
+        }
+        false
+    }
+
+    pub fn has_component_authority(&self, entity: &E, component_kind: &P::Kind) -> bool {
+        if let Some(entity_record) = self.entity_records.get(entity) {
+            return entity_record.has_component_authority(component_kind);
+        }
+        false
+    }
+
+    pub fn release_authority(&mut self, entity: &E) {
+        if let Some(entity_record) = self.entity_records.get_mut(entity) {
+            entity_record.has_authority = false;
+        }
+    }
+
+    // Ownership
+
+    pub fn is_owner(&self, entity: &E) -> bool {
+        if let Some(entity_record) = self.entity_records.get(entity) {
+            return entity_record.is_owned;
+        }
+        false
+    }
+
+    // Caching
+
+    pub fn entity_is_hidden(&self, entity: &E) -> bool {
+        if let Some(entity_record) = self.entity_records.get(entity) {
+            return entity_record.is_hidden;
+        }
+        false
+    }
+
+    // Collect
+
+    pub fn collect_dropped_update_packets(&mut self, rtt_millis: &f32) {
+        let drop_duration = Duration::from_millis((DROP_UPDATE_RTT_FACTOR * rtt_millis) as u64);
+
+        let mut dropped_packets = Vec::new();
+        for (packet_index, (time_sent, _)) in &self.sent_updates {
+            if time_sent.elapsed() > drop_duration {
+                dropped_packets.push(*packet_index);
+            }
+        }
+
+        for packet_index in dropped_packets {
+            self.dropped_update_cleanup(packet_index);
+        }
+    }
+
+    fn dropped_update_cleanup(&mut self, dropped_packet_index: PacketIndex) {
+        if let Some((_, diff_mask_map)) = self.sent_updates.remove(&dropped_packet_index) {
+            for (component_index, diff_mask) in &diff_mask_map {
+                let (entity, component_kind) = component_index;
+                let mut new_diff_mask = diff_mask.clone();
+
+                // walk from dropped packet up to most recently sent packet
+                let mut packet_index = dropped_packet_index.wrapping_add(1);
+                while packet_index != self.last_update_packet_index.wrapping_add(1) {
+                    if let Some((_, diff_mask_map)) = self.sent_updates.get(&packet_index) {
+                        if let Some(next_diff_mask) = diff_mask_map.get(component_index) {
+                            new_diff_mask.nand(next_diff_mask);
+                        }
+                    }
+
+                    packet_index = packet_index.wrapping_add(1);
+                }
+
+                if let Some(current_diff_mask) = self
+                    .entity_records
+                    .get(entity)
+                    .and_then(|entity_record| entity_record.diff_masks.get(component_kind))
+                {
+                    current_diff_mask
+                        .write()
+                        .expect("DiffMask should be initialized")
+                        .or(&new_diff_mask);
+                }
+            }
+        }
+    }
+
+    pub fn has_outgoing_updates(&self) -> bool {
+        self.entity_records.values().any(|entity_record| {
+            entity_record
+                .diff_masks
+                .iter()
+                .any(|(component_kind, diff_mask)| {
+                    entity_record.has_component_authority(component_kind)
+                        && !diff_mask
+                            .read()
+                            .expect("DiffMask should be initialized")
+                            .is_clear()
+                })
+        })
+    }
+
+    // Writing
+
+    /// Writes updates to any Component the Client has authority over, either
+    /// on its own or along with the rest of its Entity
+    pub fn write_updates<W: WorldRefType<P, E>>(
+        &mut self,
+        now: &Instant,
+        writer: &mut BitWriter,
+        packet_index: &PacketIndex,
+        world: &W,
+    ) {
+        let mut update_entities: Vec<E> = Vec::new();
+
+        // Header
+        {
+            // Measure
+            let current_packet_size = writer.bit_count();
+            if current_packet_size > MTU_SIZE_BITS {
+                message_list_header::write(writer, 0);
+                return;
+            }
+
+            let mut counter = BitCounter::default();
+            message_list_header::write(&mut counter, 123);
+
+            // Check for overflow
+            if current_packet_size + counter.bit_count() > MTU_SIZE_BITS {
+                message_list_header::write(writer, 0);
+                return;
+            }
+
+            // Find how many updates will fit into the packet
+            for (entity, entity_record) in &self.entity_records {
+                let component_kinds = Self::updated_component_kinds(world, entity, entity_record);
+                if component_kinds.is_empty() {
+                    continue;
+                }
+                self.write_update(world, &mut counter, entity, &component_kinds);
+                if current_packet_size + counter.bit_count() <= MTU_SIZE_BITS {
+                    update_entities.push(*entity);
+                } else {
+                    break;
+                }
+            }
+        }
+
+        // Write header
+        message_list_header::write(writer, update_entities.len() as u16);
+
+        if update_entities.is_empty() {
+            return;
+        }
+
+        // Updates
+        let mut sent_updates_map = HashMap::new();
+        for entity in update_entities {
+            let entity_record = self.entity_records.get(&entity).unwrap();
+            let component_kinds = Self::updated_component_kinds(world, &entity, entity_record);
+            self.write_update(world, writer, &entity, &component_kinds);
+
+            // having copied the diff mask for this update, clear the component
+            for (component_kind, diff_mask) in component_kinds {
+                entity_record
+                    .diff_masks
+                    .get(&component_kind)
+                    .unwrap()
+                    .write()
+                    .expect("DiffMask should be initialized")
+                    .clear();
+                sent_updates_map.insert((entity, component_kind), diff_mask);
+            }
+        }
+
+        self.last_update_packet_index = *packet_index;
+        self.sent_updates
+            .insert(*packet_index, (now.clone(), sent_updates_map));
+    }
+
+    fn updated_component_kinds<W: WorldRefType<P, E>>(
+        world: &W,
+        entity: &E,
+        entity_record: &EntityRecord<P::Kind>,
+    ) -> Vec<(P::Kind, DiffMask)> {
+        let mut output = Vec::new();
+        for (component_kind, diff_mask) in &entity_record.diff_masks {
+            if !entity_record.has_component_authority(component_kind) {
+                continue;
+            }
+            let diff_mask = diff_mask.read().expect("DiffMask should be initialized");
+            if !diff_mask.is_clear() && world.has_component_of_kind(entity, component_kind) {
+                output.push((*component_kind, diff_mask.clone()));
+            }
+        }
+        output
+    }
+
+    fn write_update<W: WorldRefType<P, E>>(
+        &self,
+        world: &W,
+        bit_writer: &mut dyn BitWrite,
+        entity: &E,
+        component_kinds: &[(P::Kind, DiffMask)],
+    ) {
+        // write net entity
+        self.entity_records
+            .get(entity)
+            .unwrap()
+            .net_entity
+            .ser(bit_writer);
+
+        // write number of components
+        UnsignedVariableInteger::<3>::new(component_kinds.len() as u64).ser(bit_writer);
+
+        for (component_kind, diff_mask) in component_kinds {
+            // write diff mask, so the Server can forward the update to other Clients
+            diff_mask.ser(bit_writer);
+
+            // write component kind
+            component_kind.ser(bit_writer);
+
+            // write payload
+            world
+                .component_of_kind(entity, component_kind)
+                .expect("Component does not exist in World")
+                .write_update(diff_mask, bit_writer, self);
+        }
+    }
+}
+
+// PacketNotifiable
+impl<P: Protocolize, E: Copy + Eq + Hash> PacketNotifiable for EntityManager<P, E> {
+    fn notify_packet_delivered(&mut self, packet_index: PacketIndex) {
+        self.sent_updates.remove(&packet_index);
+    }
+}
+
 impl<P: Protocolize, E: Copy + Eq + Hash> EntityHandleConverter<E> for EntityManager<P, E> {
     fn handle_to_entity(&self, entity_handle: &EntityHandle) -> E {
         *self
@@ -349,4 +958,44 @@ impl<P: Protocolize, E: Copy + Eq + Hash> NetEntityHandleConverter for EntityMan
             .expect("no entity exists associated with given net entity");
         self.entity_to_handle(entity)
     }
+
+    fn try_handle_to_net_entity(&self, entity_handle: &EntityHandle) -> Option<NetEntity> {
+        let entity = self.handle_entity_map.get(entity_handle)?;
+        Some(self.entity_records.get(entity)?.net_entity)
+    }
+
+    fn try_net_entity_to_handle(&self, net_entity: &NetEntity) -> Option<EntityHandle> {
+        let entity = self.local_to_world_entity.get(net_entity)?;
+        Some(self.entity_records.get(entity)?.entity_handle)
+    }
+}
+
+// ComponentInserter
+
+/// Inserts a Component received from the Server into the World, tracking any
+/// changes made to it so they can be sent back while the Client has authority
+/// over its Entity
+struct ComponentInserter<'w, 'r, P: Protocolize, W> {
+    world: &'w mut W,
+    entity_record: &'r mut EntityRecord<P::Kind>,
+}
+
+impl<'w, 'r, P: Protocolize, E: Copy + Eq + Hash, W: WorldMutType<P, E>> ProtocolInserter<P, E>
+    for ComponentInserter<'w, 'r, P, W>
+{
+    fn insert<R: ReplicateSafe<P>>(&mut self, entity: &E, mut component: R) {
+        if component.is_static() {
+            component.set_mutator(&PropertyMutator::new(StaticMutator));
+            self.world.insert_component(entity, component);
+            return;
+        }
+
+        let diff_mask = Arc::new(RwLock::new(DiffMask::new(component.diff_mask_size())));
+        component.set_mutator(&PropertyMutator::new(ComponentMutator::new(&diff_mask)));
+        self.entity_record
+            .diff_masks
+            .insert(component.kind(), diff_mask);
+
+        self.world.insert_component(entity, component);
+    }
 }
@@ -1,11 +1,20 @@
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
 
-use naia_shared::{EntityHandle, NetEntity, ProtocolKindType};
+use naia_shared::{DiffMask, EntityHandle, NetEntity, ProtocolKindType};
 
 pub struct EntityRecord<K: ProtocolKindType> {
     pub net_entity: NetEntity,
     pub component_kinds: HashSet<K>,
     pub entity_handle: EntityHandle,
+    pub has_authority: bool,
+    // Components the Client has authority over on their own
+    pub component_authority: HashSet<K>,
+    pub is_owned: bool,
+    pub is_hidden: bool,
+    pub diff_masks: HashMap<K, Arc<RwLock<DiffMask>>>,
 }
 
 impl<K: ProtocolKindType> EntityRecord<K> {
@@ -14,6 +23,26 @@ impl<K: ProtocolKindType> EntityRecord<K> {
             net_entity,
             component_kinds: HashSet::new(),
             entity_handle,
+            has_authority: false,
+            component_authority: HashSet::new(),
+            is_owned: false,
+            is_hidden: false,
+            diff_masks: HashMap::new(),
+        }
+    }
+
+    /// Returns whether the Client has authority over the Component, either
+    /// over it alone or over the whole Entity
+    pub fn has_component_authority(&self, component_kind: &K) -> bool {
+        self.has_authority || self.component_authority.contains(component_kind)
+    }
+
+    pub fn clear_diff_masks(&mut self) {
+        for diff_mask in self.diff_masks.values() {
+            diff_mask
+                .write()
+                .expect("DiffMask should be initialized")
+                .clear();
         }
     }
 }
@@ -1,6 +1,11 @@
 use std::{hash::Hash, marker::PhantomData};
 
-use naia_shared::{Protocolize, ReplicaRefWrapper, ReplicateSafe, WorldRefType};
+use naia_shared::{
+    ChannelIndex, Protocolize, ReplicaMutWrapper, ReplicaRefWrapper, Replicate, ReplicateSafe,
+    WorldMutType, WorldRefType,
+};
+
+use crate::client::Client;
 
 // EntityRef
 pub struct EntityRef<P: Protocolize, E: Copy + Eq + Hash, W: WorldRefType<P, E>> {
@@ -31,19 +36,68 @@ impl<P: Protocolize, E: Copy + Eq + Hash, W: WorldRefType<P, E>> EntityRef<P, E,
     }
 }
 
-// // EntityMut
-// pub struct EntityMut<P: Protocolize, E: Copy + Eq + Hash, W: WorldMutType<P, E>> {
-//     world: W,
-//     entity: E,
-//     phantom_p: PhantomData<P>,
-// }
-//
-// impl<'c, P: Protocolize, E: Copy + Eq + Hash, W: WorldMutType<P, E>> EntityMut<P, E, W> {
-//     pub fn new(world: W, entity: &E) -> Self {
-//         EntityMut {
-//             world,
-//             entity: *entity,
-//             phantom_p: PhantomData,
-//         }
-//     }
-// }
+// EntityMut
+pub struct EntityMut<
+    's,
+    P: Protocolize,
+    E: Copy + Eq + Hash,
+    W: WorldMutType<P, E>,
+    C: ChannelIndex,
+> {
+    client: &'s mut Client<P, E, C>,
+    world: W,
+    entity: E,
+}
+
+impl<'s, P: Protocolize, E: Copy + Eq + Hash, W: WorldMutType<P, E>, C: ChannelIndex>
+    EntityMut<'s, P, E, W, C>
+{
+    pub(crate) fn new(client: &'s mut Client<P, E, C>, world: W, entity: &E) -> Self {
+        EntityMut {
+            client,
+            world,
+            entity: *entity,
+        }
+    }
+
+    pub fn id(&self) -> E {
+        self.entity
+    }
+
+    pub fn despawn(&mut self) {
+        self.client.despawn_entity(&mut self.world, &self.entity);
+    }
+
+    // Components
+
+    pub fn has_component<R: ReplicateSafe<P>>(&self) -> bool {
+        self.world.has_component::<R>(&self.entity)
+    }
+
+    pub fn component<R: ReplicateSafe<P>>(&mut self) -> Option<ReplicaMutWrapper<P, R>> {
+        self.world.component_mut::<R>(&self.entity)
+    }
+
+    pub fn insert_component<R: ReplicateSafe<P>>(&mut self, component_ref: R) -> &mut Self {
+        self.client
+            .insert_component(&mut self.world, &self.entity, component_ref);
+
+        self
+    }
+
+    pub fn insert_components<R: ReplicateSafe<P>>(
+        &mut self,
+        mut component_refs: Vec<R>,
+    ) -> &mut Self {
+        while let Some(component_ref) = component_refs.pop() {
+            self.insert_component(component_ref);
+        }
+
+        self
+    }
+
+    pub fn remove_component<R: Replicate<P>>(&mut self) -> Option<R> {
+        self.client
+            .remove_component::<R, W>(&mut self.world, &self.entity)
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    time::Duration,
+};
+
+use naia_shared::{
+    message_list_header,
+    serde::{BitCounter, BitWrite, BitWriter, Serde, UnsignedVariableInteger},
+    wrapping_diff, ChannelSender, EntityActionEvent, EntityActionType, Instant, KeyGenerator,
+    MessageId, NetEntity, NetEntityHandleConverter, NetEntityWidth, PacketIndex, PacketNotifiable,
+    Protocolize, ReliableSender, WorldRefType, MTU_SIZE_BITS,
+};
+
+const RESEND_ACTION_RTT_FACTOR: f32 = 1.5;
+const ACTION_RECORD_TTL: Duration = Duration::from_secs(60);
+
+pub type ActionId = MessageId;
+
+/// Keeps track of the Entities & Components spawned by the Client itself, and
+/// reliably replicates their creation & destruction to the Server, along with
+/// any requests for authority over the Server's Entities
+pub struct HostEntityManager<P: Protocolize, E: Copy + Eq + Hash> {
+    host_world: HashMap<E, HashSet<P::Kind>>,
+    net_entity_generator: KeyGenerator<NetEntity>,
+    entity_to_net_entity_map: HashMap<E, NetEntity>,
+    net_entity_to_entity_map: HashMap<NetEntity, E>,
+    outgoing_actions: ReliableSender<EntityActionEvent<NetEntity, P::Kind>>,
+    next_send_actions: VecDeque<(ActionId, EntityActionEvent<NetEntity, P::Kind>)>,
+    sent_action_packets: HashMap<PacketIndex, (Instant, Vec<ActionId>)>,
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash> HostEntityManager<P, E> {
+    pub fn new(net_entity_width: NetEntityWidth) -> Self {
+        Self {
+            host_world: HashMap::new(),
+            net_entity_generator: KeyGenerator::new(net_entity_width.max_net_entity()),
+            entity_to_net_entity_map: HashMap::new(),
+            net_entity_to_entity_map: HashMap::new(),
+            outgoing_actions: ReliableSender::new(RESEND_ACTION_RTT_FACTOR),
+            next_send_actions: VecDeque::new(),
+            sent_action_packets: HashMap::new(),
+        }
+    }
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash> HostEntityManager<P, E> {
+    // World
+
+    pub fn has_entity(&self, entity: &E) -> bool {
+        self.host_world.contains_key(entity)
+    }
+
+    /// Returns whether another Entity can be spawned, without running out of
+    /// NetEntities
+    pub fn has_net_entity_available(&self) -> bool {
+        !self.net_entity_generator.is_exhausted()
+    }
+
+    /// Returns false if every NetEntity is already in use, in which case the
+    /// Entity is not spawned
+    pub fn spawn_entity(&mut self, entity: &E) -> bool {
+        if self.host_world.contains_key(entity) {
+            panic!("attempted to spawn an Entity which has already been spawned");
+        }
+
+        let net_entity = match self.net_entity_generator.generate() {
+            Some(net_entity) => net_entity,
+            None => return false,
+        };
+
+        self.host_world.insert(*entity, HashSet::new());
+        self.entity_to_net_entity_map.insert(*entity, net_entity);
+        self.net_entity_to_entity_map.insert(net_entity, *entity);
+
+        self.outgoing_actions
+            .send_message(EntityActionEvent::SpawnEntity(net_entity));
+
+        true
+    }
+
+    pub fn despawn_entity(&mut self, entity: &E) {
+        if self.host_world.remove(entity).is_none() {
+            return;
+        }
+
+        let net_entity = self.entity_to_net_entity_map.remove(entity).unwrap();
+        self.net_entity_to_entity_map.remove(&net_entity);
+
+        // net entity is recycled only once the despawn has been delivered
+        self.outgoing_actions
+            .send_message(EntityActionEvent::DespawnEntity(net_entity));
+    }
+
+    pub fn insert_component(&mut self, entity: &E, component: &P::Kind) {
+        let components = self
+            .host_world
+            .get_mut(entity)
+            .expect("cannot insert component into non-existent entity");
+        if !components.insert(*component) {
+            return;
+        }
+
+        let net_entity = *self.entity_to_net_entity_map.get(entity).unwrap();
+        self.outgoing_actions
+            .send_message(EntityActionEvent::InsertComponent(net_entity, *component));
+    }
+
+    pub fn remove_component(&mut self, entity: &E, component: &P::Kind) {
+        let components = self
+            .host_world
+            .get_mut(entity)
+            .expect("cannot remove component from non-existent entity");
+        if !components.remove(component) {
+            return;
+        }
+
+        let net_entity = *self.entity_to_net_entity_map.get(entity).unwrap();
+        self.outgoing_actions
+            .send_message(EntityActionEvent::RemoveComponent(net_entity, *component));
+    }
+
+    // Authority
+
+    pub fn request_authority(&mut self, net_entity: &NetEntity) {
+        self.outgoing_actions
+            .send_message(EntityActionEvent::RequestAuthority(*net_entity));
+    }
+
+    pub fn release_authority(&mut self, net_entity: &NetEntity) {
+        self.outgoing_actions
+            .send_message(EntityActionEvent::ReleaseAuthority(*net_entity));
+    }
+
+    // Collect
+
+    pub fn collect_outgoing_actions(&mut self, now: &Instant, rtt_millis: &f32) {
+        self.sent_action_packets
+            .retain(|_, (time_sent, _)| time_sent.elapsed() <= ACTION_RECORD_TTL);
+
+        self.outgoing_actions.collect_messages(now, rtt_millis);
+        self.next_send_actions = self.outgoing_actions.take_next_messages();
+    }
+
+    pub fn has_outgoing_actions(&self) -> bool {
+        !self.next_send_actions.is_empty()
+    }
+
+    // Writing
+
+    pub fn write_actions<W: WorldRefType<P, E>>(
+        &mut self,
+        now: &Instant,
+        writer: &mut BitWriter,
+        packet_index: &PacketIndex,
+        world: &W,
+        converter: &dyn NetEntityHandleConverter,
+    ) {
+        let mut action_count = 0;
+
+        // Header
+        {
+            // Measure
+            let current_packet_size = writer.bit_count();
+            if current_packet_size > MTU_SIZE_BITS {
+                message_list_header::write(writer, 0);
+                return;
+            }
+
+            let mut counter = BitCounter::default();
+            message_list_header::write(&mut counter, 123);
+
+            // Check for overflow
+            if current_packet_size + counter.bit_count() > MTU_SIZE_BITS {
+                message_list_header::write(writer, 0);
+                return;
+            }
+
+            // Find how many actions will fit into the packet
+            let mut last_written_id: Option<ActionId> = None;
+
+            for action_index in 0..self.next_send_actions.len() {
+                self.write_action(
+                    world,
+                    converter,
+                    &mut counter,
+                    action_index,
+                    &mut last_written_id,
+                );
+                if current_packet_size + counter.bit_count() <= MTU_SIZE_BITS {
+                    action_count += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        // Write header
+        message_list_header::write(writer, action_count as u64);
+
+        // Actions
+        {
+            let mut last_written_id: Option<ActionId> = None;
+
+            for action_index in 0..action_count {
+                self.write_action(world, converter, writer, action_index, &mut last_written_id);
+            }
+
+            // Pop actions, and record them as being sent in this packet
+            let sent_ids: Vec<ActionId> = self
+                .next_send_actions
+                .drain(..action_count)
+                .map(|(action_id, _)| action_id)
+                .collect();
+            if !sent_ids.is_empty() {
+                self.sent_action_packets
+                    .insert(*packet_index, (now.clone(), sent_ids));
+            }
+        }
+    }
+
+    fn write_action<W: WorldRefType<P, E>>(
+        &self,
+        world: &W,
+        converter: &dyn NetEntityHandleConverter,
+        bit_writer: &mut dyn BitWrite,
+        action_index: usize,
+        last_written_id: &mut Option<ActionId>,
+    ) {
+        let (action_id, action) = self.next_send_actions.get(action_index).unwrap();
+
+        // write action id
+        if let Some(last_id) = last_written_id {
+            let id_diff = wrapping_diff(*last_id, *action_id);
+            UnsignedVariableInteger::<3>::new(id_diff).ser(bit_writer);
+        } else {
+            action_id.ser(bit_writer);
+        }
+        *last_written_id = Some(*action_id);
+
+        match action {
+            EntityActionEvent::SpawnEntity(net_entity) => {
+                EntityActionType::SpawnEntity.ser(bit_writer);
+                net_entity.ser(bit_writer);
+
+                // Components are always sent afterwards, as individual insert actions
+                UnsignedVariableInteger::<3>::new(0).ser(bit_writer);
+            }
+            EntityActionEvent::DespawnEntity(net_entity) => {
+                EntityActionType::DespawnEntity.ser(bit_writer);
+                net_entity.ser(bit_writer);
+            }
+            EntityActionEvent::InsertComponent(net_entity, component) => {
+                let component_ref = self
+                    .net_entity_to_entity_map
+                    .get(net_entity)
+                    .and_then(|entity| world.component_of_kind(entity, component));
+                if let Some(component_ref) = component_ref {
+                    EntityActionType::InsertComponent.ser(bit_writer);
+                    net_entity.ser(bit_writer);
+                    component_ref.write(bit_writer, converter);
+                } else {
+                    // Entity or Component was removed before it could be sent
+                    EntityActionType::Noop.ser(bit_writer);
+                }
+            }
+            EntityActionEvent::RemoveComponent(net_entity, component) => {
+                EntityActionType::RemoveComponent.ser(bit_writer);
+                net_entity.ser(bit_writer);
+                component.ser(bit_writer);
+            }
+            EntityActionEvent::RequestAuthority(net_entity) => {
+                EntityActionType::RequestAuthority.ser(bit_writer);
+                net_entity.ser(bit_writer);
+            }
+            EntityActionEvent::ReleaseAuthority(net_entity) => {
+                EntityActionType::ReleaseAuthority.ser(bit_writer);
+                net_entity.ser(bit_writer);
+            }
+            EntityActionEvent::GrantAuthority(_)
+            | EntityActionEvent::RevokeAuthority(_)
+            | EntityActionEvent::DenyAuthority(_)
+            | EntityActionEvent::GrantComponentAuthority(_, _)
+            | EntityActionEvent::RevokeComponentAuthority(_, _) => {
+                panic!("Client should never grant, revoke, or deny authority over an Entity");
+            }
+            EntityActionEvent::GrantOwnership(_) | EntityActionEvent::RevokeOwnership(_) => {
+                panic!("Client should never grant or revoke ownership of an Entity");
+            }
+            EntityActionEvent::HideEntity(_) | EntityActionEvent::RevealEntity(_) => {
+                panic!("Client should never hide or reveal an Entity");
+            }
+        }
+    }
+}
+
+// PacketNotifiable
+impl<P: Protocolize, E: Copy + Eq + Hash> PacketNotifiable for HostEntityManager<P, E> {
+    fn notify_packet_delivered(&mut self, packet_index: PacketIndex) {
+        if let Some((_, action_ids)) = self.sent_action_packets.remove(&packet_index) {
+            for action_id in action_ids {
+                if let Some(EntityActionEvent::DespawnEntity(net_entity)) =
+                    self.outgoing_actions.deliver_message(&action_id)
+                {
+                    self.net_entity_generator.recycle_key(&net_entity);
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod component_mutator;
 pub mod entity_manager;
 pub mod entity_record;
 pub mod entity_ref;
+pub mod host_entity_manager;
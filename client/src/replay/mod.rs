@@ -0,0 +1,3 @@
+pub mod replay_frame;
+pub mod replay_player;
+pub mod replay_recorder;
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use naia_shared::{
+    derive_serde, serde,
+    serde::{
+        BitReader, BitVecWriter, BitWrite, OwnedBitReader, Serde, SerdeErr, UnsignedVariableInteger,
+    },
+    Tick,
+};
+
+#[derive(Copy)]
+#[derive_serde]
+pub enum ReplayFrameType {
+    // The Entity actions & updates of a data packet
+    Packet,
+    // A single Message, with the Channel it was received on
+    Message,
+    // Every Entity the Client had when recording started
+    Keyframe,
+}
+
+/// A single entry in a replay, stored as the bits which were originally
+/// read from the Server
+pub struct ReplayFrame {
+    pub elapsed: Duration,
+    pub tick: Tick,
+    pub frame_type: ReplayFrameType,
+    pub data: OwnedBitReader,
+}
+
+impl ReplayFrame {
+    pub fn write(
+        writer: &mut dyn BitWrite,
+        elapsed_millis_diff: u64,
+        tick: Tick,
+        frame_type: ReplayFrameType,
+        data: &mut BitReader,
+        bit_count: usize,
+    ) {
+        // continue bit
+        true.ser(writer);
+
+        UnsignedVariableInteger::<7>::new(elapsed_millis_diff).ser(writer);
+        tick.ser(writer);
+        frame_type.ser(writer);
+
+        UnsignedVariableInteger::<7>::new(bit_count as u64).ser(writer);
+        copy_bits(data, writer, bit_count).expect("data should hold at least bit_count bits");
+    }
+
+    pub fn write_end(writer: &mut dyn BitWrite) {
+        // finish frames
+        false.ser(writer);
+    }
+
+    /// Reads the next frame, returning None once the end of the replay is
+    /// reached
+    pub fn read(reader: &mut BitReader, last_elapsed: &Duration) -> Result<Option<Self>, SerdeErr> {
+        let has_frame = bool::de(reader)?;
+        if !has_frame {
+            return Ok(None);
+        }
+
+        let elapsed_millis_diff = UnsignedVariableInteger::<7>::de(reader)?.get() as u64;
+        let elapsed = *last_elapsed + Duration::from_millis(elapsed_millis_diff);
+        let tick = Tick::de(reader)?;
+        let frame_type = ReplayFrameType::de(reader)?;
+
+        let bit_count = UnsignedVariableInteger::<7>::de(reader)?.get() as usize;
+        if bit_count > reader.bits_remaining() {
+            return Err(SerdeErr {});
+        }
+        let mut data_writer = BitVecWriter::new();
+        copy_bits(reader, &mut data_writer, bit_count)?;
+
+        Ok(Some(Self {
+            elapsed,
+            tick,
+            frame_type,
+            data: OwnedBitReader::new(&data_writer.to_bytes()),
+        }))
+    }
+}
+
+fn copy_bits(
+    reader: &mut BitReader,
+    writer: &mut dyn BitWrite,
+    bit_count: usize,
+) -> Result<(), SerdeErr> {
+    for _ in 0..bit_count {
+        bool::de(reader)?.ser(writer);
+    }
+    Ok(())
+}
@@ -0,0 +1,229 @@
+use std::{collections::VecDeque, hash::Hash, marker::PhantomData, time::Duration};
+
+use naia_shared::{
+    sequence_greater_than,
+    serde::{BitReader, SerdeErr},
+    ChannelIndex, Instant, Protocolize, Tick, WorldMutType,
+};
+
+use crate::{error::NaiaClientError, event::Event, protocol::entity_manager::EntityManager};
+
+use super::replay_frame::{ReplayFrame, ReplayFrameType};
+
+/// Plays back a replay recorded with `Client::start_recording`, applying it
+/// to a World through the same path used for a live connection. Events are
+/// returned just as `Client::receive` would have returned them.
+pub struct ReplayPlayer<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> {
+    frames: Vec<ReplayFrame>,
+    next_frame: usize,
+    entity_manager: EntityManager<P, E>,
+    current_tick: Option<Tick>,
+    // Playback
+    is_playing: bool,
+    speed: f32,
+    position: Duration,
+    last_update: Option<Instant>,
+    // Phantom
+    phantom_c: PhantomData<C>,
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> ReplayPlayer<P, E, C> {
+    /// Create a new ReplayPlayer from the bytes returned by
+    /// `Client::stop_recording`. The player starts out paused.
+    /// Returns an error if the replay is truncated or corrupt.
+    pub fn new(replay: &[u8]) -> Result<Self, NaiaClientError> {
+        let mut reader = BitReader::new(replay);
+        let mut frames = Vec::new();
+        let mut last_elapsed = Duration::ZERO;
+        while let Some(frame) = ReplayFrame::read(&mut reader, &last_elapsed)
+            .map_err(|_| NaiaClientError::CorruptReplay)?
+        {
+            last_elapsed = frame.elapsed;
+            frames.push(frame);
+        }
+
+        Ok(Self {
+            frames,
+            next_frame: 0,
+            entity_manager: EntityManager::default(),
+            current_tick: None,
+            is_playing: false,
+            speed: 1.0,
+            position: Duration::ZERO,
+            last_update: None,
+            phantom_c: PhantomData,
+        })
+    }
+
+    // Playback
+
+    /// Starts or resumes playback
+    pub fn play(&mut self) {
+        if !self.is_playing {
+            self.is_playing = true;
+            self.last_update = Some(Instant::now());
+        }
+    }
+
+    /// Pauses playback, until `play` is called again
+    pub fn pause(&mut self) {
+        self.is_playing = false;
+        self.last_update = None;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.is_playing
+    }
+
+    /// Returns whether every frame of the replay has been applied
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.frames.len()
+    }
+
+    /// Sets how fast the replay plays back, relative to how it was recorded.
+    /// For example, 0.5 plays at half speed & 2.0 at double speed.
+    pub fn set_speed(&mut self, speed: f32) {
+        if speed < 0.0 {
+            panic!("ReplayPlayer speed cannot be negative");
+        }
+        self.speed = speed;
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Returns the Server Tick of the last applied frame, or None if
+    /// nothing has been applied yet
+    pub fn current_tick(&self) -> Option<Tick> {
+        self.current_tick
+    }
+
+    /// Returns how far into the replay playback is
+    pub fn position(&self) -> Duration {
+        self.position
+    }
+
+    /// Returns the length of the replay
+    pub fn duration(&self) -> Duration {
+        self.frames
+            .last()
+            .map(|frame| frame.elapsed)
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Must call this regularly while playing, preferably at the beginning
+    /// of every frame. Applies every recorded frame which is due, given the
+    /// time elapsed since the last call & the playback speed.
+    pub fn update<W: WorldMutType<P, E>>(
+        &mut self,
+        mut world: W,
+    ) -> VecDeque<Result<Event<P, E, C>, NaiaClientError>> {
+        let mut events = VecDeque::new();
+
+        if self.is_playing {
+            let now = Instant::now();
+            if let Some(last_update) = &self.last_update {
+                self.position += last_update.elapsed().mul_f32(self.speed);
+            }
+            self.last_update = Some(now);
+
+            while self.next_frame < self.frames.len()
+                && self.frames[self.next_frame].elapsed <= self.position
+            {
+                self.apply_next_frame(&mut world, &mut events);
+            }
+
+            if self.is_finished() {
+                self.pause();
+            }
+        }
+
+        events
+    }
+
+    /// Moves playback to the end of the given Tick. Seeking backwards
+    /// despawns every Entity in the replay & plays it again from the start.
+    /// The Tick is compared as a wrapping sequence number, so in replays
+    /// longer than `u16::MAX` Ticks the first match is used.
+    pub fn seek_to_tick<W: WorldMutType<P, E>>(
+        &mut self,
+        mut world: W,
+        tick: Tick,
+    ) -> VecDeque<Result<Event<P, E, C>, NaiaClientError>> {
+        let mut events = VecDeque::new();
+
+        let target_frame = self
+            .frames
+            .iter()
+            .position(|frame| sequence_greater_than(frame.tick, tick))
+            .unwrap_or(self.frames.len());
+
+        if target_frame < self.next_frame {
+            self.restart(&mut world, &mut events);
+        }
+
+        while self.next_frame < target_frame {
+            self.apply_next_frame(&mut world, &mut events);
+        }
+
+        if self.next_frame > 0 {
+            self.position = self.frames[self.next_frame - 1].elapsed;
+        }
+        if self.is_playing {
+            self.last_update = Some(Instant::now());
+        }
+
+        events
+    }
+
+    // Private methods
+
+    fn restart<W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        events: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
+    ) {
+        self.entity_manager.despawn_all_entities(world, events);
+        self.entity_manager = EntityManager::default();
+        self.next_frame = 0;
+        self.current_tick = None;
+        self.position = Duration::ZERO;
+    }
+
+    fn apply_next_frame<W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        events: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
+    ) {
+        let frame = &self.frames[self.next_frame];
+        let mut reader = frame.data.borrow();
+
+        let result: Result<(), SerdeErr> = match frame.frame_type {
+            ReplayFrameType::Packet => {
+                self.entity_manager
+                    .read_all(world, frame.tick, &mut reader, events);
+                Ok(())
+            }
+            ReplayFrameType::Message => C::de(&mut reader).and_then(|channel| {
+                let message = P::read(&mut reader, &self.entity_manager)?;
+                events.push_back(Ok(Event::Message(channel, message)));
+                Ok(())
+            }),
+            ReplayFrameType::Keyframe => {
+                self.entity_manager
+                    .read_keyframe(world, &mut reader, events)
+            }
+        };
+
+        self.current_tick = Some(frame.tick);
+        self.next_frame += 1;
+
+        if result.is_err() {
+            // nothing after a corrupt frame can be relied upon
+            self.next_frame = self.frames.len();
+            self.pause();
+            events.push_back(Err(NaiaClientError::CorruptReplay));
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::hash::Hash;
+
+use naia_shared::{
+    serde::{BitReader, BitVecWriter},
+    ChannelIndex, Instant, NetEntityHandleConverter, Protocolize, Tick, WorldRefType,
+};
+
+use crate::protocol::entity_manager::EntityManager;
+
+use super::replay_frame::{ReplayFrame, ReplayFrameType};
+
+/// Records the replication stream received from the Server, so that it can
+/// be played back later with a `ReplayPlayer`
+pub struct ReplayRecorder {
+    writer: BitVecWriter,
+    start: Instant,
+    last_elapsed_millis: u64,
+    last_tick: Tick,
+}
+
+impl Default for ReplayRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReplayRecorder {
+    pub fn new() -> Self {
+        Self {
+            writer: BitVecWriter::new(),
+            start: Instant::now(),
+            last_elapsed_millis: 0,
+            last_tick: 0,
+        }
+    }
+
+    /// Records the Entity actions & updates of a data packet, which must
+    /// be the only thing left to read from it
+    pub fn record_packet(&mut self, server_tick: Tick, reader: &BitReader) {
+        self.last_tick = server_tick;
+
+        let owned_reader = reader.to_owned();
+        let mut packet_reader = owned_reader.borrow();
+        let bit_count = packet_reader.bits_remaining();
+
+        let elapsed_millis_diff = self.elapsed_millis_diff();
+        ReplayFrame::write(
+            &mut self.writer,
+            elapsed_millis_diff,
+            server_tick,
+            ReplayFrameType::Packet,
+            &mut packet_reader,
+            bit_count,
+        );
+    }
+
+    /// Records every Entity the Client has received so far, so that a replay
+    /// started partway through a session begins from the same state
+    pub fn record_keyframe<P: Protocolize, E: Copy + Eq + Hash, W: WorldRefType<P, E>>(
+        &mut self,
+        server_tick: Tick,
+        entity_manager: &EntityManager<P, E>,
+        world: &W,
+    ) {
+        self.last_tick = server_tick;
+
+        let mut keyframe_writer = BitVecWriter::new();
+        entity_manager.write_keyframe(world, &mut keyframe_writer);
+        self.record_frame(ReplayFrameType::Keyframe, keyframe_writer);
+    }
+
+    /// Records a Message, as part of the last recorded Tick
+    pub fn record_message<P: Protocolize, C: ChannelIndex>(
+        &mut self,
+        channel: &C,
+        message: &P,
+        converter: &dyn NetEntityHandleConverter,
+    ) {
+        let mut message_writer = BitVecWriter::new();
+        channel.ser(&mut message_writer);
+        message.write(&mut message_writer, converter);
+        self.record_frame(ReplayFrameType::Message, message_writer);
+    }
+
+    fn record_frame(&mut self, frame_type: ReplayFrameType, frame_writer: BitVecWriter) {
+        // bit_count stops counting at u16::MAX, so any padding in the last
+        // byte is recorded too
+        let bit_count = frame_writer.byte_count() * 8;
+        let frame_bytes = frame_writer.to_bytes();
+        let mut frame_reader = BitReader::new(&frame_bytes);
+
+        let elapsed_millis_diff = self.elapsed_millis_diff();
+        ReplayFrame::write(
+            &mut self.writer,
+            elapsed_millis_diff,
+            self.last_tick,
+            frame_type,
+            &mut frame_reader,
+            bit_count,
+        );
+    }
+
+    /// Finishes the recording, returning the replay as bytes
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        ReplayFrame::write_end(&mut self.writer);
+        self.writer.to_bytes()
+    }
+
+    fn elapsed_millis_diff(&mut self) -> u64 {
+        let elapsed_millis = self.start.elapsed().as_millis() as u64;
+        let diff = elapsed_millis - self.last_elapsed_millis;
+        self.last_elapsed_millis = elapsed_millis;
+        diff
+    }
+}
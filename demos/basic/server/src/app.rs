@@ -80,7 +80,7 @@ impl Default for App {
 
 impl App {
     pub fn update(&mut self) {
-        for event in self.server.receive() {
+        for event in self.server.receive(self.world.proxy_mut()) {
             match event {
                 Ok(Event::Authorization(user_key, Protocol::Auth(auth))) => {
                     if *auth.username == "charlie" && *auth.password == "12345" {
@@ -140,6 +140,9 @@ impl App {
                         {
                             character.step();
                         }
+
+                        // Characters move every tick, so their scope must be decided again
+                        self.server.recheck_entity_scope(&entity);
                     }
 
                     // Update scopes of entities
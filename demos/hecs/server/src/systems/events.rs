@@ -5,7 +5,7 @@ use naia_hecs_demo_shared::protocol::Protocol;
 use crate::app::App;
 
 pub fn process_events(app: &mut App) {
-    for event in app.server.receive() {
+    for event in app.server.receive(&mut app.world) {
         match event {
             Ok(Event::Authorization(user_key, Protocol::Auth(auth))) => {
                 if *auth.username == "charlie" && *auth.password == "12345" {
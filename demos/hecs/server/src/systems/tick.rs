@@ -15,10 +15,16 @@ pub fn march_and_mark(app: &mut App) {
     let mut entities_to_remove: Vec<Entity> = Vec::new();
     let mut entities_to_delete: Vec<Entity> = Vec::new();
     let mut entities_to_respawn: Vec<Entity> = Vec::new();
+    let mut entities_to_recheck: Vec<Entity> = Vec::new();
 
     for (entity, position) in app.world.query_mut::<&mut Position>() {
         *position.x += 1;
 
+        // entering or leaving the scoped area
+        if *position.x == 50 || *position.x == 201 {
+            entities_to_recheck.push(entity);
+        }
+
         if *position.x == 100 {
             entities_to_add.push(entity);
         }
@@ -38,6 +44,10 @@ pub fn march_and_mark(app: &mut App) {
         }
     }
 
+    while let Some(entity) = entities_to_recheck.pop() {
+        app.server.recheck_entity_scope(&entity);
+    }
+
     // add markers
     while let Some(entity) = entities_to_add.pop() {
         if !app.has_marker.contains(&entity) {
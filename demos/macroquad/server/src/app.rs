@@ -58,7 +58,7 @@ impl Default for App {
 
 impl App {
     pub fn update(&mut self) {
-        for event in self.server.receive() {
+        for event in self.server.receive(self.world.proxy_mut()) {
             match event {
                 Ok(Event::Authorization(user_key, Protocol::Auth(auth))) => {
                     if *auth.username == "charlie" && *auth.password == "12345" {
@@ -0,0 +1,331 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Seek, SeekFrom},
+    mem,
+};
+
+use naia_shared::{
+    blob_hash,
+    serde::{BitReader, BitWriter},
+    BlobHash, BlobHasher, BlobId, BlobMessage, BulkChannel, Instant, PacketIndex, PacketNotifiable,
+    BLOB_CHUNK_SIZE_BYTES, BLOB_WINDOW_SIZE,
+};
+
+/// A source which a blob can be streamed from
+pub trait BlobSource: Read + Seek + Send + Sync {}
+
+impl<T: Read + Seek + Send + Sync> BlobSource for T {}
+
+enum BlobData {
+    Bytes(Vec<u8>),
+    Source(Box<dyn BlobSource>),
+}
+
+struct OutgoingBlob {
+    data: BlobData,
+    size: u32,
+    accepted: bool,
+    sent_bytes: u32,
+    delivered_bytes: u32,
+}
+
+/// What has become of a blob sent to a Client
+pub enum BlobStatus {
+    /// The Client has the whole blob, either downloaded or from its cache
+    Delivered,
+    /// The Client refused the blob
+    Rejected,
+    /// Reading the blob from its source failed, so the transfer was cancelled
+    Failed(io::Error),
+}
+
+/// Transfers blobs to a Client over the BulkChannel, keeping only a window
+/// of chunks in flight at once
+pub struct BlobSender {
+    bulk_channel: BulkChannel,
+    next_blob_id: BlobId,
+    outgoing_blobs: BTreeMap<BlobId, OutgoingBlob>,
+    statuses: Vec<(BlobId, BlobStatus)>,
+}
+
+impl Default for BlobSender {
+    fn default() -> Self {
+        Self {
+            bulk_channel: BulkChannel::new(),
+            next_blob_id: 0,
+            outgoing_blobs: BTreeMap::new(),
+            statuses: Vec::new(),
+        }
+    }
+}
+
+impl BlobSender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn send_blob(&mut self, name: &str, bytes: Vec<u8>) -> BlobId {
+        if bytes.len() > u32::MAX as usize {
+            panic!("a blob can be at most {} bytes", u32::MAX);
+        }
+
+        let size = bytes.len() as u32;
+        let hash = blob_hash(&bytes);
+        self.offer_blob(name, size, hash, BlobData::Bytes(bytes))
+    }
+
+    /// Reads through the source once to find its size and hash, then rewinds
+    /// it so that chunks can be read from it as the transfer goes on
+    pub fn send_blob_source(
+        &mut self,
+        name: &str,
+        mut source: Box<dyn BlobSource>,
+    ) -> io::Result<BlobId> {
+        let start = source.stream_position()?;
+
+        let mut hasher = BlobHasher::new();
+        let mut size: u64 = 0;
+        let mut buffer = [0; 4096];
+        loop {
+            let read = source.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            size += read as u64;
+        }
+
+        if size > u32::MAX as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("a blob can be at most {} bytes", u32::MAX),
+            ));
+        }
+
+        source.seek(SeekFrom::Start(start))?;
+
+        Ok(self.offer_blob(name, size as u32, hasher.finish(), BlobData::Source(source)))
+    }
+
+    fn offer_blob(&mut self, name: &str, size: u32, hash: BlobHash, data: BlobData) -> BlobId {
+        let blob_id = self.next_blob_id;
+        self.next_blob_id = self.next_blob_id.wrapping_add(1);
+
+        self.outgoing_blobs.insert(
+            blob_id,
+            OutgoingBlob {
+                data,
+                size,
+                accepted: false,
+                sent_bytes: 0,
+                delivered_bytes: 0,
+            },
+        );
+        self.bulk_channel.send_message(BlobMessage::Offer {
+            blob_id,
+            name: name.to_string(),
+            size,
+            hash,
+        });
+
+        blob_id
+    }
+
+    // Incoming
+
+    pub fn read_messages(&mut self, bit_reader: &mut BitReader) {
+        self.bulk_channel.read_messages(bit_reader);
+    }
+
+    /// Handles the Client's replies and acknowledged chunks, returning the
+    /// blobs which have finished one way or another since the last call
+    pub fn receive_statuses(&mut self) -> Vec<(BlobId, BlobStatus)> {
+        for message in self.bulk_channel.receive_messages() {
+            match message {
+                BlobMessage::Accept { blob_id } => {
+                    if let Some(blob) = self.outgoing_blobs.get_mut(&blob_id) {
+                        blob.accepted = true;
+                    }
+                }
+                BlobMessage::Cached { blob_id } => {
+                    self.finish_blob(blob_id, BlobStatus::Delivered);
+                }
+                BlobMessage::Cancel { blob_id } => {
+                    self.finish_blob(blob_id, BlobStatus::Rejected);
+                }
+                _ => {}
+            }
+        }
+
+        for message in self.bulk_channel.take_delivered_messages() {
+            if let BlobMessage::Chunk { blob_id, bytes, .. } = message {
+                if let Some(blob) = self.outgoing_blobs.get_mut(&blob_id) {
+                    blob.delivered_bytes += bytes.len() as u32;
+                    if blob.delivered_bytes >= blob.size {
+                        self.finish_blob(blob_id, BlobStatus::Delivered);
+                    }
+                }
+            }
+        }
+
+        mem::take(&mut self.statuses)
+    }
+
+    fn finish_blob(&mut self, blob_id: BlobId, status: BlobStatus) {
+        if self.outgoing_blobs.remove(&blob_id).is_some() {
+            self.statuses.push((blob_id, status));
+        }
+
+        // any chunks of the blob still in flight are no use to the Client now,
+        // so each is swapped for a Cancel, which takes up far less room
+        self.bulk_channel.replace_messages(|message| match message {
+            BlobMessage::Chunk {
+                blob_id: chunk_blob_id,
+                ..
+            } if *chunk_blob_id == blob_id => Some(BlobMessage::Cancel { blob_id }),
+            _ => None,
+        });
+    }
+
+    /// Counts the chunks which have not been delivered yet. A Cancel which
+    /// replaced a chunk, or was sent in place of one, holds its place in the
+    /// window until it is delivered too.
+    fn chunks_in_flight(&self) -> usize {
+        self.bulk_channel.count_messages(|message| {
+            matches!(
+                message,
+                BlobMessage::Chunk { .. } | BlobMessage::Cancel { .. }
+            )
+        })
+    }
+
+    // Outgoing
+
+    /// Tops up the window of chunks in flight, then gathers everything due to
+    /// be sent
+    pub fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+        let mut chunks_in_flight = self.chunks_in_flight();
+        while chunks_in_flight < BLOB_WINDOW_SIZE {
+            let (blob_id, blob) = match self
+                .outgoing_blobs
+                .iter_mut()
+                .find(|(_, blob)| blob.accepted && blob.sent_bytes < blob.size)
+            {
+                Some((blob_id, blob)) => (*blob_id, blob),
+                None => break,
+            };
+
+            let offset = blob.sent_bytes;
+            let length = BLOB_CHUNK_SIZE_BYTES.min((blob.size - offset) as usize);
+            let chunk = match &mut blob.data {
+                BlobData::Bytes(bytes) => {
+                    Ok(bytes[offset as usize..offset as usize + length].to_vec())
+                }
+                BlobData::Source(source) => {
+                    let mut chunk = vec![0; length];
+                    source.read_exact(&mut chunk).map(|_| chunk)
+                }
+            };
+
+            match chunk {
+                Ok(bytes) => {
+                    blob.sent_bytes += length as u32;
+                    chunks_in_flight += 1;
+                    self.bulk_channel.send_message(BlobMessage::Chunk {
+                        blob_id,
+                        offset,
+                        bytes,
+                    });
+                }
+                Err(error) => {
+                    chunks_in_flight += 1;
+                    self.bulk_channel
+                        .send_message(BlobMessage::Cancel { blob_id });
+                    self.finish_blob(blob_id, BlobStatus::Failed(error));
+                }
+            }
+        }
+
+        self.bulk_channel.collect_messages(now, rtt_millis);
+    }
+
+    pub fn has_outgoing_messages(&self) -> bool {
+        self.bulk_channel.has_outgoing_messages()
+    }
+
+    pub fn write_messages(&mut self, bit_writer: &mut BitWriter, packet_index: PacketIndex) {
+        self.bulk_channel.write_messages(bit_writer, packet_index);
+    }
+}
+
+impl PacketNotifiable for BlobSender {
+    fn notify_packet_delivered(&mut self, packet_index: PacketIndex) {
+        self.bulk_channel.notify_packet_delivered(packet_index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::{
+        serde::{BitReader, BitWriter},
+        BlobMessage, BulkChannel, Instant, PacketIndex, PacketNotifiable, BLOB_CHUNK_SIZE_BYTES,
+        BLOB_WINDOW_SIZE,
+    };
+
+    use super::{BlobSender, BlobStatus};
+
+    fn reply(sender: &mut BlobSender, client: &mut BulkChannel, message: BlobMessage) {
+        client.send_message(message);
+        client.collect_messages(&Instant::now(), &0.0);
+        let mut writer = BitWriter::default();
+        client.write_messages(&mut writer, 0);
+        let (length, buffer) = writer.flush();
+        sender.read_messages(&mut BitReader::new(&buffer[..length]));
+    }
+
+    fn write_packets(sender: &mut BlobSender, packet_indices: &mut Vec<PacketIndex>) {
+        sender.collect_messages(&Instant::now(), &0.0);
+        while sender.has_outgoing_messages() {
+            let packet_index = packet_indices.len() as PacketIndex;
+            let mut writer = BitWriter::default();
+            sender.write_messages(&mut writer, packet_index);
+            packet_indices.push(packet_index);
+        }
+    }
+
+    #[test]
+    fn cancelled_blobs_drain_the_window() {
+        let mut sender = BlobSender::new();
+        let mut client = BulkChannel::new();
+        let mut packet_indices = Vec::new();
+
+        let blob_size = BLOB_CHUNK_SIZE_BYTES * BLOB_WINDOW_SIZE * 2;
+        let blob_ids: Vec<_> = (0..3)
+            .map(|_| sender.send_blob("blob", vec![7; blob_size]))
+            .collect();
+
+        // each blob is accepted, then cancelled before any chunk is delivered
+        for blob_id in blob_ids {
+            reply(&mut sender, &mut client, BlobMessage::Accept { blob_id });
+            assert!(sender.receive_statuses().is_empty());
+            write_packets(&mut sender, &mut packet_indices);
+
+            reply(&mut sender, &mut client, BlobMessage::Cancel { blob_id });
+            let statuses = sender.receive_statuses();
+            assert!(matches!(
+                statuses.as_slice(),
+                [(cancelled_id, BlobStatus::Rejected)] if *cancelled_id == blob_id
+            ));
+            write_packets(&mut sender, &mut packet_indices);
+
+            // the Cancels which replaced the chunks still hold the window
+            assert_eq!(sender.chunks_in_flight(), BLOB_WINDOW_SIZE);
+        }
+
+        for packet_index in packet_indices {
+            sender.notify_packet_delivered(packet_index);
+        }
+        assert!(sender.receive_statuses().is_empty());
+        assert_eq!(sender.chunks_in_flight(), 0);
+    }
+}
@@ -2,33 +2,39 @@ use std::{
     hash::Hash,
     net::SocketAddr,
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 use naia_shared::{
     sequence_greater_than,
-    serde::{BitReader, BitWriter},
-    BaseConnection, ChannelConfig, ChannelIndex, ConnectionConfig, EntityConverter, HostType,
-    Instant, PacketType, PingManager, ProtocolIo, Protocolize, StandardHeader, Tick, WorldRefType,
+    serde::{BitReader, BitWriter, SerdeErr},
+    BandwidthBudget, BandwidthBudgetConfig, BaseConnection, ChannelConfig, ChannelIndex,
+    CongestionControl, ConnectionConfig, EntityConverter, HostType, Instant, NetEntityWidth,
+    PacketType, PingManager, ProtocolIo, Protocolize, StandardHeader, Tick, WorldRefType,
 };
 
 use crate::{
     protocol::{
         entity_manager::EntityManager, global_diff_handler::GlobalDiffHandler,
-        world_record::WorldRecord,
+        remote_entity_manager::RemoteEntityManager, world_record::WorldRecord,
     },
     tick::{tick_buffer_receiver::TickBufferReceiver, tick_manager::TickManager},
     user::UserKey,
 };
 
-use super::io::Io;
+use super::{blob_sender::BlobSender, io::Io};
 
 pub struct Connection<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> {
     pub user_key: UserKey,
     pub base: BaseConnection<P, C>,
     pub entity_manager: EntityManager<P, E, C>,
+    pub remote_entity_manager: RemoteEntityManager<P, E>,
     pub tick_buffer: TickBufferReceiver<P, C>,
     pub last_received_tick: Tick,
     pub ping_manager: PingManager,
+    pub blob_sender: BlobSender,
+    pub bandwidth_budget: Option<BandwidthBudget>,
+    pub congestion_control: Option<CongestionControl>,
 }
 
 impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connection<P, E, C> {
@@ -38,7 +44,26 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
         user_address: SocketAddr,
         user_key: &UserKey,
         diff_handler: &Arc<RwLock<GlobalDiffHandler<E, P::Kind>>>,
+        entity_cache_duration: Option<Duration>,
+        net_entity_width: NetEntityWidth,
     ) -> Self {
+        // congestion control needs a budget to lower
+        let bandwidth_budget_config = connection_config.bandwidth_budget.clone().or_else(|| {
+            connection_config
+                .congestion
+                .as_ref()
+                .map(|_| BandwidthBudgetConfig::default())
+        });
+        let congestion_control = connection_config
+            .congestion
+            .as_ref()
+            .map(|congestion_config| {
+                CongestionControl::new(
+                    congestion_config,
+                    bandwidth_budget_config.as_ref().unwrap().bytes_per_second,
+                )
+            });
+
         Connection {
             user_key: *user_key,
             base: BaseConnection::new(
@@ -47,18 +72,29 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
                 connection_config,
                 channel_config,
             ),
-            entity_manager: EntityManager::new(user_address, diff_handler),
+            entity_manager: EntityManager::new(
+                user_address,
+                diff_handler,
+                entity_cache_duration,
+                net_entity_width,
+            ),
+            remote_entity_manager: RemoteEntityManager::default(),
             tick_buffer: TickBufferReceiver::new(channel_config),
             ping_manager: PingManager::new(&connection_config.ping),
             last_received_tick: 0,
+            blob_sender: BlobSender::new(),
+            bandwidth_budget: bandwidth_budget_config.as_ref().map(BandwidthBudget::new),
+            congestion_control,
         }
     }
 
     // Incoming Data
 
     pub fn process_incoming_header(&mut self, header: &StandardHeader) {
-        self.base
-            .process_incoming_header(header, &mut Some(&mut self.entity_manager));
+        self.base.process_incoming_header(
+            header,
+            &mut [&mut self.entity_manager, &mut self.blob_sender],
+        );
     }
 
     pub fn recv_client_tick(&mut self, client_tick: Tick) {
@@ -72,7 +108,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
         server_and_client_tick_opt: Option<(Tick, Tick)>,
         bit_reader: &mut BitReader,
         world_record: &WorldRecord<E, P::Kind>,
-    ) {
+    ) -> Result<(), SerdeErr> {
         // Read Tick Buffered Messages
         if let Some((server_tick, client_tick)) = server_and_client_tick_opt {
             let converter = EntityConverter::new(world_record, &self.entity_manager);
@@ -89,6 +125,43 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
                 .message_manager
                 .read_messages(&channel_reader, bit_reader);
         }
+
+        // Read Entity Actions
+        {
+            let converter = EntityConverter::new(world_record, &self.entity_manager);
+            self.remote_entity_manager
+                .read_actions(&converter, bit_reader)?;
+        }
+
+        // Read Entity Updates
+        self.remote_entity_manager.read_updates(bit_reader)?;
+
+        // Read Blob replies
+        self.blob_sender.read_messages(bit_reader);
+
+        Ok(())
+    }
+
+    // Congestion
+
+    /// Evaluates the latest RTT and packet loss, lowering or raising the rate
+    /// of the bandwidth budget to match. Returns Some(true) when the
+    /// connection has just become congested, and Some(false) when it has
+    /// just recovered.
+    pub fn update_congestion(&mut self) -> Option<bool> {
+        let congestion_control = self.congestion_control.as_mut()?;
+
+        let changed = congestion_control.update(
+            self.ping_manager.rtt,
+            self.ping_manager.jitter,
+            self.base.packet_loss(),
+        );
+
+        if let Some(budget) = &mut self.bandwidth_budget {
+            budget.set_bytes_per_second(congestion_control.bytes_per_second());
+        }
+
+        changed
     }
 
     // Outgoing data
@@ -101,11 +174,30 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
         tick_manager_opt: &Option<TickManager>,
         rtt_millis: &f32,
     ) {
-        self.collect_outgoing_messages(now, rtt_millis);
+        let server_tick = tick_manager_opt
+            .as_ref()
+            .map(|tick_manager| tick_manager.server_tick());
+        self.collect_outgoing_messages(now, rtt_millis, world_record, server_tick);
+
+        if let Some(budget) = &mut self.bandwidth_budget {
+            budget.refill();
+        }
 
         let mut any_sent = false;
         loop {
-            if self.send_outgoing_packet(now, io, world, world_record, tick_manager_opt) {
+            // anything left over once the budget has been spent waits for a later frame
+            if let Some(budget) = &self.bandwidth_budget {
+                if !budget.can_send() {
+                    break;
+                }
+            }
+
+            if let Some(bytes) =
+                self.send_outgoing_packet(now, io, world, world_record, tick_manager_opt)
+            {
+                if let Some(budget) = &mut self.bandwidth_budget {
+                    budget.spend(bytes);
+                }
                 any_sent = true;
             } else {
                 break;
@@ -116,15 +208,24 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
         }
     }
 
-    fn collect_outgoing_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+    fn collect_outgoing_messages(
+        &mut self,
+        now: &Instant,
+        rtt_millis: &f32,
+        world_record: &WorldRecord<E, P::Kind>,
+        server_tick: Option<Tick>,
+    ) {
         self.entity_manager.collect_outgoing_messages(
             now,
             rtt_millis,
             &mut self.base.message_manager,
+            world_record,
+            server_tick,
         );
         self.base
             .message_manager
             .collect_outgoing_messages(now, rtt_millis);
+        self.blob_sender.collect_messages(now, rtt_millis);
     }
 
     fn send_outgoing_packet<W: WorldRefType<P, E>>(
@@ -134,9 +235,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
         world: &W,
         world_record: &WorldRecord<E, P::Kind>,
         tick_manager_opt: &Option<TickManager>,
-    ) -> bool {
+    ) -> Option<usize> {
         if self.base.message_manager.has_outgoing_messages()
             || self.entity_manager.has_outgoing_messages()
+            || self.blob_sender.has_outgoing_messages()
         {
             let next_packet_index = self.base.next_packet_index();
 
@@ -176,14 +278,25 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
                 world_record,
             );
 
+            // write blob chunks into whatever room is left
+            self.blob_sender
+                .write_messages(&mut bit_writer, next_packet_index);
+
             //info!("--------------\n");
 
             // send packet
-            io.send_writer(&self.base.address, &mut bit_writer);
+            let bytes = io.send_writer(&self.base.address, &mut bit_writer);
 
-            return true;
+            return Some(bytes);
         }
 
-        false
+        None
+    }
+
+    /// Returns the number of Messages, Entity actions and Component updates
+    /// waiting to be sent
+    pub fn outgoing_queue_depth(&self) -> usize {
+        self.base.message_manager.outgoing_message_count()
+            + self.entity_manager.outgoing_message_count()
     }
 }
@@ -82,8 +82,10 @@ impl<P: Protocolize> HandshakeManager<P> {
             }
 
             if has_auth {
-                let auth_message = P::read(reader, &FakeEntityConverter);
-                HandshakeResult::Success(Some(auth_message))
+                match P::read(reader, &FakeEntityConverter) {
+                    Ok(auth_message) => HandshakeResult::Success(Some(auth_message)),
+                    Err(_) => HandshakeResult::Invalid,
+                }
             } else {
                 HandshakeResult::Success(None)
             }
@@ -65,7 +65,9 @@ impl Io {
         self.packet_sender.is_some()
     }
 
-    pub fn send_writer(&mut self, address: &SocketAddr, writer: &mut BitWriter) {
+    /// Sends the contents of the writer to the given address, returning the
+    /// number of bytes sent
+    pub fn send_writer(&mut self, address: &SocketAddr, writer: &mut BitWriter) -> usize {
         // get payload
         let (length, buffer) = writer.flush();
         let mut payload = &buffer[0..length];
@@ -84,6 +86,8 @@ impl Io {
             .as_ref()
             .expect("Cannot call Server.send_packet() until you call Server.listen()!")
             .send(address, payload);
+
+        payload.len()
     }
 
     pub fn recv_reader(
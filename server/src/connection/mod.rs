@@ -1,4 +1,5 @@
 pub mod bandwidth_monitor;
+pub mod blob_sender;
 #[allow(clippy::module_inception)]
 pub mod connection;
 pub mod handshake_manager;
@@ -1,14 +1,37 @@
-use std::{error::Error, fmt};
+use std::{error::Error, fmt, io};
+
+use naia_shared::BlobId;
+
+use crate::user::UserKey;
 
 #[derive(Debug)]
 pub enum NaiaServerError {
     Wrapped(Box<dyn Error>),
+    /// Every NetEntity for the User's connection is in use, so Entities
+    /// entering their scope are left out until others leave it. Raise
+    /// `SharedConfig::net_entity_width` to address more Entities.
+    NetEntitiesExhausted(UserKey),
+    /// Reading a blob from the source given to `send_blob_source` failed, so
+    /// its transfer to the User was cancelled
+    BlobFailed(UserKey, BlobId, io::Error),
+    /// The User's Client spawned an Entity while it already had
+    /// `ServerConfig::max_client_entities` of them, so it was left out
+    ClientEntitiesExhausted(UserKey),
 }
 
 impl fmt::Display for NaiaServerError {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
             NaiaServerError::Wrapped(boxed_err) => fmt::Display::fmt(boxed_err.as_ref(), f),
+            NaiaServerError::NetEntitiesExhausted(_) => {
+                write!(f, "Naia Server Error: ran out of NetEntities for a User")
+            }
+            NaiaServerError::BlobFailed(_, _, error) => {
+                write!(f, "Naia Server Error: failed to read blob: {}", error)
+            }
+            NaiaServerError::ClientEntitiesExhausted(_) => {
+                write!(f, "Naia Server Error: a Client spawned too many Entities")
+            }
         }
     }
 }
@@ -1,10 +1,10 @@
-use naia_shared::{ChannelIndex, Protocolize};
+use naia_shared::{BlobId, ChannelIndex, Protocolize};
 
 use super::user::{User, UserKey};
 
 /// An Event that is emitted as a result of some communication with a Client, or
 /// a Tick event
-pub enum Event<P: Protocolize, C: ChannelIndex> {
+pub enum Event<P: Protocolize, E: Copy, C: ChannelIndex> {
     /// Occurs when a Client attempts to establish a connection with the Server.
     /// Used accept or reject incoming Clients
     Authorization(UserKey, P),
@@ -20,4 +20,47 @@ pub enum Event<P: Protocolize, C: ChannelIndex> {
     Tick,
     /// A Message emitted to the Server from a Client
     Message(UserKey, C, P),
+    /// Occurs when a Client has spawned an Entity of its own. The Entity has
+    /// already been added to the Server's World, but will not be in scope
+    /// for any other User until it is added to a Room. Despawn the Entity to
+    /// reject it.
+    SpawnEntity(UserKey, E),
+    /// Occurs when a Client has despawned an Entity it owns
+    DespawnEntity(UserKey, E),
+    /// Occurs when the Client which spawned an Entity has disconnected. The
+    /// Entity now belongs to the Server, which can keep it or despawn it.
+    EntityOrphaned(UserKey, E),
+    /// Occurs when a Client has added a Component to an Entity it owns
+    InsertComponent(UserKey, E, P::Kind),
+    /// Occurs when a Client has removed a Component from an Entity it owns
+    RemoveComponent(UserKey, E, P),
+    /// Occurs when a Client with authority over an Entity has updated one of
+    /// its Components
+    UpdateComponent(UserKey, E, P::Kind),
+    /// Occurs when a User has been given authority over an Entity
+    AuthorityGranted(UserKey, E),
+    /// Occurs when a User's authority over an Entity has been taken away,
+    /// either by the Server or because the Client released it
+    AuthorityRevoked(UserKey, E),
+    /// Occurs when a Client's request for authority over an Entity has been
+    /// denied
+    AuthorityDenied(UserKey, E),
+    /// Occurs when a User has been given authority over a single Component of
+    /// an Entity
+    ComponentAuthorityGranted(UserKey, E, P::Kind),
+    /// Occurs when a User's authority over a single Component of an Entity
+    /// has been taken away
+    ComponentAuthorityRevoked(UserKey, E, P::Kind),
+    /// Occurs when the connection to a Client has become congested. Until it
+    /// recovers, less data will be sent to the Client, so Component updates
+    /// will arrive less often.
+    CongestionDetected(UserKey),
+    /// Occurs when the connection to a Client has recovered from congestion
+    CongestionResolved(UserKey),
+    /// Occurs when a Client has the whole of a blob sent with `send_blob`,
+    /// whether it was downloaded or already in the Client's cache
+    BlobDelivered(UserKey, BlobId),
+    /// Occurs when a Client has refused a blob, usually because it is larger
+    /// than the Client allows
+    BlobRejected(UserKey, BlobId),
 }
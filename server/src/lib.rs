@@ -33,6 +33,8 @@ mod room;
 mod sequence_list;
 mod server;
 mod server_config;
+mod snapshot;
+mod spatial_config;
 mod tick;
 mod user;
 mod user_scope;
@@ -40,9 +42,12 @@ mod user_scope;
 pub use error::NaiaServerError;
 pub use event::Event;
 pub use protocol::entity_ref::EntityRef;
+pub use protocol::global_entity_record::EntityOwner;
 pub use room::{RoomKey, RoomMut, RoomRef};
 pub use server::Server;
 pub use server_config::ServerConfig;
+pub use snapshot::{RestoredSnapshot, SnapshotError};
+pub use spatial_config::SpatialConfig;
 pub use user::{User, UserKey, UserMut, UserRef};
 pub use user_scope::UserScopeMut;
 
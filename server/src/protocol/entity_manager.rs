@@ -1,5 +1,6 @@
 use std::{
     clone::Clone,
+    cmp::Ordering,
     collections::{HashMap, HashSet, VecDeque},
     hash::Hash,
     net::SocketAddr,
@@ -8,18 +9,19 @@ use std::{
 };
 
 use naia_shared::{
-    message_list_header,
+    message_list_header, sequence_less_than,
     serde::{BitCounter, BitWrite, BitWriter, Serde, UnsignedVariableInteger},
-    wrapping_diff, ChannelIndex, DiffMask, EntityAction, EntityActionType, EntityConverter,
-    Instant, MessageId, MessageManager, NetEntity, NetEntityConverter, PacketIndex,
-    PacketNotifiable, Protocolize, ReplicateSafe, WorldRefType, MTU_SIZE_BITS,
+    wrapping_diff, ChannelIndex, DiffMask, EntityAction, EntityActionEvent, EntityActionType,
+    EntityConverter, EntityHandleConverter, Instant, MessageId, MessageManager, NetEntity,
+    NetEntityConverter, NetEntityWidth, PacketIndex, PacketNotifiable, Protocolize, ReplicateSafe,
+    Tick, WorldRefType, MTU_SIZE_BITS,
 };
 
 use crate::sequence_list::SequenceList;
 
 use super::{
-    entity_action_event::EntityActionEvent, global_diff_handler::GlobalDiffHandler,
-    world_channel::WorldChannel, world_record::WorldRecord,
+    entity_priority::EntityPriority, global_diff_handler::GlobalDiffHandler,
+    update_schedule::UpdateSchedule, world_channel::WorldChannel, world_record::WorldRecord,
 };
 
 const DROP_UPDATE_RTT_FACTOR: f32 = 1.5;
@@ -32,6 +34,7 @@ pub type ActionId = MessageId;
 pub struct EntityManager<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> {
     // World
     world_channel: WorldChannel<P, E, C>,
+    net_entities_exhausted: bool,
     next_send_actions: VecDeque<(ActionId, EntityActionEvent<E, P::Kind>)>,
     #[allow(clippy::type_complexity)]
     sent_action_packets: SequenceList<(Instant, Vec<(ActionId, EntityAction<E, P::Kind>)>)>,
@@ -41,6 +44,10 @@ pub struct EntityManager<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: C
     #[allow(clippy::type_complexity)]
     sent_updates: HashMap<PacketIndex, (Instant, HashMap<(E, P::Kind), DiffMask>)>,
     last_update_packet_index: PacketIndex,
+    update_schedule: UpdateSchedule<E, P::Kind>,
+
+    // Priority
+    priority: EntityPriority<E>,
 }
 
 impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityManager<P, E, C> {
@@ -48,10 +55,18 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
     pub fn new(
         address: SocketAddr,
         diff_handler: &Arc<RwLock<GlobalDiffHandler<E, P::Kind>>>,
+        entity_cache_duration: Option<Duration>,
+        net_entity_width: NetEntityWidth,
     ) -> Self {
         EntityManager {
             // World
-            world_channel: WorldChannel::new(address, diff_handler),
+            world_channel: WorldChannel::new(
+                address,
+                diff_handler,
+                entity_cache_duration,
+                net_entity_width,
+            ),
+            net_entities_exhausted: false,
             next_send_actions: VecDeque::new(),
             sent_action_packets: SequenceList::new(),
 
@@ -59,17 +74,49 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
             next_send_updates: HashMap::new(),
             sent_updates: HashMap::new(),
             last_update_packet_index: 0,
+            update_schedule: UpdateSchedule::default(),
+
+            // Priority
+            priority: EntityPriority::default(),
         }
     }
 
     // World Scope
 
-    pub fn spawn_entity(&mut self, entity: &E) {
-        self.world_channel.host_spawn_entity(entity);
+    /// Adds the Entity to scope. Returns false if every NetEntity is already
+    /// in use, in which case the Entity stays out of scope.
+    pub fn spawn_entity(&mut self, entity: &E) -> bool {
+        if self.world_channel.host_spawn_entity(entity) {
+            self.net_entities_exhausted = false;
+            return true;
+        }
+        false
+    }
+
+    /// Returns true the first time it is called after running out of
+    /// NetEntities, so that running out is only reported once
+    pub fn report_net_entities_exhausted(&mut self) -> bool {
+        !std::mem::replace(&mut self.net_entities_exhausted, true)
     }
 
     pub fn despawn_entity(&mut self, entity: &E) {
         self.world_channel.host_despawn_entity(entity);
+        self.priority.remove(entity);
+        self.update_schedule.remove_entity(entity);
+    }
+
+    /// Removes the Entity from scope, hiding it on the Client if Entities are
+    /// being cached, or otherwise despawning it
+    pub fn exit_scope(&mut self, entity: &E) {
+        if self.world_channel.host_hide_entity(entity) {
+            self.priority.remove(entity);
+        } else {
+            self.despawn_entity(entity);
+        }
+    }
+
+    pub fn entity_is_hidden(&self, entity: &E) -> bool {
+        self.world_channel.host_entity_is_hidden(entity)
     }
 
     pub fn insert_component(&mut self, entity: &E, component: &P::Kind) {
@@ -78,6 +125,22 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
 
     pub fn remove_component(&mut self, entity: &E, component: &P::Kind) {
         self.world_channel.host_remove_component(entity, component);
+        self.update_schedule.remove_component(entity, component);
+    }
+
+    // Component Visibility
+
+    pub fn hide_component(&mut self, entity: &E, component: &P::Kind) {
+        self.world_channel.host_hide_component(entity, component);
+        self.update_schedule.remove_component(entity, component);
+    }
+
+    pub fn reveal_component(&mut self, entity: &E, component: &P::Kind) {
+        self.world_channel.host_reveal_component(entity, component);
+    }
+
+    pub fn clear_hidden_components(&mut self, entity: &E) {
+        self.world_channel.host_clear_hidden_components(entity);
     }
 
     pub fn scope_has_entity(&self, entity: &E) -> bool {
@@ -88,6 +151,63 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
         self.world_channel.entity_channel_is_open(entity)
     }
 
+    pub fn try_net_entity_to_entity(&self, net_entity: &NetEntity) -> Option<E> {
+        self.world_channel.net_entity_to_entity(net_entity).copied()
+    }
+
+    // Authority
+
+    pub fn has_authority(&self, entity: &E) -> bool {
+        self.world_channel.host_has_authority(entity)
+    }
+
+    pub fn grant_authority(&mut self, entity: &E) {
+        self.world_channel.host_grant_authority(entity);
+    }
+
+    pub fn revoke_authority(&mut self, entity: &E) {
+        self.world_channel.host_revoke_authority(entity);
+    }
+
+    pub fn deny_authority(&mut self, entity: &E) {
+        self.world_channel.host_deny_authority(entity);
+    }
+
+    pub fn has_component_authority(&self, entity: &E, component_kind: &P::Kind) -> bool {
+        self.world_channel
+            .host_has_component_authority(entity, component_kind)
+    }
+
+    pub fn grant_component_authority(&mut self, entity: &E, component_kind: &P::Kind) {
+        self.world_channel
+            .host_grant_component_authority(entity, component_kind);
+    }
+
+    pub fn revoke_component_authority(&mut self, entity: &E, component_kind: &P::Kind) {
+        self.world_channel
+            .host_revoke_component_authority(entity, component_kind);
+    }
+
+    // Ownership
+
+    pub fn has_ownership(&self, entity: &E) -> bool {
+        self.world_channel.host_has_ownership(entity)
+    }
+
+    pub fn grant_ownership(&mut self, entity: &E) {
+        self.world_channel.host_grant_ownership(entity);
+    }
+
+    pub fn revoke_ownership(&mut self, entity: &E) {
+        self.world_channel.host_revoke_ownership(entity);
+    }
+
+    // Priority
+
+    pub fn set_priority(&mut self, entity: &E, priority: f32) {
+        self.priority.set_priority(entity, priority);
+    }
+
     // Messages
 
     pub fn queue_entity_message<R: ReplicateSafe<P>>(
@@ -110,6 +230,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
         now: &Instant,
         rtt_millis: &f32,
         message_manager: &mut MessageManager<P, C>,
+        world_record: &WorldRecord<E, P::Kind>,
+        server_tick: Option<Tick>,
     ) {
         self.world_channel
             .delayed_entity_messages
@@ -118,15 +240,26 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
         self.collect_dropped_update_packets(rtt_millis);
 
         self.collect_dropped_action_packets();
+        self.collect_expired_hidden_entities();
         self.collect_next_actions(now, rtt_millis);
 
-        self.collect_component_updates();
+        self.update_schedule.set_now(now, server_tick);
+        self.collect_component_updates(world_record);
+
+        self.accumulate_priorities(world_record);
     }
 
     pub fn has_outgoing_messages(&self) -> bool {
         !self.next_send_actions.is_empty() || !self.next_send_updates.is_empty()
     }
 
+    /// Returns the number of Entity actions and Component updates waiting to
+    /// be sent
+    pub fn outgoing_message_count(&self) -> usize {
+        let update_count: usize = self.next_send_updates.values().map(HashSet::len).sum();
+        self.next_send_actions.len() + update_count
+    }
+
     pub fn write_all<W: WorldRefType<P, E>>(
         &mut self,
         now: &Instant,
@@ -160,8 +293,37 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
         }
     }
 
+    fn collect_expired_hidden_entities(&mut self) {
+        for entity in self.world_channel.take_expired_hidden_entities() {
+            self.despawn_entity(&entity);
+        }
+    }
+
     fn collect_next_actions(&mut self, now: &Instant, rtt_millis: &f32) {
-        self.next_send_actions = self.world_channel.take_next_actions(now, rtt_millis);
+        // actions deferred from previous frames are kept, so only add new ones
+        let waiting_ids: HashSet<ActionId> = self
+            .next_send_actions
+            .iter()
+            .map(|(action_id, _)| *action_id)
+            .collect();
+        for (action_id, action) in self.world_channel.take_next_actions(now, rtt_millis) {
+            if !waiting_ids.contains(&action_id) {
+                self.next_send_actions.push_back((action_id, action));
+            }
+        }
+
+        // actions must be written in the order they were sent
+        self.next_send_actions
+            .make_contiguous()
+            .sort_by(|(id_a, _), (id_b, _)| {
+                if id_a == id_b {
+                    Ordering::Equal
+                } else if sequence_less_than(*id_a, *id_b) {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            });
     }
 
     fn collect_dropped_update_packets(&mut self, rtt_millis: &f32) {
@@ -194,13 +356,11 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                 }
                 let mut new_diff_mask = diff_mask.clone();
 
-                // walk from dropped packet up to most recently sent packet
-                if dropped_packet_index == self.last_update_packet_index {
-                    continue;
-                }
-
+                // walk from dropped packet up to most recently sent packet, a
+                // replicated collection stays queued unless a later packet
+                // resent all of the changes which were dropped
                 let mut packet_index = dropped_packet_index.wrapping_add(1);
-                while packet_index != self.last_update_packet_index {
+                while packet_index != self.last_update_packet_index.wrapping_add(1) {
                     if let Some((_, diff_mask_map)) = self.sent_updates.get(&packet_index) {
                         if let Some(next_diff_mask) = diff_mask_map.get(component_index) {
                             new_diff_mask.nand(next_diff_mask);
@@ -217,8 +377,47 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
         }
     }
 
-    fn collect_component_updates(&mut self) {
-        self.next_send_updates = self.world_channel.collect_next_updates();
+    fn collect_component_updates(&mut self, world_record: &WorldRecord<E, P::Kind>) {
+        let mut next_send_updates = self.world_channel.collect_next_updates(world_record);
+
+        // hold back updates to Components which are not yet due, their changes
+        // keep accumulating in the diff mask until then
+        next_send_updates.retain(|entity, component_kinds| {
+            component_kinds.retain(|component_kind| {
+                let update_rate = world_record.update_rate(entity, component_kind);
+                self.update_schedule
+                    .is_due(entity, component_kind, update_rate)
+            });
+            !component_kinds.is_empty()
+        });
+
+        self.next_send_updates = next_send_updates;
+    }
+
+    /// Grows the priority of every Entity which has an update or spawn waiting
+    /// to be sent, so that Entities which keep missing out on a packet are
+    /// eventually written first
+    fn accumulate_priorities(&mut self, world_record: &WorldRecord<E, P::Kind>) {
+        for (entity, component_kinds) in &self.next_send_updates {
+            let base_priority = world_record.entity_base_priority(component_kinds.iter());
+            self.priority.accumulate(entity, base_priority);
+        }
+
+        for (_, action) in &self.next_send_actions {
+            if let EntityActionEvent::SpawnEntity(entity) = action {
+                let component_kinds = world_record.component_kinds(entity).unwrap_or_default();
+                let base_priority = world_record.entity_base_priority(component_kinds.iter());
+                self.priority.accumulate(entity, base_priority);
+            }
+        }
+    }
+
+    fn compare_priority(&self, entity_a: &E, entity_b: &E) -> Ordering {
+        // highest accumulated priority first
+        self.priority
+            .accumulated(entity_b)
+            .partial_cmp(&self.priority.accumulated(entity_a))
+            .unwrap_or(Ordering::Equal)
     }
 
     // Writing actions
@@ -248,7 +447,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
         world: &W,
         world_record: &WorldRecord<E, <P as Protocolize>::Kind>,
     ) {
-        let mut message_count = 0;
+        // indices into next_send_actions, in the order they were sent
+        let mut action_indices: Vec<usize> = Vec::new();
 
         // Header
         {
@@ -268,22 +468,39 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                 return;
             }
 
-            // Find how many messages will fit into the packet
-            let next_send_actions_len = self.next_send_actions.len();
-            let mut last_written_id: Option<ActionId> = None;
+            // Find which actions will fit into the packet, most urgent first
+            let mut action_order: Vec<usize> = (0..self.next_send_actions.len()).collect();
+            action_order
+                .sort_by(|index_a, index_b| self.compare_action_priority(*index_a, *index_b));
+
+            let mut packet_bits = u32::from(current_packet_size + counter.bit_count());
 
-            for action_index in 0..next_send_actions_len {
+            for action_index in action_order {
+                // measure the action without its id, which depends on what it is written after
+                let action_id = self.next_send_actions.get(action_index).unwrap().0;
+                let mut action_counter = BitCounter::default();
                 self.write_action(
                     world,
                     world_record,
                     packet_index,
-                    &mut counter,
+                    &mut action_counter,
                     action_index,
-                    &mut last_written_id,
+                    &mut None,
                     false,
                 );
-                if current_packet_size + counter.bit_count() <= MTU_SIZE_BITS {
-                    message_count += 1;
+                let action_bits =
+                    u32::from(action_counter.bit_count()) - Self::action_id_bits(None, action_id);
+
+                // actions are always written in the order they were sent, because only the
+                // difference between consecutive ids is written
+                let position = action_indices.binary_search(&action_index).unwrap_err();
+                let mut new_action_indices = action_indices.clone();
+                new_action_indices.insert(position, action_index);
+                let new_id_bits = self.action_ids_bits(&new_action_indices);
+
+                if packet_bits + action_bits + new_id_bits <= u32::from(MTU_SIZE_BITS) {
+                    packet_bits += action_bits;
+                    action_indices = new_action_indices;
                 } else {
                     break;
                 }
@@ -291,7 +508,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
         }
 
         // Write header
-        message_list_header::write(writer, message_count as u64);
+        message_list_header::write(writer, action_indices.len() as u64);
 
         if !self
             .sent_action_packets
@@ -306,23 +523,68 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
             let mut last_written_id: Option<ActionId> = None;
 
             // Write messages
-            for action_index in 0..message_count {
+            for action_index in &action_indices {
                 self.write_action(
                     world,
                     world_record,
                     packet_index,
                     writer,
-                    action_index,
+                    *action_index,
                     &mut last_written_id,
                     true,
                 );
+
+                if let Some(entity) = self.spawned_entity(*action_index) {
+                    self.priority.reset(&entity);
+                }
             }
 
             // Pop messages
-            self.next_send_actions.drain(..message_count);
+            for action_index in action_indices.iter().rev() {
+                self.next_send_actions.remove(*action_index);
+            }
         }
     }
 
+    /// Spawns are ordered by the priority of their Entity, behind every other
+    /// kind of action, which keep the order they were sent in
+    fn compare_action_priority(&self, index_a: usize, index_b: usize) -> Ordering {
+        let spawn_a = self.spawned_entity(index_a);
+        let spawn_b = self.spawned_entity(index_b);
+        match (spawn_a, spawn_b) {
+            (Some(entity_a), Some(entity_b)) => self.compare_priority(&entity_a, &entity_b),
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    fn spawned_entity(&self, action_index: usize) -> Option<E> {
+        if let Some((_, EntityActionEvent::SpawnEntity(entity))) =
+            self.next_send_actions.get(action_index)
+        {
+            return Some(*entity);
+        }
+        None
+    }
+
+    fn action_id_bits(mut last_id_opt: Option<ActionId>, current_id: ActionId) -> u32 {
+        let mut counter = BitCounter::default();
+        Self::write_action_id(&mut counter, &mut last_id_opt, &current_id);
+        u32::from(counter.bit_count())
+    }
+
+    fn action_ids_bits(&self, action_indices: &[usize]) -> u32 {
+        let mut bits = 0;
+        let mut last_id_opt: Option<ActionId> = None;
+        for action_index in action_indices {
+            let action_id = self.next_send_actions.get(*action_index).unwrap().0;
+            bits += Self::action_id_bits(last_id_opt, action_id);
+            last_id_opt = Some(action_id);
+        }
+        bits
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn write_action<W: WorldRefType<P, E>>(
         &mut self,
@@ -349,11 +611,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                     .unwrap()
                     .ser(bit_writer);
 
-                // get component list
-                let component_kinds = match world_record.component_kinds(entity) {
-                    Some(kind_list) => kind_list,
-                    None => Vec::new(),
-                };
+                // get component list, leaving out any hidden from this user
+                let component_kinds = self.world_channel.host_component_kinds(entity);
+                let is_owner = self.world_channel.host_has_ownership(entity);
 
                 // write number of components
                 let components_num =
@@ -367,13 +627,23 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                     world
                         .component_of_kind(entity, component_kind)
                         .expect("Component does not exist in World")
-                        .write(bit_writer, &converter);
+                        .write_for_user(bit_writer, &converter, is_owner);
                 }
 
                 // if we are writing to this packet, add it to record
                 if is_writing {
                     //info!("write SpawnEntity({})", action_id);
 
+                    for component_kind in &component_kinds {
+                        Self::record_component_references(
+                            &mut self.world_channel,
+                            world,
+                            world_record,
+                            entity,
+                            component_kind,
+                        );
+                    }
+
                     Self::record_action_written(
                         &mut self.sent_action_packets,
                         packet_index,
@@ -430,18 +700,27 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                         .unwrap()
                         .ser(bit_writer);
 
+                    let is_owner = self.world_channel.host_has_ownership(entity);
                     let converter = EntityConverter::new(world_record, self);
 
                     // write component payload
                     world
                         .component_of_kind(entity, component)
                         .expect("Component does not exist in World")
-                        .write(bit_writer, &converter);
+                        .write_for_user(bit_writer, &converter, is_owner);
 
                     // if we are actually writing this packet
                     if is_writing {
                         //info!("write InsertComponent({})", action_id);
 
+                        Self::record_component_references(
+                            &mut self.world_channel,
+                            world,
+                            world_record,
+                            entity,
+                            component,
+                        );
+
                         // add it to action record
                         Self::record_action_written(
                             &mut self.sent_action_packets,
@@ -493,6 +772,113 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                     }
                 }
             }
+            EntityActionEvent::GrantAuthority(entity)
+            | EntityActionEvent::RevokeAuthority(entity)
+            | EntityActionEvent::DenyAuthority(entity)
+            | EntityActionEvent::GrantOwnership(entity)
+            | EntityActionEvent::RevokeOwnership(entity)
+            | EntityActionEvent::HideEntity(entity)
+            | EntityActionEvent::RevealEntity(entity) => {
+                let action_record = if !self.world_channel.entity_channel_is_open(entity) {
+                    EntityActionType::Noop.ser(bit_writer);
+
+                    EntityAction::Noop
+                } else {
+                    let (action_type, action_record) = match action {
+                        EntityActionEvent::GrantAuthority(_) => (
+                            EntityActionType::GrantAuthority,
+                            EntityAction::GrantAuthority(*entity),
+                        ),
+                        EntityActionEvent::RevokeAuthority(_) => (
+                            EntityActionType::RevokeAuthority,
+                            EntityAction::RevokeAuthority(*entity),
+                        ),
+                        EntityActionEvent::GrantOwnership(_) => (
+                            EntityActionType::GrantOwnership,
+                            EntityAction::GrantOwnership(*entity),
+                        ),
+                        EntityActionEvent::RevokeOwnership(_) => (
+                            EntityActionType::RevokeOwnership,
+                            EntityAction::RevokeOwnership(*entity),
+                        ),
+                        EntityActionEvent::HideEntity(_) => (
+                            EntityActionType::HideEntity,
+                            EntityAction::HideEntity(*entity),
+                        ),
+                        EntityActionEvent::RevealEntity(_) => (
+                            EntityActionType::RevealEntity,
+                            EntityAction::RevealEntity(*entity),
+                        ),
+                        _ => (
+                            EntityActionType::DenyAuthority,
+                            EntityAction::DenyAuthority(*entity),
+                        ),
+                    };
+                    action_type.ser(bit_writer);
+
+                    // write net entity
+                    self.world_channel
+                        .entity_to_net_entity(entity)
+                        .unwrap()
+                        .ser(bit_writer);
+
+                    action_record
+                };
+
+                // if we are writing to this packet, add it to record
+                if is_writing {
+                    Self::record_action_written(
+                        &mut self.sent_action_packets,
+                        packet_index,
+                        action_id,
+                        action_record,
+                    );
+                }
+            }
+            EntityActionEvent::GrantComponentAuthority(entity, component)
+            | EntityActionEvent::RevokeComponentAuthority(entity, component) => {
+                let action_record = if !self.world_channel.entity_channel_is_open(entity) {
+                    EntityActionType::Noop.ser(bit_writer);
+
+                    EntityAction::Noop
+                } else {
+                    let (action_type, action_record) = match action {
+                        EntityActionEvent::GrantComponentAuthority(_, _) => (
+                            EntityActionType::GrantComponentAuthority,
+                            EntityAction::GrantComponentAuthority(*entity, *component),
+                        ),
+                        _ => (
+                            EntityActionType::RevokeComponentAuthority,
+                            EntityAction::RevokeComponentAuthority(*entity, *component),
+                        ),
+                    };
+                    action_type.ser(bit_writer);
+
+                    // write net entity
+                    self.world_channel
+                        .entity_to_net_entity(entity)
+                        .unwrap()
+                        .ser(bit_writer);
+
+                    // write component kind
+                    component.ser(bit_writer);
+
+                    action_record
+                };
+
+                // if we are writing to this packet, add it to record
+                if is_writing {
+                    Self::record_action_written(
+                        &mut self.sent_action_packets,
+                        packet_index,
+                        action_id,
+                        action_record,
+                    );
+                }
+            }
+            EntityActionEvent::RequestAuthority(_) | EntityActionEvent::ReleaseAuthority(_) => {
+                panic!("Server should never request authority over an Entity");
+            }
         }
     }
 
@@ -507,6 +893,28 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
         sent_actions_list.push((*action_id, action_record));
     }
 
+    fn record_component_references<W: WorldRefType<P, E>>(
+        world_channel: &mut WorldChannel<P, E, C>,
+        world: &W,
+        world_record: &WorldRecord<E, <P as Protocolize>::Kind>,
+        entity: &E,
+        component_kind: &P::Kind,
+    ) {
+        let component = world
+            .component_of_kind(entity, component_kind)
+            .expect("Component does not exist in World");
+        if !component.has_entity_properties() {
+            return;
+        }
+
+        let references: HashSet<E> = component
+            .entities()
+            .iter()
+            .map(|handle| world_record.handle_to_entity(handle))
+            .collect();
+        world_channel.set_component_references(entity, component_kind, references);
+    }
+
     fn write_updates<W: WorldRefType<P, E>>(
         &mut self,
         now: &Instant,
@@ -535,8 +943,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                 return;
             }
 
-            // Find how many messages will fit into the packet
-            let all_update_entities: Vec<E> = self.next_send_updates.keys().copied().collect();
+            // Find how many messages will fit into the packet, most urgent first
+            let mut all_update_entities: Vec<E> = self.next_send_updates.keys().copied().collect();
+            all_update_entities
+                .sort_by(|entity_a, entity_b| self.compare_priority(entity_a, entity_b));
 
             for update_entity in all_update_entities {
                 self.write_update(
@@ -622,11 +1032,12 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
 
             // write payload
             {
+                let is_owner = self.world_channel.host_has_ownership(entity);
                 let converter = EntityConverter::new(world_record, self);
                 world
                     .component_of_kind(entity, component_kind)
                     .expect("Component does not exist in World")
-                    .write_update(&diff_mask, bit_writer, &converter);
+                    .write_update_for_user(&diff_mask, bit_writer, &converter, is_owner);
             }
 
             ////////
@@ -635,6 +1046,15 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
 
                 // place diff mask in a special transmission record - like map
                 self.last_update_packet_index = *packet_index;
+                self.priority.reset(entity);
+                self.update_schedule.record_sent(entity, component_kind);
+                Self::record_component_references(
+                    &mut self.world_channel,
+                    world,
+                    world_record,
+                    entity,
+                    component_kind,
+                );
 
                 let (_, sent_updates_map) = self.sent_updates.get_mut(packet_index).unwrap();
                 sent_updates_map.insert((*entity, *component_kind), diff_mask);
@@ -685,4 +1105,17 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> NetEnti
             .net_entity_to_entity(net_entity)
             .expect("entity does not exist for this connection!");
     }
+
+    fn try_entity_to_net_entity(&self, entity: &E) -> Option<NetEntity> {
+        // until the Client has confirmed the spawn, it could not resolve the
+        // reference
+        if !self.world_channel.entity_channel_is_open(entity) {
+            return None;
+        }
+        self.world_channel.entity_to_net_entity(entity).copied()
+    }
+
+    fn try_net_entity_to_entity(&self, net_entity: &NetEntity) -> Option<E> {
+        self.world_channel.net_entity_to_entity(net_entity).copied()
+    }
 }
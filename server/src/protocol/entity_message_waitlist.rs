@@ -4,7 +4,7 @@ use std::{
     hash::Hash,
 };
 
-type MessageHandle = u16;
+type MessageHandle = u32;
 
 pub struct EntityMessageWaitlist<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> {
     message_handle_store: KeyGenerator<MessageHandle>,
@@ -20,7 +20,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Default
     fn default() -> Self {
         Self {
             messages: HashMap::default(),
-            message_handle_store: KeyGenerator::default(),
+            message_handle_store: KeyGenerator::new(u32::MAX),
             waiting_entities: HashMap::default(),
             in_scope_entities: HashSet::default(),
             ready_messages: Vec::default(),
@@ -30,7 +30,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Default
 
 impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> EntityMessageWaitlist<P, E, C> {
     pub fn queue_message(&mut self, entities: Vec<E>, channel: C, message: P) {
-        let new_handle = self.message_handle_store.generate();
+        let new_handle = self
+            .message_handle_store
+            .generate()
+            .expect("ran out of handles for messages waiting on Entities");
 
         for entity in &entities {
             if !self.waiting_entities.contains_key(entity) {
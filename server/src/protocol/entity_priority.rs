@@ -0,0 +1,53 @@
+use std::{collections::HashMap, hash::Hash};
+
+pub const DEFAULT_PRIORITY: f32 = 1.0;
+
+/// Keeps track of how urgently each Entity in a User's scope needs to be sent.
+/// Every time an Entity has data waiting to be sent, its accumulator grows by
+/// its priority, and is reset once the data has been written to a packet.
+pub struct EntityPriority<E: Copy + Eq + Hash> {
+    priorities: HashMap<E, f32>,
+    accumulators: HashMap<E, f32>,
+}
+
+impl<E: Copy + Eq + Hash> Default for EntityPriority<E> {
+    fn default() -> Self {
+        Self {
+            priorities: HashMap::new(),
+            accumulators: HashMap::new(),
+        }
+    }
+}
+
+impl<E: Copy + Eq + Hash> EntityPriority<E> {
+    pub fn set_priority(&mut self, entity: &E, priority: f32) {
+        self.priorities.insert(*entity, priority.max(0.0));
+    }
+
+    pub fn priority(&self, entity: &E) -> f32 {
+        self.priorities
+            .get(entity)
+            .copied()
+            .unwrap_or(DEFAULT_PRIORITY)
+    }
+
+    /// Grows the accumulator of the given Entity, scaled by the base priority
+    /// of the Components waiting to be sent
+    pub fn accumulate(&mut self, entity: &E, base_priority: f32) {
+        let priority = self.priority(entity) * base_priority;
+        *self.accumulators.entry(*entity).or_insert(0.0) += priority;
+    }
+
+    pub fn accumulated(&self, entity: &E) -> f32 {
+        self.accumulators.get(entity).copied().unwrap_or(0.0)
+    }
+
+    pub fn reset(&mut self, entity: &E) {
+        self.accumulators.remove(entity);
+    }
+
+    pub fn remove(&mut self, entity: &E) {
+        self.priorities.remove(entity);
+        self.accumulators.remove(entity);
+    }
+}
@@ -2,10 +2,10 @@ use std::{hash::Hash, marker::PhantomData};
 
 use naia_shared::{
     ChannelIndex, Protocolize, ReplicaMutWrapper, ReplicaRefWrapper, Replicate, ReplicateSafe,
-    WorldMutType, WorldRefType,
+    UpdateRate, WorldMutType, WorldRefType,
 };
 
-use crate::{room::RoomKey, server::Server};
+use crate::{room::RoomKey, server::Server, user::UserKey};
 
 // EntityRef
 
@@ -114,8 +114,42 @@ impl<
             .remove_component::<R, W>(&mut self.world, &self.entity)
     }
 
+    /// Limits how often updates to all of the Entity's Components are sent,
+    /// overriding the UpdateRate of each Component's type
+    pub fn set_update_rate(&mut self, update_rate: UpdateRate) -> &mut Self {
+        self.server
+            .entity_set_update_rate(&self.entity, Some(update_rate));
+
+        self
+    }
+
+    /// Removes any UpdateRate set on the Entity, so that each Component is
+    /// sent at the UpdateRate of its type
+    pub fn clear_update_rate(&mut self) -> &mut Self {
+        self.server.entity_set_update_rate(&self.entity, None);
+
+        self
+    }
+
+    /// Pauses or resumes sending updates to the Entity's Components, without
+    /// despawning it for Clients which have it in scope. Changes made while
+    /// the Entity is dormant are sent as a single update once it wakes.
+    pub fn set_dormant(&mut self, is_dormant: bool) -> &mut Self {
+        self.server.entity_set_dormant(&self.entity, is_dormant);
+
+        self
+    }
+
     // Rooms
 
+    /// Sets the position of the Entity, used to determine which Users it is
+    /// in scope for in Rooms with spatial scoping enabled
+    pub fn set_position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.server.entity_set_position(&self.entity, x, y);
+
+        self
+    }
+
     pub fn enter_room(&mut self, room_key: &RoomKey) -> &mut Self {
         self.server.room_add_entity(room_key, &self.entity);
 
@@ -127,4 +161,79 @@ impl<
 
         self
     }
+
+    // Authority
+
+    /// Gives the User authority over the Entity, so that changes their Client
+    /// makes to the Entity's Components are replicated to the Server and all
+    /// other Clients. Changes made by the Server are ignored until authority
+    /// is taken back.
+    pub fn give_authority(&mut self, user_key: &UserKey) -> &mut Self {
+        self.server.entity_give_authority(&self.entity, user_key);
+
+        self
+    }
+
+    /// Takes authority over the Entity back from whichever User has it
+    pub fn take_authority(&mut self) -> &mut Self {
+        self.server.entity_take_authority(&self.entity);
+
+        self
+    }
+
+    /// Gives the User authority over one of the Entity's Components, so that
+    /// changes their Client makes to it are replicated to the Server and all
+    /// other Clients. Takes precedence over authority over the whole Entity.
+    pub fn give_component_authority<R: ReplicateSafe<P>>(
+        &mut self,
+        user_key: &UserKey,
+    ) -> &mut Self {
+        self.server
+            .entity_give_component_authority(&self.entity, &P::kind_of::<R>(), user_key);
+
+        self
+    }
+
+    /// Takes authority over one of the Entity's Components back from
+    /// whichever User has it
+    pub fn take_component_authority<R: ReplicateSafe<P>>(&mut self) -> &mut Self {
+        self.server
+            .entity_take_component_authority(&self.entity, &P::kind_of::<R>());
+
+        self
+    }
+
+    /// Allows Clients to request authority over the Entity, which will be
+    /// granted as long as no other User has authority over it
+    pub fn enable_delegation(&mut self) -> &mut Self {
+        self.server.entity_set_delegated(&self.entity, true);
+
+        self
+    }
+
+    /// Denies any further authority requests from Clients. Does not affect
+    /// a User who already has authority over the Entity.
+    pub fn disable_delegation(&mut self) -> &mut Self {
+        self.server.entity_set_delegated(&self.entity, false);
+
+        self
+    }
+
+    // Ownership
+
+    /// Makes the User the owner of the Entity. Properties marked with
+    /// `#[property(owner_only)]` are only replicated to the owner, every other
+    /// User receives their default value instead
+    pub fn set_owner(&mut self, user_key: &UserKey) -> &mut Self {
+        self.server.entity_set_owner(&self.entity, user_key);
+
+        self
+    }
+
+    /// Removes the Entity's owner, if it has one
+    pub fn remove_owner(&mut self) -> &mut Self {
+        self.server.entity_remove_owner(&self.entity);
+
+        self
+    }
 }
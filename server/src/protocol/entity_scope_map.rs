@@ -26,6 +26,10 @@ impl<E: Copy + Eq + Hash> EntityScopeMap<E> {
         self.main_map.get(&key)
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&(UserKey, E), &bool)> {
+        self.main_map.iter()
+    }
+
     pub fn insert(&mut self, user_key: UserKey, entity: E, in_scope: bool) {
         self.entities_of_user
             .entry(user_key)
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, hash::Hash, net::SocketAddr};
 
-use naia_shared::ProtocolKindType;
+use naia_shared::{DiffMask, ProtocolKindType};
 
 use super::mut_channel::{MutChannel, MutReceiver, MutReceiverBuilder, MutSender};
 
@@ -44,6 +44,29 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> GlobalDiffHandler<E, K> {
             .remove(&(*entity, *component_kind));
     }
 
+    /// Sets which Client has authority over the Component, if any. While a
+    /// Client has authority, changes made to the Component by the Server are
+    /// ignored
+    pub fn set_authority(&self, entity: &E, component_kind: &K, authority: Option<SocketAddr>) {
+        if let Some(builder) = self.mut_receiver_builders.get(&(*entity, *component_kind)) {
+            builder.channel().set_authority(authority);
+        }
+    }
+
+    /// Queues changes made to the Component by the Client at the given address
+    /// to be sent to every other Client
+    pub fn remote_mutate(
+        &self,
+        addr: &SocketAddr,
+        entity: &E,
+        component_kind: &K,
+        diff_mask: &DiffMask,
+    ) {
+        if let Some(builder) = self.mut_receiver_builders.get(&(*entity, *component_kind)) {
+            builder.channel().send_remote(addr, diff_mask);
+        }
+    }
+
     pub fn receiver(
         &self,
         addr: &SocketAddr,
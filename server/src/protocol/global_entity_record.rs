@@ -1,18 +1,41 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use naia_shared::{EntityHandle, ProtocolKindType};
+use naia_shared::{EntityHandle, ProtocolKindType, UpdateRate};
 
-use crate::room::RoomKey;
+use crate::{room::RoomKey, user::UserKey};
+
+/// Describes whether an Entity was spawned by the Server itself, or by a
+/// Client and replicated to the Server
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EntityOwner {
+    Server,
+    Client(UserKey),
+}
 
 pub struct GlobalEntityRecord<K: ProtocolKindType> {
+    pub owner: EntityOwner,
+    pub authority: Option<UserKey>,
+    // Users with authority over a single Component, rather than the whole Entity
+    pub component_authority: HashMap<K, UserKey>,
+    pub owning_user: Option<UserKey>,
+    pub delegated: bool,
+    pub update_rate: Option<UpdateRate>,
+    pub is_dormant: bool,
     pub room_key: Option<RoomKey>,
     pub entity_handle: EntityHandle,
     pub component_kinds: HashSet<K>,
 }
 
 impl<K: ProtocolKindType> GlobalEntityRecord<K> {
-    pub fn new(entity_handle: EntityHandle) -> Self {
+    pub fn new(owner: EntityOwner, entity_handle: EntityHandle) -> Self {
         Self {
+            owner,
+            authority: None,
+            component_authority: HashMap::new(),
+            owning_user: None,
+            delegated: false,
+            update_rate: None,
+            is_dormant: false,
             room_key: None,
             entity_handle,
             component_kinds: HashSet::new(),
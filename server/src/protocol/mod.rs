@@ -1,11 +1,15 @@
-pub mod entity_action_event;
 pub mod entity_manager;
 pub mod entity_message_waitlist;
+pub mod entity_priority;
 pub mod entity_ref;
 pub mod entity_scope_map;
 pub mod global_diff_handler;
 pub mod global_entity_record;
 pub mod mut_channel;
+pub mod remote_entity_manager;
+pub mod scope_cache;
+pub mod spatial_grid;
+pub mod update_schedule;
 pub mod user_diff_handler;
 pub mod world_channel;
 pub mod world_record;
@@ -47,11 +47,32 @@ impl MutChannel {
         }
         false
     }
+
+    pub fn send_delta(&self, diff: u8, base_version: u64) -> bool {
+        if let Ok(data) = self.data.as_ref().read() {
+            data.send_delta(diff, base_version);
+            return true;
+        }
+        false
+    }
+
+    pub fn set_authority(&self, authority: Option<SocketAddr>) {
+        if let Ok(mut data) = self.data.as_ref().write() {
+            data.set_authority(authority);
+        }
+    }
+
+    pub fn send_remote(&self, addr: &SocketAddr, diff_mask: &DiffMask) {
+        if let Ok(data) = self.data.as_ref().read() {
+            data.send_remote(addr, diff_mask);
+        }
+    }
 }
 
 struct MutChannelData {
     recv_map: HashMap<SocketAddr, MutReceiver>,
     diff_mask_length: u8,
+    authority: Option<SocketAddr>,
 }
 
 impl MutChannelData {
@@ -59,6 +80,7 @@ impl MutChannelData {
         Self {
             recv_map: HashMap::new(),
             diff_mask_length,
+            authority: None,
         }
     }
 
@@ -74,10 +96,47 @@ impl MutChannelData {
     }
 
     pub fn send(&self, diff: u8) {
+        if self.authority.is_some() {
+            // a Client has authority, so changes made by the Server are ignored
+            return;
+        }
         for (_, receiver) in self.recv_map.iter() {
             receiver.mutate(diff);
         }
     }
+
+    pub fn send_delta(&self, diff: u8, base_version: u64) {
+        if self.authority.is_some() {
+            return;
+        }
+        for (_, receiver) in self.recv_map.iter() {
+            receiver.mutate_delta(diff, base_version);
+        }
+    }
+
+    pub fn set_authority(&mut self, authority: Option<SocketAddr>) {
+        // the previous authority may have made changes which never reached the
+        // Server, so resend the whole Component to them
+        if let Some(old_authority) = self.authority {
+            if authority != Some(old_authority) {
+                if let Some(receiver) = self.recv_map.get(&old_authority) {
+                    receiver.or_mask(&DiffMask::new_full(self.diff_mask_length));
+                }
+            }
+        }
+
+        self.authority = authority;
+    }
+
+    pub fn send_remote(&self, addr: &SocketAddr, diff_mask: &DiffMask) {
+        for (receiver_addr, receiver) in self.recv_map.iter() {
+            if receiver_addr == addr {
+                // don't echo changes back to the Client that made them
+                continue;
+            }
+            receiver.or_mask(diff_mask);
+        }
+    }
 }
 
 // MutReceiver
@@ -110,6 +169,12 @@ impl MutReceiver {
         }
     }
 
+    pub fn mutate_delta(&self, diff: u8, base_version: u64) {
+        if let Ok(mut mask) = self.mask.as_ref().write() {
+            mask.set_delta_bit(diff, base_version);
+        }
+    }
+
     pub fn or_mask(&self, other_mask: &DiffMask) {
         if let Ok(mut mask) = self.mask.as_ref().write() {
             mask.or(other_mask);
@@ -141,6 +206,10 @@ impl PropertyMutate for MutSender {
     fn mutate(&mut self, property_index: u8) {
         self.channel.send(property_index);
     }
+
+    fn mutate_delta(&mut self, property_index: u8, base_version: u64) {
+        self.channel.send_delta(property_index, base_version);
+    }
 }
 
 // MutReceiverBuilder
@@ -158,4 +227,8 @@ impl MutReceiverBuilder {
     pub fn build(&self, addr: &SocketAddr) -> Option<MutReceiver> {
         self.channel.new_receiver(addr)
     }
+
+    pub fn channel(&self) -> &MutChannel {
+        &self.channel
+    }
 }
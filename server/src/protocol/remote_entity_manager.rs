@@ -0,0 +1,233 @@
+use std::{collections::HashMap, hash::Hash};
+
+use naia_shared::{
+    message_list_header,
+    serde::{BitReader, Serde, SerdeErr, UnsignedVariableInteger},
+    ComponentUpdate, DiffMask, EntityAction, EntityActionReceiver, EntityActionType, MessageId,
+    NetEntity, NetEntityHandleConverter, Protocolize,
+};
+
+/// Receives the Entities & Components spawned by a Client, and keeps track of
+/// which Entities in the Server's World they correspond to. Also receives
+/// authority requests & updates for Server Entities the Client has authority
+/// over, which are identified by the Server's own NetEntities
+pub struct RemoteEntityManager<P: Protocolize, E: Copy + Eq + Hash> {
+    receiver: EntityActionReceiver<NetEntity, P::Kind>,
+    received_components: HashMap<(NetEntity, P::Kind), P>,
+    received_updates: Vec<(NetEntity, DiffMask, ComponentUpdate<P::Kind>)>,
+    net_entity_to_entity_map: HashMap<NetEntity, E>,
+    entity_to_net_entity_map: HashMap<E, NetEntity>,
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash> Default for RemoteEntityManager<P, E> {
+    fn default() -> Self {
+        Self {
+            receiver: EntityActionReceiver::default(),
+            received_components: HashMap::new(),
+            received_updates: Vec::new(),
+            net_entity_to_entity_map: HashMap::new(),
+            entity_to_net_entity_map: HashMap::new(),
+        }
+    }
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash> RemoteEntityManager<P, E> {
+    // Reading
+
+    pub fn read_actions(
+        &mut self,
+        converter: &dyn NetEntityHandleConverter,
+        reader: &mut BitReader,
+    ) -> Result<(), SerdeErr> {
+        let mut last_read_id: Option<MessageId> = None;
+        let action_count = message_list_header::read(reader)?;
+        for _ in 0..action_count {
+            self.read_action(converter, reader, &mut last_read_id)?;
+        }
+        Ok(())
+    }
+
+    fn read_message_id(
+        bit_reader: &mut BitReader,
+        last_id_opt: &mut Option<MessageId>,
+    ) -> Result<MessageId, SerdeErr> {
+        let current_id = if let Some(last_id) = last_id_opt {
+            // read diff
+            let id_diff = UnsignedVariableInteger::<3>::de(bit_reader)?.get() as MessageId;
+            last_id.wrapping_add(id_diff)
+        } else {
+            // read message id
+            MessageId::de(bit_reader)?
+        };
+        *last_id_opt = Some(current_id);
+        Ok(current_id)
+    }
+
+    fn read_action(
+        &mut self,
+        converter: &dyn NetEntityHandleConverter,
+        reader: &mut BitReader,
+        last_read_id: &mut Option<MessageId>,
+    ) -> Result<(), SerdeErr> {
+        let action_id = Self::read_message_id(reader, last_read_id)?;
+
+        let action_type = EntityActionType::de(reader)?;
+
+        match action_type {
+            // Entity Creation
+            EntityActionType::SpawnEntity => {
+                // read entity
+                let net_entity = NetEntity::de(reader)?;
+
+                // read components
+                let components_num = UnsignedVariableInteger::<3>::de(reader)?.get();
+                let mut component_kinds = Vec::new();
+                for _ in 0..components_num {
+                    let new_component = P::read(reader, converter)?;
+                    let new_component_kind = new_component.dyn_ref().kind();
+                    self.received_components
+                        .insert((net_entity, new_component_kind), new_component);
+                    component_kinds.push(new_component_kind);
+                }
+
+                self.receiver.buffer_action(
+                    action_id,
+                    EntityAction::SpawnEntity(net_entity, component_kinds),
+                );
+            }
+            // Entity Deletion
+            EntityActionType::DespawnEntity => {
+                let net_entity = NetEntity::de(reader)?;
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::DespawnEntity(net_entity));
+            }
+            // Add Component to Entity
+            EntityActionType::InsertComponent => {
+                let net_entity = NetEntity::de(reader)?;
+                let new_component = P::read(reader, converter)?;
+                let new_component_kind = new_component.dyn_ref().kind();
+
+                self.receiver.buffer_action(
+                    action_id,
+                    EntityAction::InsertComponent(net_entity, new_component_kind),
+                );
+                self.received_components
+                    .insert((net_entity, new_component_kind), new_component);
+            }
+            // Component Removal
+            EntityActionType::RemoveComponent => {
+                let net_entity = NetEntity::de(reader)?;
+                let component_kind = P::Kind::de(reader)?;
+
+                self.receiver.buffer_action(
+                    action_id,
+                    EntityAction::RemoveComponent(net_entity, component_kind),
+                );
+            }
+            // Authority over a Server Entity
+            EntityActionType::RequestAuthority => {
+                let net_entity = NetEntity::de(reader)?;
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::RequestAuthority(net_entity));
+            }
+            EntityActionType::ReleaseAuthority => {
+                let net_entity = NetEntity::de(reader)?;
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::ReleaseAuthority(net_entity));
+            }
+            EntityActionType::GrantAuthority
+            | EntityActionType::RevokeAuthority
+            | EntityActionType::DenyAuthority
+            | EntityActionType::GrantOwnership
+            | EntityActionType::RevokeOwnership
+            | EntityActionType::HideEntity
+            | EntityActionType::RevealEntity => {
+                // only the Server can grant authority or ownership, or hide Entities, ignore
+                let _net_entity = NetEntity::de(reader)?;
+
+                self.receiver.buffer_action(action_id, EntityAction::Noop);
+            }
+            EntityActionType::GrantComponentAuthority
+            | EntityActionType::RevokeComponentAuthority => {
+                // only the Server can grant authority over a Component, ignore
+                let _net_entity = NetEntity::de(reader)?;
+                let _component_kind = P::Kind::de(reader)?;
+
+                self.receiver.buffer_action(action_id, EntityAction::Noop);
+            }
+            EntityActionType::Noop => {
+                self.receiver.buffer_action(action_id, EntityAction::Noop);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn read_updates(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let update_count = message_list_header::read(reader)?;
+        for _ in 0..update_count {
+            self.read_update(reader)?;
+        }
+        Ok(())
+    }
+
+    fn read_update(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let net_entity = NetEntity::de(reader)?;
+
+        let components_number = UnsignedVariableInteger::<3>::de(reader)?.get();
+
+        for _ in 0..components_number {
+            let diff_mask = DiffMask::de(reader)?;
+            let component_update = P::read_create_update(reader)?;
+
+            self.received_updates
+                .push((net_entity, diff_mask, component_update));
+        }
+
+        Ok(())
+    }
+
+    // Processing
+
+    pub fn receive_actions(&mut self) -> Vec<EntityAction<NetEntity, P::Kind>> {
+        self.receiver.receive_actions()
+    }
+
+    pub fn receive_updates(&mut self) -> Vec<(NetEntity, DiffMask, ComponentUpdate<P::Kind>)> {
+        std::mem::take(&mut self.received_updates)
+    }
+
+    pub fn take_component(
+        &mut self,
+        net_entity: &NetEntity,
+        component_kind: &P::Kind,
+    ) -> Option<P> {
+        self.received_components
+            .remove(&(*net_entity, *component_kind))
+    }
+
+    pub fn register_entity(&mut self, net_entity: &NetEntity, entity: &E) {
+        self.net_entity_to_entity_map.insert(*net_entity, *entity);
+        self.entity_to_net_entity_map.insert(*entity, *net_entity);
+    }
+
+    /// Stops tracking the given Entity, any further actions the Client sends
+    /// for it will be ignored
+    pub fn deregister_entity(&mut self, entity: &E) {
+        if let Some(net_entity) = self.entity_to_net_entity_map.remove(entity) {
+            self.net_entity_to_entity_map.remove(&net_entity);
+        }
+    }
+
+    /// How many Entities spawned by the Client are being tracked
+    pub fn entity_count(&self) -> usize {
+        self.net_entity_to_entity_map.len()
+    }
+
+    pub fn net_entity_to_entity(&self, net_entity: &NetEntity) -> Option<&E> {
+        self.net_entity_to_entity_map.get(net_entity)
+    }
+}
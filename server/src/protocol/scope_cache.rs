@@ -0,0 +1,74 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    mem,
+};
+
+use crate::user::UserKey;
+
+/// Keeps track of which (User, Entity) pairs need their scope re-evaluated,
+/// so that only pairs affected by a change in Room membership, spatial
+/// visibility or an include/exclude decision are looked at each frame
+pub struct ScopeCache<E: Copy + Eq + Hash> {
+    // pairs whose scope must be recalculated before the next send
+    dirty: HashSet<(UserKey, E)>,
+    // pairs which are waiting on a decision through `Server::scope_checks()`
+    unchecked: HashMap<E, HashSet<UserKey>>,
+}
+
+impl<E: Copy + Eq + Hash> Default for ScopeCache<E> {
+    fn default() -> Self {
+        Self {
+            dirty: HashSet::new(),
+            unchecked: HashMap::new(),
+        }
+    }
+}
+
+impl<E: Copy + Eq + Hash> ScopeCache<E> {
+    /// Marks the pair's scope to be recalculated
+    pub fn mark_dirty(&mut self, user_key: &UserKey, entity: &E) {
+        self.dirty.insert((*user_key, *entity));
+    }
+
+    /// Marks the pair's scope to be recalculated, and to be returned from
+    /// `Server::scope_checks()` until a decision is made for it
+    pub fn mark_unchecked(&mut self, user_key: &UserKey, entity: &E) {
+        self.dirty.insert((*user_key, *entity));
+        self.unchecked.entry(*entity).or_default().insert(*user_key);
+    }
+
+    /// Records that a decision has been made for the pair
+    pub fn mark_checked(&mut self, user_key: &UserKey, entity: &E) {
+        if let Some(user_keys) = self.unchecked.get_mut(entity) {
+            user_keys.remove(user_key);
+            if user_keys.is_empty() {
+                self.unchecked.remove(entity);
+            }
+        }
+    }
+
+    pub fn unchecked(&self) -> impl Iterator<Item = (&UserKey, &E)> {
+        self.unchecked.iter().flat_map(|(entity, user_keys)| {
+            user_keys.iter().map(move |user_key| (user_key, entity))
+        })
+    }
+
+    pub fn take_dirty(&mut self) -> HashSet<(UserKey, E)> {
+        mem::take(&mut self.dirty)
+    }
+
+    pub fn remove_user(&mut self, user_key: &UserKey) {
+        self.dirty.retain(|(dirty_user, _)| dirty_user != user_key);
+        self.unchecked.retain(|_, user_keys| {
+            user_keys.remove(user_key);
+            !user_keys.is_empty()
+        });
+    }
+
+    pub fn remove_entity(&mut self, entity: &E) {
+        self.dirty
+            .retain(|(_, dirty_entity)| dirty_entity != entity);
+        self.unchecked.remove(entity);
+    }
+}
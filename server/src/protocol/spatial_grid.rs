@@ -0,0 +1,214 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{spatial_config::SpatialConfig, user::UserKey};
+
+type Cell = (i32, i32);
+
+struct UserView {
+    x: f32,
+    y: f32,
+    radius: f32,
+}
+
+/// Buckets Entities into a uniform grid by position, so that the Entities
+/// within view of each User can be found by only checking the cells around
+/// them. Entities stay visible until they are a hysteresis margin beyond a
+/// User's view radius, so that Entities moving along the edge of the view
+/// don't flicker in and out of scope.
+pub struct SpatialGrid<E: Copy + Eq + Hash> {
+    cell_size: f32,
+    hysteresis: f32,
+    entity_positions: HashMap<E, (f32, f32)>,
+    cells: HashMap<Cell, HashSet<E>>,
+    user_views: HashMap<UserKey, UserView>,
+    visible_entities: HashMap<UserKey, HashSet<E>>,
+}
+
+impl<E: Copy + Eq + Hash> SpatialGrid<E> {
+    pub fn new(config: &SpatialConfig) -> Self {
+        Self {
+            cell_size: config.cell_size,
+            hysteresis: config.hysteresis,
+            entity_positions: HashMap::new(),
+            cells: HashMap::new(),
+            user_views: HashMap::new(),
+            visible_entities: HashMap::new(),
+        }
+    }
+
+    // Entities
+
+    pub fn set_entity_position(&mut self, entity: &E, x: f32, y: f32) {
+        let new_cell = self.cell(x, y);
+        if let Some((old_x, old_y)) = self.entity_positions.insert(*entity, (x, y)) {
+            let old_cell = self.cell(old_x, old_y);
+            if old_cell == new_cell {
+                return;
+            }
+            self.remove_from_cell(entity, &old_cell);
+        }
+        self.cells.entry(new_cell).or_default().insert(*entity);
+    }
+
+    pub fn entity_position(&self, entity: &E) -> Option<(f32, f32)> {
+        self.entity_positions.get(entity).copied()
+    }
+
+    pub fn remove_entity(&mut self, entity: &E) {
+        if let Some((x, y)) = self.entity_positions.remove(entity) {
+            let cell = self.cell(x, y);
+            self.remove_from_cell(entity, &cell);
+        }
+        for visible_entities in self.visible_entities.values_mut() {
+            visible_entities.remove(entity);
+        }
+    }
+
+    // Users
+
+    pub fn set_user_view(&mut self, user_key: &UserKey, x: f32, y: f32, radius: f32) {
+        self.user_views.insert(
+            *user_key,
+            UserView {
+                x,
+                y,
+                radius: radius.max(0.0),
+            },
+        );
+    }
+
+    pub fn remove_user(&mut self, user_key: &UserKey) {
+        self.user_views.remove(user_key);
+        self.visible_entities.remove(user_key);
+    }
+
+    // Visibility
+
+    /// Recalculates which Entities are within view of each User, returning
+    /// every (User, Entity) pair which has entered or left view
+    pub fn update(&mut self) -> Vec<(UserKey, E)> {
+        let mut all_visible_entities = HashMap::new();
+
+        for (user_key, view) in &self.user_views {
+            let previously_visible = self.visible_entities.get(user_key);
+            let outer_radius = view.radius + self.hysteresis;
+
+            let (min_x, min_y) = self.cell(view.x - outer_radius, view.y - outer_radius);
+            let (max_x, max_y) = self.cell(view.x + outer_radius, view.y + outer_radius);
+
+            // a large view may cover far more cells than are occupied, in
+            // which case only the occupied cells are looked at
+            let view_cell_count =
+                (max_x as i64 - min_x as i64 + 1) * (max_y as i64 - min_y as i64 + 1);
+            let view_cells: Vec<&HashSet<E>> = if view_cell_count > self.cells.len() as i64 {
+                self.cells
+                    .iter()
+                    .filter(|((cell_x, cell_y), _)| {
+                        (min_x..=max_x).contains(cell_x) && (min_y..=max_y).contains(cell_y)
+                    })
+                    .map(|(_, cell_entities)| cell_entities)
+                    .collect()
+            } else {
+                (min_x..=max_x)
+                    .flat_map(|cell_x| (min_y..=max_y).map(move |cell_y| (cell_x, cell_y)))
+                    .filter_map(|cell| self.cells.get(&cell))
+                    .collect()
+            };
+
+            let mut visible_entities = HashSet::new();
+            for cell_entities in view_cells {
+                for entity in cell_entities {
+                    let (x, y) = self.entity_positions.get(entity).unwrap();
+                    let distance_squared = (x - view.x).powi(2) + (y - view.y).powi(2);
+
+                    // Entities already in view may go a little further before leaving it
+                    let was_visible = previously_visible
+                        .map(|entities| entities.contains(entity))
+                        .unwrap_or(false);
+                    let radius = if was_visible {
+                        outer_radius
+                    } else {
+                        view.radius
+                    };
+
+                    if distance_squared <= radius * radius {
+                        visible_entities.insert(*entity);
+                    }
+                }
+            }
+
+            all_visible_entities.insert(*user_key, visible_entities);
+        }
+
+        let mut changed = Vec::new();
+        for (user_key, visible_entities) in &all_visible_entities {
+            match self.visible_entities.get(user_key) {
+                Some(previously_visible) => {
+                    for entity in visible_entities.symmetric_difference(previously_visible) {
+                        changed.push((*user_key, *entity));
+                    }
+                }
+                None => {
+                    for entity in visible_entities {
+                        changed.push((*user_key, *entity));
+                    }
+                }
+            }
+        }
+
+        self.visible_entities = all_visible_entities;
+
+        changed
+    }
+
+    /// Returns whether the Entity was within view of the User as of the last
+    /// update
+    pub fn is_visible(&self, user_key: &UserKey, entity: &E) -> bool {
+        self.visible_entities
+            .get(user_key)
+            .map(|entities| entities.contains(entity))
+            .unwrap_or(false)
+    }
+
+    fn cell(&self, x: f32, y: f32) -> Cell {
+        (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        )
+    }
+
+    fn remove_from_cell(&mut self, entity: &E, cell: &Cell) {
+        if let Some(cell_entities) = self.cells.get_mut(cell) {
+            cell_entities.remove(entity);
+            if cell_entities.is_empty() {
+                self.cells.remove(cell);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::BigMapKey;
+
+    use crate::{spatial_config::SpatialConfig, user::UserKey};
+
+    use super::SpatialGrid;
+
+    #[test]
+    fn huge_view_only_checks_occupied_cells() {
+        let mut grid = SpatialGrid::new(&SpatialConfig::new(1.0, 0.0));
+        let user_key = UserKey::from_u64(0);
+        grid.set_entity_position(&1, 5.0, 5.0);
+        grid.set_entity_position(&2, 5.0e9, 5.0e9);
+
+        // this view spans trillions of cells, but only two are occupied
+        grid.set_user_view(&user_key, 0.0, 0.0, 1.0e9);
+        assert_eq!(grid.update(), vec![(user_key, 1)]);
+        assert!(grid.is_visible(&user_key, &1));
+        assert!(!grid.is_visible(&user_key, &2));
+    }
+}
@@ -0,0 +1,69 @@
+use std::{collections::HashMap, hash::Hash};
+
+use naia_shared::{wrapping_diff, Instant, ProtocolKindType, Tick, UpdateRate};
+
+/// Keeps track of when updates to each Component were last sent to a User, so
+/// that Components with a limited UpdateRate only have their accumulated
+/// changes sent once they are due
+pub struct UpdateSchedule<E: Copy + Eq + Hash, K: ProtocolKindType> {
+    last_sent: HashMap<(E, K), (Instant, Option<Tick>)>,
+    now: Instant,
+    server_tick: Option<Tick>,
+}
+
+impl<E: Copy + Eq + Hash, K: ProtocolKindType> Default for UpdateSchedule<E, K> {
+    fn default() -> Self {
+        Self {
+            last_sent: HashMap::new(),
+            now: Instant::now(),
+            server_tick: None,
+        }
+    }
+}
+
+impl<E: Copy + Eq + Hash, K: ProtocolKindType> UpdateSchedule<E, K> {
+    /// Sets the time & Tick against which updates are scheduled
+    pub fn set_now(&mut self, now: &Instant, server_tick: Option<Tick>) {
+        self.now = now.clone();
+        self.server_tick = server_tick;
+    }
+
+    pub fn is_due(&self, entity: &E, component_kind: &K, update_rate: UpdateRate) -> bool {
+        let (last_sent_time, last_sent_tick) = match self.last_sent.get(&(*entity, *component_kind))
+        {
+            Some(last_sent) => last_sent,
+            None => return true,
+        };
+
+        match update_rate {
+            UpdateRate::Immediate => true,
+            UpdateRate::Ticks(ticks) => match (last_sent_tick, self.server_tick) {
+                (Some(last_sent_tick), Some(server_tick)) => {
+                    i32::from(wrapping_diff(*last_sent_tick, server_tick)) >= i32::from(ticks)
+                }
+                _ => true,
+            },
+            UpdateRate::Millis(millis) => {
+                let mut due_time = last_sent_time.clone();
+                due_time.add_millis(millis);
+                self.now >= due_time
+            }
+        }
+    }
+
+    pub fn record_sent(&mut self, entity: &E, component_kind: &K) {
+        self.last_sent.insert(
+            (*entity, *component_kind),
+            (self.now.clone(), self.server_tick),
+        );
+    }
+
+    pub fn remove_component(&mut self, entity: &E, component_kind: &K) {
+        self.last_sent.remove(&(*entity, *component_kind));
+    }
+
+    pub fn remove_entity(&mut self, entity: &E) {
+        self.last_sent
+            .retain(|(sent_entity, _), _| sent_entity != entity);
+    }
+}
@@ -3,18 +3,19 @@ use std::{
     hash::Hash,
     net::SocketAddr,
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 use naia_shared::{
-    ChannelIndex, ChannelSender, EntityAction, EntityActionReceiver, KeyGenerator, NetEntity,
-    ProtocolKindType, Protocolize, ReliableSender,
+    ChannelIndex, ChannelSender, DiffMask, EntityAction, EntityActionEvent, EntityActionReceiver,
+    KeyGenerator, NetEntity, NetEntityWidth, ProtocolKindType, Protocolize, ReliableSender,
 };
 
 use crate::{
     protocol::{
-        entity_action_event::EntityActionEvent, entity_manager::ActionId,
-        entity_message_waitlist::EntityMessageWaitlist, global_diff_handler::GlobalDiffHandler,
-        user_diff_handler::UserDiffHandler,
+        entity_manager::ActionId, entity_message_waitlist::EntityMessageWaitlist,
+        global_diff_handler::GlobalDiffHandler, user_diff_handler::UserDiffHandler,
+        world_record::WorldRecord,
     },
     server::Instant,
 };
@@ -43,6 +44,15 @@ pub struct WorldChannel<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: Ch
     host_world: CheckedMap<E, CheckedSet<P::Kind>>,
     remote_world: CheckedMap<E, CheckedSet<P::Kind>>,
     entity_channels: CheckedMap<E, EntityChannel<P::Kind>>,
+    host_authority: HashSet<E>,
+    host_component_authority: HashSet<(E, P::Kind)>,
+    host_ownership: HashSet<E>,
+    hidden_components: HashMap<E, HashSet<P::Kind>>,
+    hidden_entities: HashMap<E, Instant>,
+    entity_cache_duration: Option<Duration>,
+    // the Entities each Component referred to when it was last written, and
+    // whether any of them were left out as they were not yet in scope
+    component_references: HashMap<(E, P::Kind), (HashSet<E>, bool)>,
     outgoing_actions: ReliableSender<EntityActionEvent<E, P::Kind>>,
     delivered_actions: EntityActionReceiver<E, P::Kind>,
 
@@ -58,17 +68,26 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
     pub fn new(
         address: SocketAddr,
         diff_handler: &Arc<RwLock<GlobalDiffHandler<E, P::Kind>>>,
+        entity_cache_duration: Option<Duration>,
+        net_entity_width: NetEntityWidth,
     ) -> Self {
         Self {
             host_world: CheckedMap::new(),
             remote_world: CheckedMap::new(),
             entity_channels: CheckedMap::new(),
+            host_authority: HashSet::new(),
+            host_component_authority: HashSet::new(),
+            host_ownership: HashSet::new(),
+            hidden_components: HashMap::new(),
+            hidden_entities: HashMap::new(),
+            entity_cache_duration,
+            component_references: HashMap::new(),
             outgoing_actions: ReliableSender::new(RESEND_ACTION_RTT_FACTOR),
             delivered_actions: EntityActionReceiver::default(),
 
             address,
             diff_handler: UserDiffHandler::new(diff_handler),
-            net_entity_generator: KeyGenerator::default(),
+            net_entity_generator: KeyGenerator::new(net_entity_width.max_net_entity()),
             net_entity_to_entity_map: HashMap::new(),
             entity_to_net_entity_map: HashMap::new(),
             delayed_entity_messages: EntityMessageWaitlist::default(),
@@ -78,7 +97,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
     // Main
 
     pub fn host_has_entity(&self, entity: &E) -> bool {
-        self.host_world.contains_key(entity)
+        self.host_world.contains_key(entity) && !self.hidden_entities.contains_key(entity)
     }
 
     pub fn entity_channel_is_open(&self, entity: &E) -> bool {
@@ -90,10 +109,22 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
 
     // Host Updates
 
-    pub fn host_spawn_entity(&mut self, entity: &E) {
+    /// Returns false if the Entity needs a new NetEntity, but every NetEntity
+    /// is already in use
+    pub fn host_spawn_entity(&mut self, entity: &E) -> bool {
         if self.host_world.contains_key(entity) {
-            // do nothing
-            return;
+            if self.hidden_entities.remove(entity).is_some() {
+                // the Client still has the Entity, any changes made while it was
+                // hidden go out as a regular update
+                self.outgoing_actions
+                    .send_message(EntityActionEvent::RevealEntity(*entity));
+                self.on_entity_revealed(entity);
+            }
+            return true;
+        }
+
+        if self.entity_channels.get(entity).is_none() && self.net_entity_generator.is_exhausted() {
+            return false;
         }
 
         self.host_world.insert(*entity, CheckedSet::new());
@@ -106,6 +137,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
                 .send_message(EntityActionEvent::SpawnEntity(*entity));
             self.on_entity_channel_opening(entity);
         }
+
+        true
     }
 
     pub fn host_despawn_entity(&mut self, entity: &E) {
@@ -115,6 +148,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
         }
 
         self.host_world.remove(entity);
+        self.host_authority.remove(entity);
+        self.host_component_authority
+            .retain(|(authority_entity, _)| authority_entity != entity);
+        self.hidden_entities.remove(entity);
 
         let mut despawn = false;
         let mut removing_components = Vec::new();
@@ -143,11 +180,60 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
         }
     }
 
+    // Entity Caching
+
+    pub fn host_entity_is_hidden(&self, entity: &E) -> bool {
+        self.hidden_entities.contains_key(entity)
+    }
+
+    /// Hides the Entity on the remote host instead of despawning it, keeping
+    /// track of its changes so they can be sent if it is revealed again.
+    /// Returns false if the Entity can't be hidden, and should be despawned.
+    pub fn host_hide_entity(&mut self, entity: &E) -> bool {
+        if self.entity_cache_duration.is_none()
+            || !self.host_world.contains_key(entity)
+            || !self.entity_channel_is_open(entity)
+        {
+            return false;
+        }
+
+        if self.hidden_entities.contains_key(entity) {
+            // do nothing
+            return true;
+        }
+
+        self.hidden_entities.insert(*entity, Instant::now());
+        self.outgoing_actions
+            .send_message(EntityActionEvent::HideEntity(*entity));
+
+        true
+    }
+
+    /// Returns the hidden Entities which have been out of scope for longer
+    /// than the cache duration, which should now be despawned
+    pub fn take_expired_hidden_entities(&mut self) -> Vec<E> {
+        let cache_duration = match self.entity_cache_duration {
+            Some(cache_duration) => cache_duration,
+            None => return Vec::new(),
+        };
+
+        self.hidden_entities
+            .iter()
+            .filter(|(_, hidden_at)| hidden_at.elapsed() > cache_duration)
+            .map(|(entity, _)| *entity)
+            .collect()
+    }
+
     pub fn host_insert_component(&mut self, entity: &E, component: &P::Kind) {
         if !self.host_world.contains_key(entity) {
             panic!("cannot insert component into non-existent entity");
         }
 
+        if self.host_component_is_hidden(entity, component) {
+            // do nothing
+            return;
+        }
+
         let components = self.host_world.get_mut(entity).unwrap();
         if components.contains(component) {
             // do nothing
@@ -156,6 +242,11 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
 
         components.insert(*component);
 
+        if self.hidden_entities.contains_key(entity) {
+            // inserted once the Entity is revealed
+            return;
+        }
+
         if let Some(EntityChannel::Spawned(component_channels)) =
             self.entity_channels.get_mut(entity)
         {
@@ -181,6 +272,11 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
 
         components.remove(component);
 
+        if self.hidden_entities.contains_key(entity) {
+            // removed once the Entity is revealed
+            return;
+        }
+
         if let Some(EntityChannel::Spawned(component_channels)) =
             self.entity_channels.get_mut(entity)
         {
@@ -196,6 +292,214 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
         }
     }
 
+    pub fn host_component_kinds(&self, entity: &E) -> Vec<P::Kind> {
+        match self.host_world.get(entity) {
+            Some(components) => components.inner.iter().copied().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    // Component Visibility
+
+    pub fn host_component_is_hidden(&self, entity: &E, component: &P::Kind) -> bool {
+        match self.hidden_components.get(entity) {
+            Some(hidden) => hidden.contains(component),
+            None => false,
+        }
+    }
+
+    /// Hides the Component from the remote host, removing it there if it has
+    /// already been inserted
+    pub fn host_hide_component(&mut self, entity: &E, component: &P::Kind) {
+        let hidden = self.hidden_components.entry(*entity).or_default();
+        if !hidden.insert(*component) {
+            // do nothing
+            return;
+        }
+
+        if self.host_world.contains_key(entity) {
+            self.host_remove_component(entity, component);
+        }
+    }
+
+    /// Stops hiding the Component, it is only inserted again once the Server
+    /// calls `host_insert_component`
+    pub fn host_reveal_component(&mut self, entity: &E, component: &P::Kind) {
+        if let Some(hidden) = self.hidden_components.get_mut(entity) {
+            hidden.remove(component);
+            if hidden.is_empty() {
+                self.hidden_components.remove(entity);
+            }
+        }
+    }
+
+    pub fn host_clear_hidden_components(&mut self, entity: &E) {
+        self.hidden_components.remove(entity);
+    }
+
+    pub fn host_has_authority(&self, entity: &E) -> bool {
+        self.host_authority.contains(entity)
+    }
+
+    pub fn host_grant_authority(&mut self, entity: &E) {
+        if !self.host_authority.insert(*entity) {
+            // do nothing
+            return;
+        }
+
+        // if the entity channel is not open yet, authority will be granted once it is
+        if self.entity_channel_is_open(entity) {
+            self.outgoing_actions
+                .send_message(EntityActionEvent::GrantAuthority(*entity));
+        }
+    }
+
+    pub fn host_revoke_authority(&mut self, entity: &E) {
+        if !self.host_authority.remove(entity) {
+            // do nothing
+            return;
+        }
+
+        if self.entity_channel_is_open(entity) {
+            self.outgoing_actions
+                .send_message(EntityActionEvent::RevokeAuthority(*entity));
+        }
+    }
+
+    pub fn host_deny_authority(&mut self, entity: &E) {
+        if self.entity_channel_is_open(entity) {
+            self.outgoing_actions
+                .send_message(EntityActionEvent::DenyAuthority(*entity));
+        }
+    }
+
+    pub fn host_has_component_authority(&self, entity: &E, component: &P::Kind) -> bool {
+        self.host_component_authority
+            .contains(&(*entity, *component))
+    }
+
+    pub fn host_grant_component_authority(&mut self, entity: &E, component: &P::Kind) {
+        if !self.host_component_authority.insert((*entity, *component)) {
+            // do nothing
+            return;
+        }
+
+        // if the entity channel is not open yet, authority will be granted once it is
+        if self.entity_channel_is_open(entity) {
+            self.outgoing_actions
+                .send_message(EntityActionEvent::GrantComponentAuthority(
+                    *entity, *component,
+                ));
+        }
+    }
+
+    pub fn host_revoke_component_authority(&mut self, entity: &E, component: &P::Kind) {
+        if !self.host_component_authority.remove(&(*entity, *component)) {
+            // do nothing
+            return;
+        }
+
+        if self.entity_channel_is_open(entity) {
+            self.outgoing_actions
+                .send_message(EntityActionEvent::RevokeComponentAuthority(
+                    *entity, *component,
+                ));
+        }
+    }
+
+    // Ownership
+
+    pub fn host_has_ownership(&self, entity: &E) -> bool {
+        self.host_ownership.contains(entity)
+    }
+
+    pub fn host_grant_ownership(&mut self, entity: &E) {
+        if !self.host_ownership.insert(*entity) {
+            // do nothing
+            return;
+        }
+
+        // if the entity channel is not open yet, ownership will be granted once it is
+        if self.entity_channel_is_open(entity) {
+            self.outgoing_actions
+                .send_message(EntityActionEvent::GrantOwnership(*entity));
+            self.resend_components(entity);
+        }
+    }
+
+    pub fn host_revoke_ownership(&mut self, entity: &E) {
+        if !self.host_ownership.remove(entity) {
+            // do nothing
+            return;
+        }
+
+        if self.entity_channel_is_open(entity) {
+            self.outgoing_actions
+                .send_message(EntityActionEvent::RevokeOwnership(*entity));
+        }
+    }
+
+    // queues every Property of the Entity's inserted Components to be sent
+    // again, so that a new owner receives its owner-only Properties
+    fn resend_components(&mut self, entity: &E) {
+        let mut inserted_components = Vec::new();
+        if let Some(EntityChannel::Spawned(component_channels)) = self.entity_channels.get(entity) {
+            for (component, component_channel) in component_channels.iter() {
+                if let ComponentChannel::Inserted = component_channel {
+                    inserted_components.push(*component);
+                }
+            }
+        }
+
+        for component in inserted_components {
+            self.resend_component(entity, &component);
+        }
+    }
+
+    fn resend_component(&mut self, entity: &E, component: &P::Kind) {
+        let byte_number = match self.diff_handler.diff_mask(entity, component) {
+            Some(diff_mask) => diff_mask.byte_number(),
+            None => return,
+        };
+        self.diff_handler
+            .or_diff_mask(entity, component, &DiffMask::new_full(byte_number));
+    }
+
+    /// Records which Entities a Component referred to as it was written.
+    /// Entities not yet in scope are left out of what the Client receives,
+    /// so the Component is sent again once they are.
+    pub fn set_component_references(
+        &mut self,
+        entity: &E,
+        component: &P::Kind,
+        references: HashSet<E>,
+    ) {
+        if references.is_empty() {
+            self.component_references.remove(&(*entity, *component));
+        } else {
+            let has_unresolved = references
+                .iter()
+                .any(|referenced_entity| !self.entity_channel_is_open(referenced_entity));
+            self.component_references
+                .insert((*entity, *component), (references, has_unresolved));
+        }
+    }
+
+    // queues every Component which refers to the Entity to be sent again,
+    // now that the Client can resolve the reference
+    fn resend_referring_components(&mut self, entity: &E) {
+        let referring_components: Vec<(E, P::Kind)> = self
+            .component_references
+            .iter()
+            .filter(|(_, (references, _))| references.contains(entity))
+            .map(|(component_key, _)| *component_key)
+            .collect();
+
+        for (referring_entity, component) in referring_components {
+            self.resend_component(&referring_entity, &component);
+        }
+    }
+
     // Remote Actions
 
     pub fn remote_spawn_entity(&mut self, entity: E, inserted_components: HashSet<P::Kind>) {
@@ -295,8 +599,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
                 component_channels.remove(&component);
 
                 let host_has_component = self.host_world.get(&entity).unwrap().contains(&component);
-                if host_has_component {
-                    // if component exist in host, finalize channel state
+                if host_has_component || self.hidden_entities.contains_key(&entity) {
+                    // if component exist in host, finalize channel state. A hidden
+                    // Entity's Component is removed once it is revealed instead
                     component_channels.insert(component, ComponentChannel::Inserted);
                     self.on_component_channel_opened(&entity, &component);
                 } else {
@@ -331,9 +636,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
             if let ComponentChannel::Removing = component_channels.get(&component).unwrap() {
                 component_channels.remove(&component);
 
-                // if component exists in host, start insertion
+                // if component exists in host, start insertion, unless the
+                // Entity is hidden and it will be inserted once revealed
                 let host_has_component = self.host_world.get(&entity).unwrap().contains(&component);
-                if host_has_component {
+                if host_has_component && !self.hidden_entities.contains_key(&entity) {
                     // insert component
                     component_channels.insert(component, ComponentChannel::Inserting);
                     self.outgoing_actions
@@ -354,7 +660,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
 
     fn on_entity_channel_opening(&mut self, entity: &E) {
         // generate new net entity
-        let new_net_entity = self.net_entity_generator.generate();
+        let new_net_entity = self
+            .net_entity_generator
+            .generate()
+            .expect("NetEntity availability should be checked before opening an Entity channel");
         self.entity_to_net_entity_map
             .insert(*entity, new_net_entity);
         self.net_entity_to_entity_map
@@ -363,6 +672,67 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
 
     fn on_entity_channel_opened(&mut self, entity: &E) {
         self.delayed_entity_messages.add_entity(entity);
+        self.resend_referring_components(entity);
+
+        if self.host_authority.contains(entity) {
+            self.outgoing_actions
+                .send_message(EntityActionEvent::GrantAuthority(*entity));
+        }
+
+        for (authority_entity, component) in &self.host_component_authority {
+            if authority_entity == entity {
+                self.outgoing_actions
+                    .send_message(EntityActionEvent::GrantComponentAuthority(
+                        *entity, *component,
+                    ));
+            }
+        }
+
+        if self.host_ownership.contains(entity) {
+            self.outgoing_actions
+                .send_message(EntityActionEvent::GrantOwnership(*entity));
+        }
+    }
+
+    /// Sends the Component insertions & removals which were held back while
+    /// the Entity was hidden, so they only reach the Client once it is back
+    /// in scope
+    fn on_entity_revealed(&mut self, entity: &E) {
+        let host_components = self.host_world.get(entity).unwrap();
+        let mut removing_components = Vec::new();
+
+        if let Some(EntityChannel::Spawned(component_channels)) =
+            self.entity_channels.get_mut(entity)
+        {
+            for component in host_components.inner.iter() {
+                if component_channels.get(component).is_none() {
+                    // insert component
+                    component_channels.insert(*component, ComponentChannel::Inserting);
+                    self.outgoing_actions
+                        .send_message(EntityActionEvent::InsertComponent(*entity, *component));
+                }
+            }
+
+            for (component, component_channel) in component_channels.iter() {
+                if let ComponentChannel::Inserted = component_channel {
+                    if !host_components.contains(component) {
+                        removing_components.push(*component);
+                    }
+                }
+            }
+
+            for component in &removing_components {
+                // remove component
+                component_channels.remove(component);
+                component_channels.insert(*component, ComponentChannel::Removing);
+                self.outgoing_actions
+                    .send_message(EntityActionEvent::RemoveComponent(*entity, *component));
+            }
+        }
+
+        for component in removing_components {
+            self.on_component_channel_closing(entity, &component);
+        }
     }
 
     fn on_entity_channel_closing(&mut self, entity: &E) {
@@ -374,15 +744,25 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
         let net_entity = self.entity_to_net_entity_map.remove(entity).unwrap();
         self.net_entity_to_entity_map.remove(&net_entity);
         self.net_entity_generator.recycle_key(&net_entity);
+
+        self.component_references
+            .retain(|(referring_entity, _), _| referring_entity != entity);
     }
 
     fn on_component_channel_opened(&mut self, entity: &E, component: &P::Kind) {
         self.diff_handler
             .register_component(&self.address, entity, component);
+
+        // references which could not be resolved when the Component was
+        // inserted may have entered scope since
+        if let Some((_, true)) = self.component_references.get(&(*entity, *component)) {
+            self.resend_component(entity, component);
+        }
     }
 
     fn on_component_channel_closing(&mut self, entity: &E, component: &P::Kind) {
         self.diff_handler.deregister_component(entity, component);
+        self.component_references.remove(&(*entity, *component));
     }
 
     // Action Delivery
@@ -411,7 +791,18 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
                 EntityAction::RemoveComponent(entity, component) => {
                     self.remote_remove_component(entity, component);
                 }
-                EntityAction::Noop => {
+                EntityAction::GrantAuthority(_)
+                | EntityAction::RevokeAuthority(_)
+                | EntityAction::DenyAuthority(_)
+                | EntityAction::RequestAuthority(_)
+                | EntityAction::ReleaseAuthority(_)
+                | EntityAction::GrantComponentAuthority(_, _)
+                | EntityAction::RevokeComponentAuthority(_, _)
+                | EntityAction::GrantOwnership(_)
+                | EntityAction::RevokeOwnership(_)
+                | EntityAction::HideEntity(_)
+                | EntityAction::RevealEntity(_)
+                | EntityAction::Noop => {
                     // do nothing
                 }
             }
@@ -429,10 +820,22 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
         self.outgoing_actions.take_next_messages()
     }
 
-    pub fn collect_next_updates(&self) -> HashMap<E, HashSet<P::Kind>> {
+    pub fn collect_next_updates(
+        &self,
+        world_record: &WorldRecord<E, P::Kind>,
+    ) -> HashMap<E, HashSet<P::Kind>> {
         let mut output = HashMap::new();
 
         for (entity, entity_channel) in self.entity_channels.iter() {
+            // changes to hidden Entities keep accumulating until they are revealed
+            if self.hidden_entities.contains_key(entity) {
+                continue;
+            }
+            // likewise, changes to dormant Entities go out as a single update
+            // once they wake
+            if world_record.entity_is_dormant(entity) {
+                continue;
+            }
             if let EntityChannel::Spawned(component_channels) = entity_channel {
                 for (component, component_channel) in component_channels.iter() {
                     if let ComponentChannel::Inserted = component_channel {
@@ -1,12 +1,21 @@
 use std::{collections::HashMap, hash::Hash};
 
-use naia_shared::{BigMap, EntityHandle, EntityHandleConverter, ProtocolKindType};
+use naia_shared::{BigMap, EntityHandle, EntityHandleConverter, ProtocolKindType, UpdateRate};
 
-use crate::{protocol::global_entity_record::GlobalEntityRecord, room::RoomKey};
+use crate::{
+    protocol::{
+        entity_priority::DEFAULT_PRIORITY,
+        global_entity_record::{EntityOwner, GlobalEntityRecord},
+    },
+    room::RoomKey,
+    user::UserKey,
+};
 
 pub struct WorldRecord<E: Copy + Eq + Hash, K: ProtocolKindType> {
     entity_records: HashMap<E, GlobalEntityRecord<K>>,
     handle_entity_map: BigMap<EntityHandle, E>,
+    component_priorities: HashMap<K, f32>,
+    component_update_rates: HashMap<K, UpdateRate>,
 }
 
 impl<E: Copy + Eq + Hash, K: ProtocolKindType> Default for WorldRecord<E, K> {
@@ -14,6 +23,8 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> Default for WorldRecord<E, K> {
         Self {
             entity_records: HashMap::default(),
             handle_entity_map: BigMap::default(),
+            component_priorities: HashMap::default(),
+            component_update_rates: HashMap::default(),
         }
     }
 }
@@ -21,13 +32,13 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> Default for WorldRecord<E, K> {
 impl<E: Copy + Eq + Hash, K: ProtocolKindType> WorldRecord<E, K> {
     // Sync w/ World & Server
 
-    pub fn spawn_entity(&mut self, entity: &E) {
+    pub fn spawn_entity(&mut self, entity: &E, owner: EntityOwner) {
         if self.entity_records.contains_key(entity) {
             panic!("entity already initialized!");
         }
         let entity_handle = self.handle_entity_map.insert(*entity);
         self.entity_records
-            .insert(*entity, GlobalEntityRecord::new(entity_handle));
+            .insert(*entity, GlobalEntityRecord::new(owner, entity_handle));
     }
 
     pub fn despawn_entity(&mut self, entity: &E) -> Option<GlobalEntityRecord<K>> {
@@ -58,10 +69,33 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> WorldRecord<E, K> {
 
     // Access
 
+    pub fn entities(&self) -> Vec<E> {
+        self.entity_records.keys().copied().collect()
+    }
+
     pub fn has_entity(&self, entity: &E) -> bool {
         self.entity_records.contains_key(entity)
     }
 
+    pub fn entity_owner(&self, entity: &E) -> Option<EntityOwner> {
+        self.entity_records
+            .get(entity)
+            .map(|entity_record| entity_record.owner)
+    }
+
+    /// Hands every Entity spawned by the given User's Client over to the
+    /// Server, returning them
+    pub(crate) fn release_client_entities(&mut self, user_key: &UserKey) -> Vec<E> {
+        let mut released = Vec::new();
+        for (entity, entity_record) in self.entity_records.iter_mut() {
+            if entity_record.owner == EntityOwner::Client(*user_key) {
+                entity_record.owner = EntityOwner::Server;
+                released.push(*entity);
+            }
+        }
+        released
+    }
+
     pub fn component_kinds(&self, entity: &E) -> Option<Vec<K>> {
         if !self.entity_records.contains_key(entity) {
             return None;
@@ -71,8 +105,199 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> WorldRecord<E, K> {
         return Some(component_kind_set.iter().copied().collect());
     }
 
+    // Authority
+
+    pub(crate) fn entity_authority(&self, entity: &E) -> Option<UserKey> {
+        self.entity_records
+            .get(entity)
+            .and_then(|entity_record| entity_record.authority)
+    }
+
+    pub(crate) fn set_entity_authority(&mut self, entity: &E, authority: Option<UserKey>) {
+        if let Some(entity_record) = self.entity_records.get_mut(entity) {
+            entity_record.authority = authority;
+        }
+    }
+
+    pub(crate) fn entities_with_authority(&self, user_key: &UserKey) -> Vec<E> {
+        self.entity_records
+            .iter()
+            .filter(|(_, entity_record)| entity_record.authority == Some(*user_key))
+            .map(|(entity, _)| *entity)
+            .collect()
+    }
+
+    pub(crate) fn component_authority(&self, entity: &E, component_kind: &K) -> Option<UserKey> {
+        self.entity_records
+            .get(entity)
+            .and_then(|entity_record| entity_record.component_authority.get(component_kind))
+            .copied()
+    }
+
+    pub(crate) fn set_component_authority(
+        &mut self,
+        entity: &E,
+        component_kind: &K,
+        authority: Option<UserKey>,
+    ) {
+        if let Some(entity_record) = self.entity_records.get_mut(entity) {
+            match authority {
+                Some(user_key) => {
+                    entity_record
+                        .component_authority
+                        .insert(*component_kind, user_key);
+                }
+                None => {
+                    entity_record.component_authority.remove(component_kind);
+                }
+            }
+        }
+    }
+
+    /// Gets the kinds of the Entity's Components which a User has authority
+    /// over on their own
+    pub(crate) fn components_with_authority(&self, entity: &E) -> Vec<K> {
+        self.entity_records
+            .get(entity)
+            .map(|entity_record| entity_record.component_authority.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn user_component_authorities(&self, user_key: &UserKey) -> Vec<(E, K)> {
+        let mut output = Vec::new();
+        for (entity, entity_record) in &self.entity_records {
+            for (component_kind, authority) in &entity_record.component_authority {
+                if authority == user_key {
+                    output.push((*entity, *component_kind));
+                }
+            }
+        }
+        output
+    }
+
+    // Ownership
+
+    pub(crate) fn entity_owning_user(&self, entity: &E) -> Option<UserKey> {
+        self.entity_records
+            .get(entity)
+            .and_then(|entity_record| entity_record.owning_user)
+    }
+
+    pub(crate) fn set_entity_owning_user(&mut self, entity: &E, owning_user: Option<UserKey>) {
+        if let Some(entity_record) = self.entity_records.get_mut(entity) {
+            entity_record.owning_user = owning_user;
+        }
+    }
+
+    pub(crate) fn entities_owned_by(&self, user_key: &UserKey) -> Vec<E> {
+        self.entity_records
+            .iter()
+            .filter(|(_, entity_record)| entity_record.owning_user == Some(*user_key))
+            .map(|(entity, _)| *entity)
+            .collect()
+    }
+
+    pub(crate) fn entity_is_delegated(&self, entity: &E) -> bool {
+        if let Some(entity_record) = self.entity_records.get(entity) {
+            return entity_record.delegated;
+        }
+        false
+    }
+
+    pub(crate) fn set_entity_delegated(&mut self, entity: &E, delegated: bool) {
+        if let Some(entity_record) = self.entity_records.get_mut(entity) {
+            entity_record.delegated = delegated;
+        }
+    }
+
+    // Priority
+
+    pub(crate) fn component_priority(&self, component_kind: &K) -> f32 {
+        self.component_priorities
+            .get(component_kind)
+            .copied()
+            .unwrap_or(DEFAULT_PRIORITY)
+    }
+
+    pub(crate) fn set_component_priority(&mut self, component_kind: &K, priority: f32) {
+        self.component_priorities
+            .insert(*component_kind, priority.max(0.0));
+    }
+
+    /// Returns the base priority of an Entity, which is that of its most
+    /// important Component
+    pub(crate) fn entity_base_priority<'a>(
+        &self,
+        component_kinds: impl Iterator<Item = &'a K>,
+    ) -> f32
+    where
+        K: 'a,
+    {
+        component_kinds
+            .map(|component_kind| self.component_priority(component_kind))
+            .fold(None, |max: Option<f32>, priority| {
+                Some(max.map_or(priority, |max| max.max(priority)))
+            })
+            .unwrap_or(DEFAULT_PRIORITY)
+    }
+
+    // Update Rate
+
+    /// Returns how often updates to the given Component may be sent. An
+    /// UpdateRate set on the Entity takes precedence over that of the
+    /// Component's type.
+    pub(crate) fn update_rate(&self, entity: &E, component_kind: &K) -> UpdateRate {
+        if let Some(update_rate) = self
+            .entity_records
+            .get(entity)
+            .and_then(|entity_record| entity_record.update_rate)
+        {
+            return update_rate;
+        }
+        self.component_update_rates
+            .get(component_kind)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn set_component_update_rate(
+        &mut self,
+        component_kind: &K,
+        update_rate: UpdateRate,
+    ) {
+        self.component_update_rates
+            .insert(*component_kind, update_rate);
+    }
+
+    pub(crate) fn set_entity_update_rate(&mut self, entity: &E, update_rate: Option<UpdateRate>) {
+        if let Some(entity_record) = self.entity_records.get_mut(entity) {
+            entity_record.update_rate = update_rate;
+        }
+    }
+
+    // Dormancy
+
+    pub(crate) fn entity_is_dormant(&self, entity: &E) -> bool {
+        self.entity_records
+            .get(entity)
+            .map(|entity_record| entity_record.is_dormant)
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn set_entity_dormant(&mut self, entity: &E, is_dormant: bool) {
+        if let Some(entity_record) = self.entity_records.get_mut(entity) {
+            entity_record.is_dormant = is_dormant;
+        }
+    }
+
     // Rooms
 
+    pub(crate) fn entity_room(&self, entity: &E) -> Option<RoomKey> {
+        self.entity_records
+            .get(entity)
+            .and_then(|entity_record| entity_record.room_key)
+    }
+
     pub(crate) fn entity_is_in_room(&self, entity: &E, room_key: &RoomKey) -> bool {
         if let Some(entity_record) = self.entity_records.get(entity) {
             if let Some(actual_room_key) = entity_record.room_key {
@@ -26,6 +26,7 @@ pub struct Room<E: Copy + Eq + Hash> {
     users: HashSet<UserKey>,
     entities: HashSet<E>,
     entity_removal_queue: VecDeque<(UserKey, E)>,
+    spatial: bool,
 }
 
 impl<E: Copy + Eq + Hash> Room<E> {
@@ -34,9 +35,20 @@ impl<E: Copy + Eq + Hash> Room<E> {
             users: HashSet::new(),
             entities: HashSet::new(),
             entity_removal_queue: VecDeque::new(),
+            spatial: false,
         }
     }
 
+    // Spatial Scoping
+
+    pub(crate) fn is_spatial(&self) -> bool {
+        self.spatial
+    }
+
+    pub(crate) fn set_spatial(&mut self, spatial: bool) {
+        self.spatial = spatial;
+    }
+
     // Users
 
     pub(crate) fn has_user(&self, user_key: &UserKey) -> bool {
@@ -133,6 +145,14 @@ impl<'s, P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Roo
     pub fn entities_count(&self) -> usize {
         self.server.room_entities_count(&self.key)
     }
+
+    // Spatial Scoping
+
+    /// Returns whether the Room's Entities are scoped by their distance to
+    /// each User
+    pub fn is_spatial(&self) -> bool {
+        self.server.room_is_spatial(&self.key)
+    }
 }
 
 // RoomMut
@@ -197,4 +217,28 @@ impl<'s, P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Roo
     pub fn entities_count(&self) -> usize {
         self.server.room_entities_count(&self.key)
     }
+
+    // Spatial Scoping
+
+    /// Scopes the Room's Entities by their distance to each User, instead of
+    /// by `Server::scope_checks()`. An Entity is in scope for a User in the
+    /// Room while its position is within the User's view. Entities without
+    /// a position, or Users without a view, are never in scope, unless the
+    /// Entity has been explicitly included in the User's scope.
+    pub fn enable_spatial_scoping(&mut self) -> &mut Self {
+        self.server.room_set_spatial(&self.key, true);
+
+        self
+    }
+
+    /// Returns the Room to scoping by `Server::scope_checks()`
+    pub fn disable_spatial_scoping(&mut self) -> &mut Self {
+        self.server.room_set_spatial(&self.key, false);
+
+        self
+    }
+
+    pub fn is_spatial(&self) -> bool {
+        self.server.room_is_spatial(&self.key)
+    }
 }
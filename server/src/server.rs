@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, VecDeque},
     hash::Hash,
+    io::{self, Read, Seek},
     net::SocketAddr,
     panic,
     sync::{Arc, RwLock},
@@ -8,18 +9,21 @@ use std::{
 
 use naia_server_socket::{ServerAddrs, Socket};
 use naia_shared::{
-    serde::{BitWriter, Serde},
-    ChannelIndex, EntityHandle, EntityHandleConverter, Tick,
+    message_size_bytes,
+    serde::{BitReader, BitVecWriter, BitWriter, Serde, SerdeErr, UnsignedVariableInteger},
+    BigMapKey, BlobId, ChannelIndex, DiffMask, EntityAction, EntityConverter, EntityHandle,
+    EntityHandleConverter, ProtocolInserter, Tick, UpdateRate,
 };
 pub use naia_shared::{
     wrapping_diff, BaseConnection, BigMap, ConnectionConfig, Instant, KeyGenerator, NetEntity,
     PacketType, PingConfig, PropertyMutate, PropertyMutator, ProtocolKindType, Protocolize,
-    Replicate, ReplicateSafe, SharedConfig, StandardHeader, Timer, Timestamp, WorldMutType,
-    WorldRefType,
+    Replicate, ReplicateSafe, SharedConfig, StandardHeader, StaticMutator, Timer, Timestamp,
+    WorldMutType, WorldRefType,
 };
 
 use crate::{
     connection::{
+        blob_sender::BlobStatus,
         connection::Connection,
         handshake_manager::{HandshakeManager, HandshakeResult},
         io::Io,
@@ -28,6 +32,9 @@ use crate::{
         entity_ref::{EntityMut, EntityRef},
         entity_scope_map::EntityScopeMap,
         global_diff_handler::GlobalDiffHandler,
+        global_entity_record::EntityOwner,
+        scope_cache::ScopeCache,
+        spatial_grid::SpatialGrid,
         world_record::WorldRecord,
     },
     tick::tick_manager::TickManager,
@@ -38,6 +45,7 @@ use super::{
     event::Event,
     room::{Room, RoomKey, RoomMut, RoomRef},
     server_config::ServerConfig,
+    snapshot::{self, RestoredSnapshot, SnapshotEntityConverter, SnapshotError},
     user::{User, UserKey, UserMut, UserRef},
     user_scope::UserScopeMut,
 };
@@ -63,10 +71,12 @@ pub struct Server<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelI
     // Entities
     world_record: WorldRecord<E, P::Kind>,
     entity_scope_map: EntityScopeMap<E>,
+    scope_cache: ScopeCache<E>,
+    spatial_grid: SpatialGrid<E>,
     // Components
     diff_handler: Arc<RwLock<GlobalDiffHandler<E, P::Kind>>>,
     // Events
-    incoming_events: VecDeque<Result<Event<P, C>, NaiaServerError>>,
+    incoming_events: VecDeque<Result<Event<P, E, C>, NaiaServerError>>,
     // Ticks
     tick_manager: Option<TickManager>,
 }
@@ -100,6 +110,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
             // Entities
             world_record: WorldRecord::default(),
             entity_scope_map: EntityScopeMap::new(),
+            scope_cache: ScopeCache::default(),
+            spatial_grid: SpatialGrid::new(&server_config.spatial),
             // Components
             diff_handler: Arc::new(RwLock::new(GlobalDiffHandler::default())),
             // Events
@@ -123,8 +135,12 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     }
 
     /// Must be called regularly, maintains connection to and receives messages
-    /// from all Clients
-    pub fn receive(&mut self) -> VecDeque<Result<Event<P, C>, NaiaServerError>> {
+    /// from all Clients, and applies any Entity changes made by Clients to the
+    /// given World
+    pub fn receive<W: WorldMutType<P, E>>(
+        &mut self,
+        mut world: W,
+    ) -> VecDeque<Result<Event<P, E, C>, NaiaServerError>> {
         // Need to run this to maintain connection with all clients, and receive packets
         // until none left
         self.maintain_socket();
@@ -153,6 +169,24 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
                     message,
                 )));
             }
+
+            // receive the fate of blobs sent to the Client
+            for (blob_id, status) in connection.blob_sender.receive_statuses() {
+                let user_key = connection.user_key;
+                self.incoming_events.push_back(match status {
+                    BlobStatus::Delivered => Ok(Event::BlobDelivered(user_key, blob_id)),
+                    BlobStatus::Rejected => Ok(Event::BlobRejected(user_key, blob_id)),
+                    BlobStatus::Failed(error) => {
+                        Err(NaiaServerError::BlobFailed(user_key, blob_id, error))
+                    }
+                });
+            }
+
+            // receive updates to Entities the Client has authority over
+            self.receive_entity_updates(&mut world, user_address);
+
+            // receive entity actions from Client-owned Entities
+            self.receive_entity_actions(&mut world, user_address);
         }
 
         // receive tick buffered messages on tick
@@ -191,6 +225,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
                 user.address,
                 user_key,
                 &self.diff_handler,
+                self.server_config.entity_cache_duration,
+                self.shared_config.net_entity_width,
             );
             // send connectaccept response
             let mut writer = self.handshake_manager.write_connect_response();
@@ -214,22 +250,26 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     // Messages
 
     /// Queues up an Message to be sent to the Client associated with a given
-    /// UserKey
+    /// UserKey.
+    /// Panics if the Message is larger than the Channel's `max_message_size`.
     pub fn send_message<R: ReplicateSafe<P>>(
         &mut self,
         user_key: &UserKey,
         channel: C,
         message: &R,
     ) {
-        if !self
-            .shared_config
-            .channel
-            .channel(&channel)
-            .can_send_to_client()
-        {
+        let channel_settings = self.shared_config.channel.channel(&channel);
+
+        if !channel_settings.can_send_to_client() {
             panic!("Cannot send message to Client on this Channel");
         }
 
+        if let Some(max_message_size) = channel_settings.max_message_size() {
+            if message_size_bytes(message) > max_message_size {
+                panic!("Message is too large for this Channel");
+            }
+        }
+
         if let Some(user) = self.users.get(user_key) {
             if let Some(connection) = self.user_connections.get_mut(&user.address) {
                 if message.has_entity_properties() {
@@ -269,6 +309,41 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         }
     }
 
+    // Blobs
+
+    /// Queues up a blob of bytes, such as a custom map, to be sent to the
+    /// Client associated with a given UserKey. Blobs are sent in chunks over
+    /// a bulk channel which only uses the room left in packets by Messages and
+    /// Entity data. If the Client already has a blob with the same contents
+    /// in its cache, it is not downloaded again.
+    /// Returns None if the User is not connected.
+    pub fn send_blob(&mut self, user_key: &UserKey, name: &str, bytes: Vec<u8>) -> Option<BlobId> {
+        let user = self.users.get(user_key)?;
+        let connection = self.user_connections.get_mut(&user.address)?;
+        Some(connection.blob_sender.send_blob(name, bytes))
+    }
+
+    /// Queues up a blob to be sent to the Client associated with a given
+    /// UserKey, like `send_blob`, but streams it from the given source
+    /// rather than holding it in memory. The source is read through once
+    /// right away to hash it, then read a chunk at a time as the transfer
+    /// goes on.
+    pub fn send_blob_source<R: Read + Seek + Send + Sync + 'static>(
+        &mut self,
+        user_key: &UserKey,
+        name: &str,
+        source: R,
+    ) -> io::Result<BlobId> {
+        let connection = self
+            .users
+            .get(user_key)
+            .and_then(|user| self.user_connections.get_mut(&user.address))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "User is not connected"))?;
+        connection
+            .blob_sender
+            .send_blob_source(name, Box::new(source))
+    }
+
     // Updates
 
     /// Used to evaluate whether, given a User & Entity that are in the
@@ -279,16 +354,22 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     ///
     /// Return a collection of Entity Scope Sets, being a unique combination of
     /// a related Room, User, and Entity, used to determine which Entities to
-    /// replicate to which Users
+    /// replicate to which Users. Rooms with spatial scoping enabled are left
+    /// out, as their scope is determined by Entity positions instead.
+    ///
+    /// Only Sets which still need a decision are returned: those which have
+    /// not been included or excluded since the User and Entity came to share
+    /// a Room, or since `recheck_entity_scope()` or `recheck_user_scope()`
+    /// was last called for them.
     pub fn scope_checks(&self) -> Vec<(RoomKey, UserKey, E)> {
         let mut list: Vec<(RoomKey, UserKey, E)> = Vec::new();
 
-        // TODO: precache this, instead of generating a new list every call
-        // likely this is called A LOT
-        for (room_key, room) in self.rooms.iter() {
-            for user_key in room.user_keys() {
-                for entity in room.entities() {
-                    list.push((room_key, *user_key, *entity));
+        for (user_key, entity) in self.scope_cache.unchecked() {
+            if let Some(room_key) = self.world_record.entity_room(entity) {
+                if let Some(room) = self.rooms.get(&room_key) {
+                    if !room.is_spatial() && room.has_user(user_key) {
+                        list.push((room_key, *user_key, *entity));
+                    }
                 }
             }
         }
@@ -296,6 +377,34 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         list
     }
 
+    /// Marks an Entity's scope as needing a new decision for every User in
+    /// its Room, so that they are returned from `scope_checks()` again. Call
+    /// this whenever something your scoping decisions depend on changes for
+    /// the Entity.
+    pub fn recheck_entity_scope(&mut self, entity: &E) {
+        if let Some(room_key) = self.world_record.entity_room(entity) {
+            if let Some(room) = self.rooms.get(&room_key) {
+                for user_key in room.user_keys() {
+                    self.scope_cache.mark_unchecked(user_key, entity);
+                }
+            }
+        }
+    }
+
+    /// Marks the scope of every Entity in the User's Rooms as needing a new
+    /// decision, so that they are returned from `scope_checks()` again. Call
+    /// this whenever something your scoping decisions depend on changes for
+    /// the User.
+    pub fn recheck_user_scope(&mut self, user_key: &UserKey) {
+        for (_, room) in self.rooms.iter() {
+            if room.has_user(user_key) {
+                for entity in room.entities() {
+                    self.scope_cache.mark_unchecked(user_key, entity);
+                }
+            }
+        }
+    }
+
     /// Sends all update messages to all Clients. If you don't call this
     /// method, the Server will never communicate with it's connected
     /// Clients
@@ -312,6 +421,18 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         for user_address in user_addresses {
             let connection = self.user_connections.get_mut(&user_address).unwrap();
 
+            match connection.update_congestion() {
+                Some(true) => {
+                    self.incoming_events
+                        .push_back(Ok(Event::CongestionDetected(connection.user_key)));
+                }
+                Some(false) => {
+                    self.incoming_events
+                        .push_back(Ok(Event::CongestionResolved(connection.user_key)));
+                }
+                None => {}
+            }
+
             let rtt = connection.ping_manager.rtt;
 
             connection.send_outgoing_packets(
@@ -370,6 +491,251 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         world.entities()
     }
 
+    /// Returns whether the given Entity was spawned by the Server, or by the
+    /// Client of a given User.
+    /// Returns None if the Entity is not tracked by the Server.
+    pub fn entity_owner(&self, entity: &E) -> Option<EntityOwner> {
+        self.world_record.entity_owner(entity)
+    }
+
+    /// Sets the base priority of a type of Component, for all Users. An
+    /// Entity's priority for a User is multiplied by the highest base
+    /// priority of the Components waiting to be sent. The default base
+    /// priority is 1.0.
+    pub fn set_component_priority<R: ReplicateSafe<P>>(&mut self, priority: f32) {
+        self.world_record
+            .set_component_priority(&P::kind_of::<R>(), priority);
+    }
+
+    /// Returns the User which currently has authority over the given Entity,
+    /// if any
+    pub fn entity_authority(&self, entity: &E) -> Option<UserKey> {
+        self.world_record.entity_authority(entity)
+    }
+
+    /// Returns the User which currently has authority over the given Entity's
+    /// Component, if any, whether over the Component alone or over the whole
+    /// Entity
+    pub fn component_authority<R: ReplicateSafe<P>>(&self, entity: &E) -> Option<UserKey> {
+        self.component_authority_of_kind(entity, &P::kind_of::<R>())
+    }
+
+    /// Returns whether updates to the given Entity's Components are currently
+    /// paused with `set_dormant`
+    pub fn entity_is_dormant(&self, entity: &E) -> bool {
+        self.world_record.entity_is_dormant(entity)
+    }
+
+    /// Returns the User which has been made the owner of the given Entity
+    /// with `set_owner`, if any
+    pub fn entity_owning_user(&self, entity: &E) -> Option<UserKey> {
+        self.world_record.entity_owning_user(entity)
+    }
+
+    // Snapshots
+
+    /// Writes every Entity tracked by the Server to bytes, along with its
+    /// Components, its position, the Rooms it is in and any scope set for it
+    /// with `user_scope`. Passing the result to `restore_snapshot` rebuilds
+    /// the same state, for example after a crash or to load a checkpoint.
+    pub fn snapshot<W: WorldRefType<P, E>>(&self, world: W) -> Vec<u8> {
+        let entities = self.world_record.entities();
+        let entity_indices: HashMap<E, usize> = entities
+            .iter()
+            .enumerate()
+            .map(|(index, entity)| (*entity, index))
+            .collect();
+        let converter = SnapshotEntityConverter::new(
+            entities
+                .iter()
+                .map(|entity| self.world_record.entity_to_handle(entity))
+                .collect(),
+        );
+
+        let mut writer = BitVecWriter::new();
+
+        // Entities
+        UnsignedVariableInteger::<7>::new(entities.len() as u64).ser(&mut writer);
+        for entity in &entities {
+            self.spatial_grid.entity_position(entity).ser(&mut writer);
+
+            let component_kinds: Vec<P::Kind> = self
+                .world_record
+                .component_kinds(entity)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|component_kind| world.has_component_of_kind(entity, component_kind))
+                .collect();
+            UnsignedVariableInteger::<3>::new(component_kinds.len() as u64).ser(&mut writer);
+            for component_kind in &component_kinds {
+                world
+                    .component_of_kind(entity, component_kind)
+                    .unwrap()
+                    .write(&mut writer, &converter);
+            }
+        }
+
+        // Rooms
+        UnsignedVariableInteger::<7>::new(self.rooms.len() as u64).ser(&mut writer);
+        for (_, room) in self.rooms.iter() {
+            room.is_spatial().ser(&mut writer);
+
+            let room_entities: Vec<usize> = room
+                .entities()
+                .filter_map(|entity| entity_indices.get(entity).copied())
+                .collect();
+            UnsignedVariableInteger::<7>::new(room_entities.len() as u64).ser(&mut writer);
+            for index in room_entities {
+                UnsignedVariableInteger::<7>::new(index as u64).ser(&mut writer);
+            }
+
+            UnsignedVariableInteger::<7>::new(room.users_count() as u64).ser(&mut writer);
+            for user_key in room.user_keys() {
+                user_key.to_u64().ser(&mut writer);
+            }
+        }
+
+        // Scopes
+        let scopes: Vec<(UserKey, usize, bool)> = self
+            .entity_scope_map
+            .iter()
+            .filter_map(|((user_key, entity), in_scope)| {
+                entity_indices
+                    .get(entity)
+                    .map(|index| (*user_key, *index, *in_scope))
+            })
+            .collect();
+        UnsignedVariableInteger::<7>::new(scopes.len() as u64).ser(&mut writer);
+        for (user_key, index, in_scope) in scopes {
+            user_key.to_u64().ser(&mut writer);
+            UnsignedVariableInteger::<7>::new(index as u64).ser(&mut writer);
+            in_scope.ser(&mut writer);
+        }
+
+        snapshot::write_header(writer.to_bytes())
+    }
+
+    /// Rebuilds the state written by `snapshot`, spawning its Entities into
+    /// the given World alongside any which already exist. Restored Entities
+    /// are always owned by the Server. UserKeys do not outlive the Server
+    /// which created them, so Users are only added back to Rooms & scopes if
+    /// they are still connected.
+    /// Returns an error, leaving the Server & World as they were, if the
+    /// snapshot is truncated, corrupt, or was written by an incompatible
+    /// version of Naia.
+    pub fn restore_snapshot<W: WorldMutType<P, E>>(
+        &mut self,
+        mut world: W,
+        snapshot: &[u8],
+    ) -> Result<RestoredSnapshot<E>, SnapshotError> {
+        let mut reader = BitReader::new(snapshot::read_header(snapshot)?);
+
+        let mut restored = RestoredSnapshot {
+            entities: Vec::new(),
+            rooms: Vec::new(),
+        };
+        if self
+            .read_snapshot(&mut world, &mut reader, &mut restored)
+            .is_err()
+        {
+            // don't leave anything half-restored behind
+            for room_key in &restored.rooms {
+                self.room_destroy(room_key);
+            }
+            for entity in &restored.entities {
+                self.despawn_entity(&mut world, entity);
+            }
+            return Err(SnapshotError::Corrupt);
+        }
+
+        Ok(restored)
+    }
+
+    fn read_snapshot<W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        reader: &mut BitReader,
+        restored: &mut RestoredSnapshot<E>,
+    ) -> Result<(), SerdeErr> {
+        // Entities are all spawned first, so that Components can refer to any of them
+        let entity_count = UnsignedVariableInteger::<7>::de(reader)?.get() as usize;
+        if entity_count > reader.bits_remaining() {
+            return Err(SerdeErr {});
+        }
+        for _ in 0..entity_count {
+            let entity = world.spawn_entity();
+            self.spawn_entity_init(&entity);
+            restored.entities.push(entity);
+        }
+        let entities = restored.entities.clone();
+        let converter = SnapshotEntityConverter::new(
+            entities
+                .iter()
+                .map(|entity| self.world_record.entity_to_handle(entity))
+                .collect(),
+        );
+        let read_entity = |reader: &mut BitReader| -> Result<E, SerdeErr> {
+            let index = UnsignedVariableInteger::<7>::de(reader)?.get() as usize;
+            entities.get(index).copied().ok_or(SerdeErr {})
+        };
+
+        for entity in &entities {
+            if let Some((x, y)) = Option::<(f32, f32)>::de(reader)? {
+                self.entity_set_position(entity, x, y);
+            }
+
+            let component_count = UnsignedVariableInteger::<3>::de(reader)?.get() as usize;
+            for _ in 0..component_count {
+                let component = P::read(reader, &converter)?;
+                component.extract_and_insert(
+                    entity,
+                    &mut ComponentInserter {
+                        server: self,
+                        world,
+                    },
+                );
+            }
+        }
+
+        // Rooms
+        let room_count = UnsignedVariableInteger::<7>::de(reader)?.get() as usize;
+        for _ in 0..room_count {
+            let room_key = self.make_room().key();
+            restored.rooms.push(room_key);
+
+            if bool::de(reader)? {
+                self.room_set_spatial(&room_key, true);
+            }
+
+            let room_entity_count = UnsignedVariableInteger::<7>::de(reader)?.get() as usize;
+            for _ in 0..room_entity_count {
+                let entity = read_entity(reader)?;
+                self.room_add_entity(&room_key, &entity);
+            }
+
+            let room_user_count = UnsignedVariableInteger::<7>::de(reader)?.get() as usize;
+            for _ in 0..room_user_count {
+                let user_key = UserKey::from_u64(u64::de(reader)?);
+                if self.users.contains_key(&user_key) {
+                    self.room_add_user(&room_key, &user_key);
+                }
+            }
+        }
+
+        // Scopes
+        let scope_count = UnsignedVariableInteger::<7>::de(reader)?.get() as usize;
+        for _ in 0..scope_count {
+            let user_key = UserKey::from_u64(u64::de(reader)?);
+            let entity = read_entity(reader)?;
+            let in_scope = bool::de(reader)?;
+            if self.users.contains_key(&user_key) {
+                self.user_scope_set_entity(&user_key, &entity, in_scope);
+            }
+        }
+
+        Ok(())
+    }
+
     // Users
 
     /// Returns whether or not a User exists for the given RoomKey
@@ -511,6 +877,57 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         self.io.incoming_bandwidth_from_client(address)
     }
 
+    // Bandwidth budget
+    /// Gets the number of bytes which can currently be sent to the given
+    /// User's Client, or None if no bandwidth budget has been configured
+    pub fn outgoing_budget(&self, user_key: &UserKey) -> Option<u32> {
+        if let Some(user) = self.users.get(user_key) {
+            if let Some(user_connection) = self.user_connections.get(&user.address) {
+                return user_connection
+                    .bandwidth_budget
+                    .as_ref()
+                    .map(|budget| budget.available_bytes());
+            }
+        }
+        None
+    }
+
+    /// Gets the number of Messages, Entity actions and Component updates
+    /// waiting to be sent to the given User's Client
+    pub fn outgoing_queue_depth(&self, user_key: &UserKey) -> Option<usize> {
+        if let Some(user) = self.users.get(user_key) {
+            if let Some(user_connection) = self.user_connections.get(&user.address) {
+                return Some(user_connection.outgoing_queue_depth());
+            }
+        }
+        None
+    }
+
+    /// Gets the fraction of recently sent packets to the given User's Client
+    /// which were dropped, between 0.0 and 1.0
+    pub fn packet_loss(&self, user_key: &UserKey) -> Option<f32> {
+        if let Some(user) = self.users.get(user_key) {
+            if let Some(user_connection) = self.user_connections.get(&user.address) {
+                return Some(user_connection.base.packet_loss());
+            }
+        }
+        None
+    }
+
+    /// Returns whether the connection to the given User's Client is currently
+    /// congested, or None if congestion control has not been configured
+    pub fn is_congested(&self, user_key: &UserKey) -> Option<bool> {
+        if let Some(user) = self.users.get(user_key) {
+            if let Some(user_connection) = self.user_connections.get(&user.address) {
+                return user_connection
+                    .congestion_control
+                    .as_ref()
+                    .map(|congestion_control| congestion_control.is_congested());
+            }
+        }
+        None
+    }
+
     // Ping
     /// Gets the average Round Trip Time measured to the given User's Client
     pub fn rtt(&self, user_key: &UserKey) -> Option<f32> {
@@ -546,11 +963,31 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
             panic!("attempted to de-spawn nonexistent entity");
         }
 
+        self.entity_take_authority(entity);
+        for component_kind in self.world_record.components_with_authority(entity) {
+            self.entity_take_component_authority(entity, &component_kind);
+        }
+        self.entity_remove_owner(entity);
+
         // TODO: we can make this more efficient in the future by caching which Entities
         // are in each User's scope
         for (_, user_connection) in self.user_connections.iter_mut() {
             //remove entity from user connection
             user_connection.entity_manager.despawn_entity(entity);
+            user_connection
+                .entity_manager
+                .clear_hidden_components(entity);
+        }
+
+        // Ignore any further changes from the owning Client
+        if let Some(EntityOwner::Client(user_key)) = self.world_record.entity_owner(entity) {
+            if let Some(user) = self.users.get(&user_key) {
+                if let Some(user_connection) = self.user_connections.get_mut(&user.address) {
+                    user_connection
+                        .remote_entity_manager
+                        .deregister_entity(entity);
+                }
+            }
         }
 
         // Clean up associated components
@@ -563,6 +1000,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
 
         // Delete scope
         self.entity_scope_map.remove_entity(entity);
+        self.scope_cache.remove_entity(entity);
+        self.spatial_grid.remove_entity(entity);
 
         // Remove from ECS Record
         self.world_record.despawn_entity(entity);
@@ -578,6 +1017,67 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     ) {
         self.entity_scope_map
             .insert(*user_key, *entity, is_contained);
+        self.scope_cache.mark_checked(user_key, entity);
+        self.scope_cache.mark_dirty(user_key, entity);
+    }
+
+    pub(crate) fn user_scope_set_priority(
+        &mut self,
+        user_key: &UserKey,
+        entity: &E,
+        priority: f32,
+    ) {
+        if let Some(user) = self.users.get(user_key) {
+            if let Some(user_connection) = self.user_connections.get_mut(&user.address) {
+                user_connection
+                    .entity_manager
+                    .set_priority(entity, priority);
+            }
+        }
+    }
+
+    pub(crate) fn user_scope_set_component_hidden(
+        &mut self,
+        user_key: &UserKey,
+        entity: &E,
+        component_kind: &P::Kind,
+        is_hidden: bool,
+    ) {
+        if let Some(user) = self.users.get(user_key) {
+            if let Some(user_connection) = self.user_connections.get_mut(&user.address) {
+                if is_hidden {
+                    user_connection
+                        .entity_manager
+                        .hide_component(entity, component_kind);
+                    return;
+                }
+
+                user_connection
+                    .entity_manager
+                    .reveal_component(entity, component_kind);
+
+                // insert the Component again if the User can still see its Entity
+                let has_component = self
+                    .world_record
+                    .component_kinds(entity)
+                    .is_some_and(|kinds| kinds.contains(component_kind));
+                if has_component && user_connection.entity_manager.scope_has_entity(entity) {
+                    user_connection
+                        .entity_manager
+                        .insert_component(entity, component_kind);
+                }
+            }
+        }
+    }
+
+    //// Spatial Scoping
+
+    pub(crate) fn entity_set_position(&mut self, entity: &E, x: f32, y: f32) {
+        self.spatial_grid.set_entity_position(entity, x, y);
+    }
+
+    pub(crate) fn user_set_view(&mut self, user_key: &UserKey, x: f32, y: f32, radius: f32) {
+        self.spatial_grid.set_user_view(user_key, x, y, radius);
     }
 
     //// Components
@@ -609,8 +1109,11 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
 
         // add component to connections already tracking entity
         for (_, user_connection) in self.user_connections.iter_mut() {
-            // insert component into user's connection
-            if user_connection.entity_manager.scope_has_entity(entity) {
+            // insert component into user's connection, including any copy of the
+            // Entity hidden on the Client, which holds it back until revealed
+            if user_connection.entity_manager.scope_has_entity(entity)
+                || user_connection.entity_manager.entity_is_hidden(entity)
+            {
                 user_connection
                     .entity_manager
                     .insert_component(entity, &component_kind);
@@ -627,22 +1130,242 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         // get component key from type
         let component_kind = P::kind_of::<R>();
 
-        // clean up component on all connections
+        self.component_removal_cleanup(entity, &component_kind);
 
-        // TODO: should be able to make this more efficient by caching for every Entity
-        // which scopes they are part of
-        for (_, user_connection) in self.user_connections.iter_mut() {
-            // remove component from user connection
+        // remove from world
+        world.remove_component::<R>(entity)
+    }
+
+    /// Removes a Component from an Entity, given the Component's Kind
+    pub(crate) fn remove_component_of_kind<W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        entity: &E,
+        component_kind: &P::Kind,
+    ) -> Option<P> {
+        self.component_removal_cleanup(entity, component_kind);
+
+        // remove from world
+        world.remove_component_of_kind(entity, component_kind)
+    }
+
+    //// Authority
+
+    pub(crate) fn entity_give_authority(&mut self, entity: &E, user_key: &UserKey) {
+        match self.world_record.entity_owner(entity) {
+            None => panic!("attempted to give authority over nonexistent entity"),
+            Some(EntityOwner::Client(_)) => {
+                panic!("cannot give authority over an Entity which is owned by a Client")
+            }
+            Some(EntityOwner::Server) => {}
+        }
+
+        let user_address = self
+            .user_address(user_key)
+            .expect("attempted to give authority to nonexistent User");
+
+        if let Some(authority) = self.world_record.entity_authority(entity) {
+            if authority == *user_key {
+                // do nothing
+                return;
+            }
+            self.entity_take_authority(entity);
+        }
+
+        self.world_record
+            .set_entity_authority(entity, Some(*user_key));
+        self.diff_set_authority(entity, Some(user_address));
+
+        if let Some(user_connection) = self.user_connections.get_mut(&user_address) {
+            user_connection.entity_manager.grant_authority(entity);
+        }
+
+        self.incoming_events
+            .push_back(Ok(Event::AuthorityGranted(*user_key, *entity)));
+    }
+
+    pub(crate) fn entity_take_authority(&mut self, entity: &E) {
+        if let Some(user_key) = self.world_record.entity_authority(entity) {
+            self.world_record.set_entity_authority(entity, None);
+            self.diff_set_authority(entity, None);
+
+            if let Some(user_address) = self.user_address(&user_key) {
+                if let Some(user_connection) = self.user_connections.get_mut(&user_address) {
+                    user_connection.entity_manager.revoke_authority(entity);
+                }
+            }
+
+            self.incoming_events
+                .push_back(Ok(Event::AuthorityRevoked(user_key, *entity)));
+        }
+    }
+
+    pub(crate) fn entity_give_component_authority(
+        &mut self,
+        entity: &E,
+        component_kind: &P::Kind,
+        user_key: &UserKey,
+    ) {
+        match self.world_record.entity_owner(entity) {
+            None => panic!("attempted to give authority over a Component of nonexistent entity"),
+            Some(EntityOwner::Client(_)) => {
+                panic!("cannot give authority over a Component of an Entity which is owned by a Client")
+            }
+            Some(EntityOwner::Server) => {}
+        }
+
+        if !self
+            .world_record
+            .component_kinds(entity)
+            .unwrap()
+            .contains(component_kind)
+        {
+            panic!("attempted to give authority over nonexistent Component");
+        }
+
+        let user_address = self
+            .user_address(user_key)
+            .expect("attempted to give authority to nonexistent User");
+
+        if let Some(authority) = self
+            .world_record
+            .component_authority(entity, component_kind)
+        {
+            if authority == *user_key {
+                // do nothing
+                return;
+            }
+            self.entity_take_component_authority(entity, component_kind);
+        }
+
+        self.world_record
+            .set_component_authority(entity, component_kind, Some(*user_key));
+        self.diff_handler
+            .as_ref()
+            .read()
+            .expect("DiffHandler should be initialized")
+            .set_authority(entity, component_kind, Some(user_address));
+
+        if let Some(user_connection) = self.user_connections.get_mut(&user_address) {
             user_connection
                 .entity_manager
-                .remove_component(entity, &component_kind);
+                .grant_component_authority(entity, component_kind);
         }
 
-        // cleanup all other loose ends
-        self.component_cleanup(entity, &component_kind);
+        self.incoming_events
+            .push_back(Ok(Event::ComponentAuthorityGranted(
+                *user_key,
+                *entity,
+                *component_kind,
+            )));
+    }
 
-        // remove from world
-        world.remove_component::<R>(entity)
+    pub(crate) fn entity_take_component_authority(&mut self, entity: &E, component_kind: &P::Kind) {
+        if let Some(user_key) = self
+            .world_record
+            .component_authority(entity, component_kind)
+        {
+            self.world_record
+                .set_component_authority(entity, component_kind, None);
+
+            // authority falls back to whoever has authority over the whole Entity
+            let entity_authority_address = self
+                .world_record
+                .entity_authority(entity)
+                .and_then(|entity_user_key| self.user_address(&entity_user_key));
+            self.diff_handler
+                .as_ref()
+                .read()
+                .expect("DiffHandler should be initialized")
+                .set_authority(entity, component_kind, entity_authority_address);
+
+            if let Some(user_address) = self.user_address(&user_key) {
+                if let Some(user_connection) = self.user_connections.get_mut(&user_address) {
+                    user_connection
+                        .entity_manager
+                        .revoke_component_authority(entity, component_kind);
+                }
+            }
+
+            self.incoming_events
+                .push_back(Ok(Event::ComponentAuthorityRevoked(
+                    user_key,
+                    *entity,
+                    *component_kind,
+                )));
+        }
+    }
+
+    /// Gets the User with authority over the Component, which is whoever has
+    /// authority over it alone, or else whoever has authority over its Entity
+    fn component_authority_of_kind(&self, entity: &E, component_kind: &P::Kind) -> Option<UserKey> {
+        self.world_record
+            .component_authority(entity, component_kind)
+            .or_else(|| self.world_record.entity_authority(entity))
+    }
+
+    pub(crate) fn entity_set_delegated(&mut self, entity: &E, delegated: bool) {
+        if let Some(EntityOwner::Client(_)) = self.world_record.entity_owner(entity) {
+            panic!("cannot delegate authority over an Entity which is owned by a Client");
+        }
+
+        self.world_record.set_entity_delegated(entity, delegated);
+    }
+
+    //// Ownership
+
+    pub(crate) fn entity_set_owner(&mut self, entity: &E, user_key: &UserKey) {
+        match self.world_record.entity_owner(entity) {
+            None => panic!("attempted to set the owner of nonexistent entity"),
+            Some(EntityOwner::Client(_)) => {
+                panic!("cannot set the owner of an Entity which is owned by a Client")
+            }
+            Some(EntityOwner::Server) => {}
+        }
+
+        let user_address = self
+            .user_address(user_key)
+            .expect("attempted to make nonexistent User an owner");
+
+        if let Some(owning_user) = self.world_record.entity_owning_user(entity) {
+            if owning_user == *user_key {
+                // do nothing
+                return;
+            }
+            self.entity_remove_owner(entity);
+        }
+
+        self.world_record
+            .set_entity_owning_user(entity, Some(*user_key));
+
+        if let Some(user_connection) = self.user_connections.get_mut(&user_address) {
+            user_connection.entity_manager.grant_ownership(entity);
+        }
+    }
+
+    pub(crate) fn entity_remove_owner(&mut self, entity: &E) {
+        if let Some(user_key) = self.world_record.entity_owning_user(entity) {
+            self.world_record.set_entity_owning_user(entity, None);
+
+            if let Some(user_address) = self.user_address(&user_key) {
+                if let Some(user_connection) = self.user_connections.get_mut(&user_address) {
+                    user_connection.entity_manager.revoke_ownership(entity);
+                }
+            }
+        }
+    }
+
+    //// Update Rate
+
+    pub(crate) fn entity_set_update_rate(&mut self, entity: &E, update_rate: Option<UpdateRate>) {
+        self.world_record
+            .set_entity_update_rate(entity, update_rate);
+    }
+
+    //// Dormancy
+
+    pub(crate) fn entity_set_dormant(&mut self, entity: &E, is_dormant: bool) {
+        self.world_record.set_entity_dormant(entity, is_dormant);
     }
 
     //// Users
@@ -657,9 +1380,25 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
 
     /// All necessary cleanup, when they're actually gone...
     pub(crate) fn delete_user(&mut self, user_key: &UserKey) -> Option<User> {
+        for entity in self.world_record.entities_with_authority(user_key) {
+            self.entity_take_authority(&entity);
+        }
+        for (entity, component_kind) in self.world_record.user_component_authorities(user_key) {
+            self.entity_take_component_authority(&entity, &component_kind);
+        }
+        for entity in self.world_record.entities_owned_by(user_key) {
+            self.world_record.set_entity_owning_user(&entity, None);
+        }
+        for entity in self.world_record.release_client_entities(user_key) {
+            self.incoming_events
+                .push_back(Ok(Event::EntityOrphaned(*user_key, entity)));
+        }
+
         if let Some(user) = self.users.remove(user_key) {
             if self.user_connections.remove(&user.address).is_some() {
                 self.entity_scope_map.remove_user(user_key);
+                self.scope_cache.remove_user(user_key);
+                self.spatial_grid.remove_user(user_key);
                 self.handshake_manager.delete_user(&user.address);
 
                 // TODO: cache this?
@@ -698,6 +1437,34 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         }
     }
 
+    /// Returns whether the Room's Entities are scoped by their distance to
+    /// each User
+    pub(crate) fn room_is_spatial(&self, room_key: &RoomKey) -> bool {
+        if let Some(room) = self.rooms.get(room_key) {
+            return room.is_spatial();
+        }
+        false
+    }
+
+    /// Enables or disables spatial scoping for a Room
+    pub(crate) fn room_set_spatial(&mut self, room_key: &RoomKey, spatial: bool) {
+        if let Some(room) = self.rooms.get_mut(room_key) {
+            if room.is_spatial() == spatial {
+                return;
+            }
+            room.set_spatial(spatial);
+            for user_key in room.user_keys() {
+                for entity in room.entities() {
+                    if spatial {
+                        self.scope_cache.mark_dirty(user_key, entity);
+                    } else {
+                        self.scope_cache.mark_unchecked(user_key, entity);
+                    }
+                }
+            }
+        }
+    }
+
     //////// users
 
     /// Returns whether or not an User is currently in a specific Room, given
@@ -715,6 +1482,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     pub(crate) fn room_add_user(&mut self, room_key: &RoomKey, user_key: &UserKey) {
         if let Some(room) = self.rooms.get_mut(room_key) {
             room.subscribe_user(user_key);
+            for entity in room.entities() {
+                self.scope_cache.mark_unchecked(user_key, entity);
+            }
         }
     }
 
@@ -722,6 +1492,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     pub(crate) fn room_remove_user(&mut self, room_key: &RoomKey, user_key: &UserKey) {
         if let Some(room) = self.rooms.get_mut(room_key) {
             room.unsubscribe_user(user_key);
+            for entity in room.entities() {
+                self.scope_cache.mark_checked(user_key, entity);
+            }
         }
     }
 
@@ -748,6 +1521,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         let mut is_some = false;
         if let Some(room) = self.rooms.get_mut(room_key) {
             room.add_entity(entity);
+            for user_key in room.user_keys() {
+                self.scope_cache.mark_unchecked(user_key, entity);
+            }
             is_some = true;
         }
         if is_some {
@@ -759,6 +1535,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     pub(crate) fn room_remove_entity(&mut self, room_key: &RoomKey, entity: &E) {
         if let Some(room) = self.rooms.get_mut(room_key) {
             room.remove_entity(entity);
+            for user_key in room.user_keys() {
+                self.scope_cache.mark_checked(user_key, entity);
+            }
             self.world_record.entity_leave_rooms(entity);
         }
     }
@@ -769,6 +1548,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
             let entities: Vec<E> = room.entities().copied().collect();
             for entity in entities {
                 room.remove_entity(&entity);
+                for user_key in room.user_keys() {
+                    self.scope_cache.mark_checked(user_key, &entity);
+                }
                 self.world_record.entity_leave_rooms(&entity);
             }
         }
@@ -934,22 +1716,28 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
                                     }
                                 };
 
-                                // process data
-                                user_connection.process_incoming_data(
-                                    server_and_client_tick_opt,
-                                    &mut reader,
-                                    &self.world_record,
-                                );
-                            }
-                            PacketType::Disconnect => {
-                                if self
-                                    .handshake_manager
-                                    .verify_disconnect_request(user_connection, &mut reader)
+                                // process data, a Client which sends malformed data is
+                                // disconnected rather than trusted any further
+                                if user_connection
+                                    .process_incoming_data(
+                                        server_and_client_tick_opt,
+                                        &mut reader,
+                                        &self.world_record,
+                                    )
+                                    .is_err()
                                 {
                                     let user_key = user_connection.user_key;
                                     self.disconnect_user(&user_key);
                                 }
                             }
+                            PacketType::Disconnect
+                                if self
+                                    .handshake_manager
+                                    .verify_disconnect_request(user_connection, &mut reader) =>
+                            {
+                                let user_key = user_connection.user_key;
+                                self.disconnect_user(&user_key);
+                            }
                             PacketType::Heartbeat => {
                                 // read client tick, don't need to do anything else
                                 if let Some(tick_manager) = self.tick_manager.as_ref() {
@@ -1022,67 +1810,416 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     // Entity Helpers
 
     fn spawn_entity_init(&mut self, entity: &E) {
-        self.world_record.spawn_entity(entity);
+        self.world_record.spawn_entity(entity, EntityOwner::Server);
+    }
+
+    // Client-owned Entities
+
+    fn receive_entity_actions<W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        user_address: &SocketAddr,
+    ) {
+        let (user_key, actions) = {
+            let connection = self.user_connections.get_mut(user_address).unwrap();
+            (
+                connection.user_key,
+                connection.remote_entity_manager.receive_actions(),
+            )
+        };
+
+        for action in actions {
+            match action {
+                EntityAction::SpawnEntity(net_entity, component_kinds) => {
+                    let remote_entity_manager = &mut self
+                        .user_connections
+                        .get_mut(user_address)
+                        .unwrap()
+                        .remote_entity_manager;
+                    if let Some(max_client_entities) = self.server_config.max_client_entities {
+                        if remote_entity_manager.entity_count() >= max_client_entities {
+                            // leave the Entity unregistered, so the Client's further
+                            // actions on it are ignored
+                            for component_kind in component_kinds {
+                                remote_entity_manager.take_component(&net_entity, &component_kind);
+                            }
+                            self.incoming_events
+                                .push_back(Err(NaiaServerError::ClientEntitiesExhausted(user_key)));
+                            continue;
+                        }
+                    }
+
+                    let entity = world.spawn_entity();
+                    self.world_record
+                        .spawn_entity(&entity, EntityOwner::Client(user_key));
+                    self.user_connections
+                        .get_mut(user_address)
+                        .unwrap()
+                        .remote_entity_manager
+                        .register_entity(&net_entity, &entity);
+
+                    self.incoming_events
+                        .push_back(Ok(Event::SpawnEntity(user_key, entity)));
+
+                    for component_kind in component_kinds {
+                        self.receive_insert_component(
+                            world,
+                            user_address,
+                            &net_entity,
+                            &component_kind,
+                        );
+                    }
+                }
+                EntityAction::DespawnEntity(net_entity) => {
+                    let remote_entity_manager = &mut self
+                        .user_connections
+                        .get_mut(user_address)
+                        .unwrap()
+                        .remote_entity_manager;
+                    if let Some(entity) = remote_entity_manager
+                        .net_entity_to_entity(&net_entity)
+                        .copied()
+                    {
+                        remote_entity_manager.deregister_entity(&entity);
+
+                        if !world.has_entity(&entity) {
+                            continue;
+                        }
+
+                        // Generate event for each component, handing references off just in
+                        // case
+                        for component_kind in self.world_record.component_kinds(&entity).unwrap() {
+                            if let Some(component) =
+                                self.remove_component_of_kind(world, &entity, &component_kind)
+                            {
+                                self.incoming_events.push_back(Ok(Event::RemoveComponent(
+                                    user_key, entity, component,
+                                )));
+                            }
+                        }
+
+                        self.despawn_entity(world, &entity);
+
+                        self.incoming_events
+                            .push_back(Ok(Event::DespawnEntity(user_key, entity)));
+                    }
+                }
+                EntityAction::InsertComponent(net_entity, component_kind) => {
+                    self.receive_insert_component(
+                        world,
+                        user_address,
+                        &net_entity,
+                        &component_kind,
+                    );
+                }
+                EntityAction::RemoveComponent(net_entity, component_kind) => {
+                    let entity_opt = self
+                        .user_connections
+                        .get(user_address)
+                        .unwrap()
+                        .remote_entity_manager
+                        .net_entity_to_entity(&net_entity)
+                        .copied();
+                    if let Some(entity) = entity_opt {
+                        if !world.has_component_of_kind(&entity, &component_kind) {
+                            continue;
+                        }
+                        if let Some(component) =
+                            self.remove_component_of_kind(world, &entity, &component_kind)
+                        {
+                            self.incoming_events
+                                .push_back(Ok(Event::RemoveComponent(user_key, entity, component)));
+                        }
+                    }
+                }
+                EntityAction::RequestAuthority(net_entity) => {
+                    let connection = self.user_connections.get_mut(user_address).unwrap();
+                    let entity_opt = connection
+                        .entity_manager
+                        .try_net_entity_to_entity(&net_entity);
+                    if let Some(entity) = entity_opt {
+                        if !connection.entity_manager.scope_has_entity(&entity) {
+                            continue;
+                        }
+                        match self.world_record.entity_authority(&entity) {
+                            Some(authority) if authority == user_key => {
+                                // already has authority
+                            }
+                            None if self.world_record.entity_is_delegated(&entity) => {
+                                self.entity_give_authority(&entity, &user_key);
+                            }
+                            _ => {
+                                connection.entity_manager.deny_authority(&entity);
+                                self.incoming_events
+                                    .push_back(Ok(Event::AuthorityDenied(user_key, entity)));
+                            }
+                        }
+                    }
+                }
+                EntityAction::ReleaseAuthority(net_entity) => {
+                    let entity_opt = self
+                        .user_connections
+                        .get(user_address)
+                        .unwrap()
+                        .entity_manager
+                        .try_net_entity_to_entity(&net_entity);
+                    if let Some(entity) = entity_opt {
+                        if self.world_record.entity_authority(&entity) == Some(user_key) {
+                            self.entity_take_authority(&entity);
+                        }
+                    }
+                }
+                EntityAction::GrantAuthority(_)
+                | EntityAction::RevokeAuthority(_)
+                | EntityAction::DenyAuthority(_)
+                | EntityAction::GrantComponentAuthority(_, _)
+                | EntityAction::RevokeComponentAuthority(_, _)
+                | EntityAction::GrantOwnership(_)
+                | EntityAction::RevokeOwnership(_)
+                | EntityAction::HideEntity(_)
+                | EntityAction::RevealEntity(_)
+                | EntityAction::Noop => {
+                    // do nothing
+                }
+            }
+        }
+    }
+
+    fn receive_entity_updates<W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        user_address: &SocketAddr,
+    ) {
+        let (user_key, updates) = {
+            let connection = self.user_connections.get_mut(user_address).unwrap();
+            (
+                connection.user_key,
+                connection.remote_entity_manager.receive_updates(),
+            )
+        };
+
+        for (net_entity, diff_mask, component_update) in updates {
+            let connection = self.user_connections.get(user_address).unwrap();
+            let entity = match connection
+                .entity_manager
+                .try_net_entity_to_entity(&net_entity)
+            {
+                Some(entity) => entity,
+                None => continue,
+            };
+
+            let component_kind = component_update.kind;
+            if !world.has_component_of_kind(&entity, &component_kind) {
+                continue;
+            }
+
+            // only accept updates from the User with authority over the
+            // Component, or over its whole Entity
+            if self.component_authority_of_kind(&entity, &component_kind) != Some(user_key) {
+                continue;
+            }
+
+            // only forward changes to Properties the Component actually has
+            let diff_mask = {
+                let component = world
+                    .component_of_kind(&entity, &component_kind)
+                    .expect("Component does not exist in World");
+                if diff_mask.byte_number() != component.diff_mask_size() {
+                    continue;
+                }
+                let mut property_mask = DiffMask::new(component.diff_mask_size());
+                for property_index in 0..component.property_count() {
+                    if let Some(true) = diff_mask.bit(property_index) {
+                        property_mask.set_bit(property_index, true);
+                    }
+                }
+                property_mask
+            };
+
+            {
+                let converter =
+                    EntityConverter::new(&self.world_record, &connection.entity_manager);
+                world.component_apply_update(
+                    &converter,
+                    &entity,
+                    &component_kind,
+                    component_update,
+                );
+            }
+
+            // forward the update to every other User
+            self.diff_handler
+                .as_ref()
+                .read()
+                .expect("DiffHandler should be initialized")
+                .remote_mutate(user_address, &entity, &component_kind, &diff_mask);
+
+            self.incoming_events.push_back(Ok(Event::UpdateComponent(
+                user_key,
+                entity,
+                component_kind,
+            )));
+        }
+    }
+
+    fn receive_insert_component<W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        user_address: &SocketAddr,
+        net_entity: &NetEntity,
+        component_kind: &P::Kind,
+    ) {
+        let connection = self.user_connections.get_mut(user_address).unwrap();
+        let user_key = connection.user_key;
+        let component_opt = connection
+            .remote_entity_manager
+            .take_component(net_entity, component_kind);
+        let entity_opt = connection
+            .remote_entity_manager
+            .net_entity_to_entity(net_entity)
+            .copied();
+
+        if let (Some(entity), Some(component)) = (entity_opt, component_opt) {
+            if !world.has_entity(&entity) || world.has_component_of_kind(&entity, component_kind) {
+                return;
+            }
+
+            component.extract_and_insert(
+                &entity,
+                &mut ComponentInserter {
+                    server: self,
+                    world,
+                },
+            );
+
+            self.incoming_events.push_back(Ok(Event::InsertComponent(
+                user_key,
+                entity,
+                *component_kind,
+            )));
+        }
     }
 
     // Entity Scopes
 
     fn update_entity_scopes<W: WorldRefType<P, E>>(&mut self, world: &W) {
+        // Users lose authority over any Entity which leaves their scope, and
+        // over its Components
+        let mut revoked_entities = Vec::new();
+        let mut revoked_components = Vec::new();
+
         for (_, room) in self.rooms.iter_mut() {
             while let Some((removed_user, removed_entity)) = room.pop_entity_removal_queue() {
+                if self.world_record.entity_authority(&removed_entity) == Some(removed_user) {
+                    revoked_entities.push(removed_entity);
+                }
+                for component_kind in self.world_record.components_with_authority(&removed_entity) {
+                    if self
+                        .world_record
+                        .component_authority(&removed_entity, &component_kind)
+                        == Some(removed_user)
+                    {
+                        revoked_components.push((removed_entity, component_kind));
+                    }
+                }
                 if let Some(user) = self.users.get(&removed_user) {
                     if let Some(user_connection) = self.user_connections.get_mut(&user.address) {
                         //remove entity from user connection
-                        user_connection
-                            .entity_manager
-                            .despawn_entity(&removed_entity);
+                        user_connection.entity_manager.exit_scope(&removed_entity);
                     }
                 }
             }
+        }
 
-            // TODO: we should be able to cache these tuples of keys to avoid building a new
-            // list each time
-            for user_key in room.user_keys() {
-                for entity in room.entities() {
-                    if world.has_entity(entity) {
-                        if let Some(user) = self.users.get(user_key) {
-                            if let Some(user_connection) =
-                                self.user_connections.get_mut(&user.address)
-                            {
-                                let currently_in_scope =
-                                    user_connection.entity_manager.scope_has_entity(entity);
+        for (user_key, entity) in self.spatial_grid.update() {
+            self.scope_cache.mark_dirty(&user_key, &entity);
+        }
 
-                                let should_be_in_scope = if let Some(in_scope) =
-                                    self.entity_scope_map.get(user_key, entity)
-                                {
-                                    *in_scope
-                                } else {
-                                    false
-                                };
+        // only pairs affected by a change since the last update need to be looked at
+        for (user_key, entity) in self.scope_cache.take_dirty() {
+            if !world.has_entity(&entity) {
+                // the Entity may not have been added to the World yet
+                if self.world_record.has_entity(&entity) {
+                    self.scope_cache.mark_dirty(&user_key, &entity);
+                }
+                continue;
+            }
 
-                                if should_be_in_scope {
-                                    if !currently_in_scope {
-                                        // add entity to the connections local scope
-                                        user_connection.entity_manager.spawn_entity(entity);
-                                        // add components to connections local scope
-                                        for component_kind in
-                                            self.world_record.component_kinds(entity).unwrap()
-                                        {
-                                            user_connection
-                                                .entity_manager
-                                                .insert_component(entity, &component_kind);
-                                        }
-                                    }
-                                } else if currently_in_scope {
-                                    // remove entity from the connections local scope
-                                    user_connection.entity_manager.despawn_entity(entity);
+            let room = match self
+                .world_record
+                .entity_room(&entity)
+                .and_then(|room_key| self.rooms.get(&room_key))
+            {
+                Some(room) => room,
+                None => continue,
+            };
+            if !room.has_user(&user_key) {
+                continue;
+            }
+
+            // an explicit include / exclude always takes precedence over position
+            let should_be_in_scope = match self.entity_scope_map.get(&user_key, &entity).copied() {
+                Some(in_scope) => in_scope,
+                None => room.is_spatial() && self.spatial_grid.is_visible(&user_key, &entity),
+            };
+
+            // never replicate an Entity back to the Client that owns it
+            let should_be_in_scope = should_be_in_scope
+                && self.world_record.entity_owner(&entity) != Some(EntityOwner::Client(user_key));
+
+            if let Some(user) = self.users.get(&user_key) {
+                if let Some(user_connection) = self.user_connections.get_mut(&user.address) {
+                    let currently_in_scope =
+                        user_connection.entity_manager.scope_has_entity(&entity);
+
+                    if should_be_in_scope {
+                        if !currently_in_scope {
+                            // add entity to the connections local scope
+                            if user_connection.entity_manager.spawn_entity(&entity) {
+                                // add components to connections local scope
+                                for component_kind in
+                                    self.world_record.component_kinds(&entity).unwrap()
+                                {
+                                    user_connection
+                                        .entity_manager
+                                        .insert_component(&entity, &component_kind);
                                 }
+                            } else if user_connection
+                                .entity_manager
+                                .report_net_entities_exhausted()
+                            {
+                                self.incoming_events.push_back(Err(
+                                    NaiaServerError::NetEntitiesExhausted(user_key),
+                                ));
+                            }
+                        }
+                    } else if currently_in_scope {
+                        // remove entity from the connections local scope
+                        user_connection.entity_manager.exit_scope(&entity);
+
+                        if self.world_record.entity_authority(&entity) == Some(user_key) {
+                            revoked_entities.push(entity);
+                        }
+                        for component_kind in self.world_record.components_with_authority(&entity) {
+                            if self
+                                .world_record
+                                .component_authority(&entity, &component_kind)
+                                == Some(user_key)
+                            {
+                                revoked_components.push((entity, component_kind));
                             }
                         }
                     }
                 }
             }
         }
+
+        for entity in revoked_entities {
+            self.entity_take_authority(&entity);
+        }
+        for (entity, component_kind) in revoked_components {
+            self.entity_take_component_authority(&entity, &component_kind);
+        }
     }
 
     // Component Helpers
@@ -1090,21 +2227,81 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     fn component_init<R: ReplicateSafe<P>>(&mut self, entity: &E, component_ref: &mut R) {
         let component_kind = component_ref.kind();
         self.world_record.add_component(entity, &component_kind);
+        self.world_record
+            .set_component_update_rate(&component_kind, component_ref.update_rate());
+
+        if component_ref.is_static() {
+            // static Components are sent whole on insert and never diffed
+            component_ref.set_mutator(&PropertyMutator::new(StaticMutator));
+            return;
+        }
 
         let diff_mask_length: u8 = component_ref.diff_mask_size();
 
-        let mut_sender = self
-            .diff_handler
-            .as_ref()
-            .write()
-            .expect("DiffHandler should be initialized")
-            .register_component(entity, &component_kind, diff_mask_length);
+        let authority_address = self
+            .world_record
+            .entity_authority(entity)
+            .and_then(|user_key| self.user_address(&user_key));
+
+        let mut_sender = {
+            let mut diff_handler = self
+                .diff_handler
+                .as_ref()
+                .write()
+                .expect("DiffHandler should be initialized");
+            let mut_sender =
+                diff_handler.register_component(entity, &component_kind, diff_mask_length);
+            diff_handler.set_authority(entity, &component_kind, authority_address);
+            mut_sender
+        };
 
         let prop_mutator = PropertyMutator::new(mut_sender);
 
         component_ref.set_mutator(&prop_mutator);
     }
 
+    fn diff_set_authority(&mut self, entity: &E, authority_address: Option<SocketAddr>) {
+        let diff_handler = self
+            .diff_handler
+            .as_ref()
+            .read()
+            .expect("DiffHandler should be initialized");
+        for component_kind in self.world_record.component_kinds(entity).unwrap() {
+            // a User with authority over the Component alone keeps it
+            if self
+                .world_record
+                .component_authority(entity, &component_kind)
+                .is_some()
+            {
+                continue;
+            }
+            diff_handler.set_authority(entity, &component_kind, authority_address);
+        }
+    }
+
+    fn component_removal_cleanup(&mut self, entity: &E, component_kind: &P::Kind) {
+        self.entity_take_component_authority(entity, component_kind);
+
+        // clean up component on all connections
+
+        // TODO: should be able to make this more efficient by caching for every Entity
+        // which scopes they are part of
+        for (_, user_connection) in self.user_connections.iter_mut() {
+            // remove component from user connection, including any copy of the
+            // Entity hidden on the Client, which holds it back until revealed
+            if user_connection.entity_manager.scope_has_entity(entity)
+                || user_connection.entity_manager.entity_is_hidden(entity)
+            {
+                user_connection
+                    .entity_manager
+                    .remove_component(entity, component_kind);
+            }
+        }
+
+        // cleanup all other loose ends
+        self.component_cleanup(entity, component_kind);
+    }
+
     fn component_cleanup(&mut self, entity: &E, component_kind: &P::Kind) {
         self.world_record.remove_component(entity, component_kind);
         self.diff_handler
@@ -1126,3 +2323,33 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityH
         self.world_record.entity_to_handle(entity)
     }
 }
+
+// ComponentInserter
+
+/// Inserts a Component received from a Client into the World, making sure it is
+/// tracked by the Server the same as any Component inserted by the Server itself
+struct ComponentInserter<
+    's,
+    'w,
+    P: Protocolize,
+    E: Copy + Eq + Hash + Send + Sync,
+    C: ChannelIndex,
+    W: WorldMutType<P, E>,
+> {
+    server: &'s mut Server<P, E, C>,
+    world: &'w mut W,
+}
+
+impl<
+        's,
+        'w,
+        P: Protocolize,
+        E: Copy + Eq + Hash + Send + Sync,
+        C: ChannelIndex,
+        W: WorldMutType<P, E>,
+    > ProtocolInserter<P, E> for ComponentInserter<'s, 'w, P, E, C, W>
+{
+    fn insert<R: ReplicateSafe<P>>(&mut self, entity: &E, component: R) {
+        self.server.insert_component(self.world, entity, component);
+    }
+}
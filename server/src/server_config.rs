@@ -1,7 +1,9 @@
-use std::default::Default;
+use std::{default::Default, time::Duration};
 
 use naia_shared::ConnectionConfig;
 
+use crate::SpatialConfig;
+
 /// Contains Config properties which will be used by the Server
 #[derive(Clone)]
 pub struct ServerConfig {
@@ -10,6 +12,17 @@ pub struct ServerConfig {
     /// Determines whether to require that the Client send some auth message
     /// in order to connect.
     pub require_auth: bool,
+    /// Used to configure scoping in Rooms which have spatial scoping enabled
+    pub spatial: SpatialConfig,
+    /// When set, an Entity which leaves a User's scope is hidden on their
+    /// Client rather than despawned, for up to this long. If it comes back
+    /// into scope in that time, only the changes made while it was hidden
+    /// are sent, instead of the whole Entity.
+    pub entity_cache_duration: Option<Duration>,
+    /// When set, the most Entities each Client may have spawned at once. An
+    /// Entity a Client spawns past this is not created on the Server, and a
+    /// `NaiaServerError::ClientEntitiesExhausted` is returned instead.
+    pub max_client_entities: Option<usize>,
 }
 
 impl Default for ServerConfig {
@@ -17,6 +30,9 @@ impl Default for ServerConfig {
         Self {
             connection: ConnectionConfig::default(),
             require_auth: true,
+            spatial: SpatialConfig::default(),
+            entity_cache_duration: None,
+            max_client_entities: None,
         }
     }
 }
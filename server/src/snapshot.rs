@@ -0,0 +1,127 @@
+use std::{collections::HashMap, error::Error, fmt};
+
+use naia_shared::{blob_hash, EntityHandle, NetEntity, NetEntityHandleConverter};
+
+use crate::room::RoomKey;
+
+/// Marks the start of every snapshot
+const SNAPSHOT_MAGIC: [u8; 4] = *b"NSNP";
+/// Raised whenever the layout of a snapshot changes, so that snapshots
+/// written in an older layout are rejected rather than misread
+const SNAPSHOT_VERSION: u16 = 1;
+/// The magic, the version, then a hash of everything that follows
+const SNAPSHOT_HEADER_SIZE: usize = 4 + 2 + 8;
+
+/// Why `Server::restore_snapshot` could not restore a snapshot
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The bytes were not written by `Server::snapshot`
+    NotASnapshot,
+    /// The snapshot was written in a layout this version of Naia can't read
+    UnsupportedVersion(u16),
+    /// The snapshot has been truncated or corrupted
+    Corrupt,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            SnapshotError::NotASnapshot => write!(f, "Naia Snapshot Error: not a snapshot"),
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "Naia Snapshot Error: unsupported snapshot version {}, expected {}",
+                version, SNAPSHOT_VERSION
+            ),
+            SnapshotError::Corrupt => {
+                write!(f, "Naia Snapshot Error: snapshot is truncated or corrupt")
+            }
+        }
+    }
+}
+
+impl Error for SnapshotError {}
+
+/// Puts the header in front of the body of a snapshot
+pub(crate) fn write_header(body: Vec<u8>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(SNAPSHOT_HEADER_SIZE + body.len());
+    bytes.extend_from_slice(&SNAPSHOT_MAGIC);
+    bytes.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&blob_hash(&body).to_le_bytes());
+    bytes.extend_from_slice(&body);
+    bytes
+}
+
+/// Checks the header of a snapshot, returning its body
+pub(crate) fn read_header(bytes: &[u8]) -> Result<&[u8], SnapshotError> {
+    if bytes.len() < SNAPSHOT_HEADER_SIZE || bytes[0..4] != SNAPSHOT_MAGIC {
+        return Err(SnapshotError::NotASnapshot);
+    }
+
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    if version != SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(version));
+    }
+
+    let mut hash_bytes = [0; 8];
+    hash_bytes.copy_from_slice(&bytes[6..SNAPSHOT_HEADER_SIZE]);
+    let body = &bytes[SNAPSHOT_HEADER_SIZE..];
+    if blob_hash(body) != u64::from_le_bytes(hash_bytes) {
+        return Err(SnapshotError::Corrupt);
+    }
+
+    Ok(body)
+}
+
+/// The Entities and Rooms created by `Server::restore_snapshot`, in the same
+/// order they were in when the snapshot was taken
+pub struct RestoredSnapshot<E> {
+    pub entities: Vec<E>,
+    pub rooms: Vec<RoomKey>,
+}
+
+/// Converts between Entity handles and an Entity's position in a snapshot,
+/// so that references between Entities survive being written and restored
+pub(crate) struct SnapshotEntityConverter {
+    indices: HashMap<EntityHandle, NetEntity>,
+    handles: Vec<EntityHandle>,
+}
+
+impl SnapshotEntityConverter {
+    pub fn new(handles: Vec<EntityHandle>) -> Self {
+        if handles.len() > u32::MAX as usize {
+            panic!("a snapshot can hold at most {} Entities", u32::MAX);
+        }
+
+        let indices = handles
+            .iter()
+            .enumerate()
+            .map(|(index, handle)| (*handle, NetEntity::from(index as u32)))
+            .collect();
+
+        Self { indices, handles }
+    }
+}
+
+impl NetEntityHandleConverter for SnapshotEntityConverter {
+    fn handle_to_net_entity(&self, entity_handle: &EntityHandle) -> NetEntity {
+        *self
+            .indices
+            .get(entity_handle)
+            .expect("Entity is not part of the snapshot")
+    }
+
+    fn net_entity_to_handle(&self, net_entity: &NetEntity) -> EntityHandle {
+        *self
+            .handles
+            .get(u32::from(*net_entity) as usize)
+            .expect("snapshot refers to an Entity it does not contain")
+    }
+
+    fn try_handle_to_net_entity(&self, entity_handle: &EntityHandle) -> Option<NetEntity> {
+        self.indices.get(entity_handle).copied()
+    }
+
+    fn try_net_entity_to_handle(&self, net_entity: &NetEntity) -> Option<EntityHandle> {
+        self.handles.get(u32::from(*net_entity) as usize).copied()
+    }
+}
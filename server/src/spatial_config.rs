@@ -0,0 +1,33 @@
+use std::default::Default;
+
+/// Contains Config properties which will be used to determine which Entities
+/// are within view of each User, in Rooms which use spatial scoping
+#[derive(Clone, Debug)]
+pub struct SpatialConfig {
+    /// The width and height of each cell of the grid Entities are sorted
+    /// into. Works best when close to the typical view radius of a User.
+    pub cell_size: f32,
+    /// How far beyond a User's view radius an Entity must move before it
+    /// leaves their scope, so that Entities on the edge of the view don't
+    /// repeatedly enter and leave scope
+    pub hysteresis: f32,
+}
+
+impl SpatialConfig {
+    /// Creates a new SpatialConfig, used to configure spatial scoping
+    pub fn new(cell_size: f32, hysteresis: f32) -> Self {
+        SpatialConfig {
+            cell_size,
+            hysteresis,
+        }
+    }
+}
+
+impl Default for SpatialConfig {
+    fn default() -> Self {
+        Self {
+            cell_size: 100.0,
+            hysteresis: 10.0,
+        }
+    }
+}
@@ -29,7 +29,7 @@ impl<P: Protocolize> ChannelTickBufferReceiver<P> {
         bit_reader: &mut BitReader,
     ) {
         let mut last_read_tick = *remote_tick;
-        let message_count = message_list_header::read(bit_reader);
+        let message_count = message_list_header::read(bit_reader).unwrap();
         for _ in 0..message_count {
             self.read_message(host_tick, &mut last_read_tick, channel_reader, bit_reader);
         }
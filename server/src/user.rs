@@ -5,7 +5,7 @@ use naia_shared::{BigMapKey, ChannelIndex, Protocolize};
 use crate::{RoomKey, Server};
 
 // UserKey
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 pub struct UserKey(u64);
 
 impl BigMapKey for UserKey {
@@ -75,6 +75,14 @@ impl<'s, P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Use
         self.server.disconnect_user(&self.key);
     }
 
+    /// Sets the position & radius of the User's view. In Rooms with spatial
+    /// scoping enabled, Entities within this view are in the User's scope.
+    pub fn set_view(&mut self, x: f32, y: f32, radius: f32) -> &mut Self {
+        self.server.user_set_view(&self.key, x, y, radius);
+
+        self
+    }
+
     // Rooms
 
     pub fn enter_room(&mut self, room_key: &RoomKey) -> &mut Self {
@@ -1,6 +1,6 @@
 use std::hash::Hash;
 
-use naia_shared::{ChannelIndex, Protocolize};
+use naia_shared::{ChannelIndex, Protocolize, Replicate};
 
 use super::{server::Server, user::UserKey};
 
@@ -27,4 +27,42 @@ impl<'s, P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex>
 
         self
     }
+
+    /// Sets how important the given Entity is to the User, relative to other
+    /// Entities in scope. Entities with a higher priority have their updates
+    /// written first when there is not enough room to send everything at
+    /// once. The default priority is 1.0.
+    pub fn set_priority(&mut self, entity: &E, priority: f32) -> &mut Self {
+        self.server
+            .user_scope_set_priority(&self.key, entity, priority);
+
+        self
+    }
+
+    /// Hides a Component of the given Entity from the User, who still sees the
+    /// Entity but not that Component. If the User already has the Component,
+    /// it is removed from their copy of the Entity
+    pub fn hide_component<R: Replicate<P>>(&mut self, entity: &E) -> &mut Self {
+        self.server.user_scope_set_component_hidden(
+            &self.key,
+            entity,
+            &Protocolize::kind_of::<R>(),
+            true,
+        );
+
+        self
+    }
+
+    /// Shows a Component previously hidden with `hide_component`, inserting
+    /// it into the User's copy of the Entity
+    pub fn reveal_component<R: Replicate<P>>(&mut self, entity: &E) -> &mut Self {
+        self.server.user_scope_set_component_hidden(
+            &self.key,
+            entity,
+            &Protocolize::kind_of::<R>(),
+            false,
+        );
+
+        self
+    }
 }
@@ -19,7 +19,7 @@ pub fn protocolize_derive(input: proc_macro::TokenStream) -> proc_macro::TokenSt
 }
 
 /// Derives the Replicate trait for a given struct
-#[proc_macro_derive(Replicate, attributes(protocol_path))]
+#[proc_macro_derive(Replicate, attributes(protocol_path, replicate, property))]
 pub fn replicate_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     replicate_impl(input)
 }
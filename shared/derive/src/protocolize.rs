@@ -186,8 +186,8 @@ pub fn read_method(enum_name: &Ident, variants: &Vec<Ident>) -> TokenStream {
     }
 
     return quote! {
-        fn read(bit_reader: &mut serde::BitReader, converter: &dyn NetEntityHandleConverter) -> Self {
-            let protocol_kind: Self::Kind = Self::Kind::de(bit_reader).unwrap();
+        fn read(bit_reader: &mut serde::BitReader, converter: &dyn NetEntityHandleConverter) -> Result<Self, serde::SerdeErr> {
+            let protocol_kind: Self::Kind = Self::Kind::de(bit_reader)?;
             match protocol_kind {
                 #variants_build
             }
@@ -215,8 +215,8 @@ pub fn read_create_update_method(enum_name: &Ident, variants: &Vec<Ident>) -> To
     }
 
     return quote! {
-        fn read_create_update(bit_reader: &mut serde::BitReader) -> ComponentUpdate<Self::Kind> {
-            let protocol_kind: Self::Kind = Self::Kind::de(bit_reader).unwrap();
+        fn read_create_update(bit_reader: &mut serde::BitReader) -> Result<ComponentUpdate<Self::Kind>, serde::SerdeErr> {
+            let protocol_kind: Self::Kind = Self::Kind::de(bit_reader)?;
             match protocol_kind {
                 #variants_build
             }
@@ -1,8 +1,8 @@
 use proc_macro2::{Punct, Spacing, Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, Ident, Lit, Meta, Path,
-    PathArguments, Result, Type,
+    parse_macro_input, Data, DeriveInput, Field, Fields, GenericArgument, Ident, Lit, Meta,
+    NestedMeta, Path, PathArguments, Result, Type,
 };
 
 pub fn replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -11,9 +11,17 @@ pub fn replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     // Helper Properties
     let properties = properties(&input);
 
+    if is_nested(&input) {
+        return nested_impl(input, properties);
+    }
+
     // Paths
     let (protocol_path, protocol_name) = protocol_path(&input);
 
+    // Options
+    let update_rate_method = update_rate_method(&input);
+    let is_static_method = is_static_method(&input);
+
     // Names
     let replica_name = input.ident;
     let protocol_kind_name = format_ident!("{}Kind", protocol_name);
@@ -29,21 +37,14 @@ pub fn replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream
         read_create_update_method(&replica_name, &protocol_kind_name, &properties);
 
     // ReplicateSafe Derive Methods
-    let diff_mask_size = {
-        let len = properties.len();
-        if len == 0 {
-            0
-        } else {
-            ((len - 1) / 8) + 1
-        }
-    } as u8;
+    let (_, property_count) = property_offsets(&properties);
     let dyn_ref_method = dyn_ref_method(&protocol_name);
     let dyn_mut_method = dyn_mut_method(&protocol_name);
     let to_protocol_method = into_protocol_method(&protocol_name, &replica_name);
     let protocol_copy_method = protocol_copy_method(&protocol_name, &replica_name);
     let clone_method = clone_method(&replica_name, &properties);
     let mirror_method = mirror_method(&protocol_name, &replica_name, &properties);
-    let set_mutator_method = set_mutator_method(&properties);
+    let set_mutator_method = set_mutator_method(&enum_name, &properties);
     let read_apply_update_method = read_apply_update_method(&protocol_kind_name, &properties);
     let write_method = write_method(&properties);
     let write_update_method = write_update_method(&enum_name, &properties);
@@ -52,8 +53,8 @@ pub fn replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
     let gen = quote! {
         use std::{rc::Rc, cell::RefCell, io::Cursor};
-        use naia_shared::{DiffMask, PropertyMutate, ReplicateSafe, PropertyMutator, ComponentUpdate,
-            Protocolize, ReplicaDynRef, ReplicaDynMut, serde::{BitReader, BitWrite, BitWriter, OwnedBitReader, Serde}, NetEntityHandleConverter};
+        use naia_shared::{DiffMask, PropertyMutate, ReplicateSafe, ReplicateNested, PropertyMutator, ComponentUpdate,
+            Protocolize, ReplicaDynRef, ReplicaDynMut, serde::{BitReader, BitWrite, BitWriter, OwnedBitReader, Serde, SerdeErr}, NetEntityHandleConverter, UpdateRate};
         use #protocol_path::{#protocol_name, #protocol_kind_name};
         mod internal {
             pub use naia_shared::{EntityProperty, EntityHandle};
@@ -67,7 +68,19 @@ pub fn replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream
             #read_create_update_method
         }
         impl ReplicateSafe<#protocol_name> for #replica_name {
-            fn diff_mask_size(&self) -> u8 { #diff_mask_size }
+            fn property_count(&self) -> u8 {
+                #property_count
+            }
+            fn diff_mask_size(&self) -> u8 {
+                let property_count: u8 = #property_count;
+                if property_count == 0 {
+                    0
+                } else {
+                    ((property_count - 1) / 8) + 1
+                }
+            }
+            #update_rate_method
+            #is_static_method
             fn kind(&self) -> #protocol_kind_name {
                 return Protocolize::kind_of::<Self>();
             }
@@ -92,14 +105,110 @@ pub fn replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     proc_macro::TokenStream::from(gen)
 }
 
+/// Derives `ReplicateNested` for a struct marked `#[replicate(nested)]`, which
+/// can then be used as a field of another Replica
+fn nested_impl(input: DeriveInput, properties: Vec<Property>) -> proc_macro::TokenStream {
+    if properties.iter().any(|property| property.owner_only()) {
+        panic!("owner_only is not supported on nested structs");
+    }
+
+    // Names
+    let nested_name = input.ident;
+    let enum_name = format_ident!("{}Property", nested_name);
+
+    // Definitions
+    let property_enum_definition = property_enum(&enum_name, &properties);
+    let (_, property_count) = property_offsets(&properties);
+
+    // Methods
+    let new_complete_method = new_complete_method(&nested_name, &enum_name, &properties);
+    let set_mutator_method = set_mutator_method(&enum_name, &properties);
+    let mirror_body = mirror_body(&properties);
+    let write_body = write_body(&properties);
+    let (prop_reads, prop_names) = read_body(&enum_name, &properties);
+    let read_write_body = read_write_body(&properties);
+    let write_update_body = write_update_body(&enum_name, &properties);
+    let read_apply_update_body = read_apply_update_body(&properties);
+    let has_entity_properties = has_entity_properties_method(&properties);
+    let entities = entities_method(&properties);
+    let clone_method = clone_method(&nested_name, &properties);
+
+    let gen = quote! {
+        use naia_shared::{DiffMask, PropertyMutator, ReplicateNested,
+            serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr}, NetEntityHandleConverter};
+        mod internal {
+            pub use naia_shared::{EntityProperty, EntityHandle};
+        }
+
+        #property_enum_definition
+
+        impl #nested_name {
+            #new_complete_method
+        }
+        impl ReplicateNested for #nested_name {
+            const PROPERTY_COUNT: u8 = #property_count;
+            #set_mutator_method
+            fn mirror(&mut self, replica: &Self) {
+                #mirror_body
+            }
+            fn write(&self, bit_writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter) {
+                #write_body
+            }
+            fn read(bit_reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) -> Result<Self, SerdeErr> {
+                #prop_reads
+                Ok(#nested_name {
+                    #prop_names
+                })
+            }
+            fn read_write(bit_reader: &mut BitReader, update_writer: &mut BitWriter) -> Result<(), SerdeErr> {
+                #read_write_body
+                Ok(())
+            }
+            fn write_update(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter) {
+                #write_update_body
+            }
+            fn read_apply_update(&mut self, reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) {
+                #read_apply_update_body
+            }
+            #has_entity_properties
+            #entities
+        }
+        impl Clone for #nested_name {
+            #clone_method
+        }
+    };
+
+    proc_macro::TokenStream::from(gen)
+}
+
 pub struct NormalProperty {
     pub variable_name: Ident,
     pub inner_type: Type,
     pub uppercase_variable_name: Ident,
+    pub codec: Option<Path>,
+    pub owner_only: bool,
 }
 
 pub struct EntityProperty {
     pub variable_name: Ident,
+    pub property_type: Type,
+    // whether the Property holds many Entities, rather than one
+    pub is_collection: bool,
+    pub uppercase_variable_name: Ident,
+    pub owner_only: bool,
+}
+
+pub struct CollectionProperty {
+    pub variable_name: Ident,
+    pub collection_type: Type,
+    pub value_type: TokenStream,
+    pub uppercase_variable_name: Ident,
+    pub owner_only: bool,
+}
+
+pub struct NestedProperty {
+    pub variable_name: Ident,
+    pub nested_type: Type,
     pub uppercase_variable_name: Ident,
 }
 
@@ -107,10 +216,17 @@ pub struct EntityProperty {
 pub enum Property {
     Normal(NormalProperty),
     Entity(EntityProperty),
+    Collection(CollectionProperty),
+    Nested(NestedProperty),
 }
 
 impl Property {
-    pub fn normal(variable_name: Ident, inner_type: Type) -> Self {
+    pub fn normal(
+        variable_name: Ident,
+        inner_type: Type,
+        codec: Option<Path>,
+        owner_only: bool,
+    ) -> Self {
         Self::Normal(NormalProperty {
             variable_name: variable_name.clone(),
             inner_type,
@@ -118,12 +234,51 @@ impl Property {
                 variable_name.to_string().to_uppercase().as_str(),
                 Span::call_site(),
             ),
+            codec,
+            owner_only,
         })
     }
 
-    pub fn entity(variable_name: Ident) -> Self {
+    pub fn entity(
+        variable_name: Ident,
+        property_type: Type,
+        is_collection: bool,
+        owner_only: bool,
+    ) -> Self {
         Self::Entity(EntityProperty {
             variable_name: variable_name.clone(),
+            property_type,
+            is_collection,
+            uppercase_variable_name: Ident::new(
+                variable_name.to_string().to_uppercase().as_str(),
+                Span::call_site(),
+            ),
+            owner_only,
+        })
+    }
+
+    pub fn collection(
+        variable_name: Ident,
+        collection_type: Type,
+        value_type: TokenStream,
+        owner_only: bool,
+    ) -> Self {
+        Self::Collection(CollectionProperty {
+            variable_name: variable_name.clone(),
+            collection_type,
+            value_type,
+            uppercase_variable_name: Ident::new(
+                variable_name.to_string().to_uppercase().as_str(),
+                Span::call_site(),
+            ),
+            owner_only,
+        })
+    }
+
+    pub fn nested(variable_name: Ident, nested_type: Type) -> Self {
+        Self::Nested(NestedProperty {
+            variable_name: variable_name.clone(),
+            nested_type,
             uppercase_variable_name: Ident::new(
                 variable_name.to_string().to_uppercase().as_str(),
                 Span::call_site(),
@@ -135,6 +290,18 @@ impl Property {
         match self {
             Self::Normal(property) => &property.variable_name,
             Self::Entity(property) => &property.variable_name,
+            Self::Collection(property) => &property.variable_name,
+            Self::Nested(property) => &property.variable_name,
+        }
+    }
+
+    /// Whether the Property is only written to the User which owns the Entity
+    pub fn owner_only(&self) -> bool {
+        match self {
+            Self::Normal(property) => property.owner_only,
+            Self::Entity(property) => property.owner_only,
+            Self::Collection(property) => property.owner_only,
+            Self::Nested(_) => false,
         }
     }
 
@@ -142,6 +309,8 @@ impl Property {
         match self {
             Self::Normal(property) => &property.uppercase_variable_name,
             Self::Entity(property) => &property.uppercase_variable_name,
+            Self::Collection(property) => &property.uppercase_variable_name,
+            Self::Nested(property) => &property.uppercase_variable_name,
         }
     }
 }
@@ -153,11 +322,50 @@ fn properties(input: &DeriveInput) -> Vec<Property> {
         if let Fields::Named(fields_named) = &data_struct.fields {
             for field in fields_named.named.iter() {
                 if let Some(variable_name) = &field.ident {
+                    let owner_only = property_owner_only(field);
                     if let Type::Path(type_path) = &field.ty {
                         if let Some(property_seg) = type_path.path.segments.first() {
                             let property_type = property_seg.ident.clone();
-                            if property_type == "EntityProperty" {
-                                fields.push(Property::entity(variable_name.clone()));
+                            if property_type == "EntityProperty"
+                                || property_type == "EntityListProperty"
+                                || property_type == "EntitySetProperty"
+                            {
+                                fields.push(Property::entity(
+                                    variable_name.clone(),
+                                    field.ty.clone(),
+                                    property_type != "EntityProperty",
+                                    owner_only,
+                                ));
+                                continue;
+                            } else if property_type == "ReplicatedVec"
+                                || property_type == "ReplicatedMap"
+                            {
+                                if let PathArguments::AngleBracketed(angle_args) =
+                                    &property_seg.arguments
+                                {
+                                    let args = &angle_args.args;
+                                    let value_type = if property_type == "ReplicatedVec" {
+                                        quote! { Vec<#args> }
+                                    } else {
+                                        quote! { std::collections::HashMap<#args> }
+                                    };
+                                    fields.push(Property::collection(
+                                        variable_name.clone(),
+                                        field.ty.clone(),
+                                        value_type,
+                                        owner_only,
+                                    ));
+                                    continue;
+                                }
+                            } else if let PathArguments::None = &property_seg.arguments {
+                                // a plain struct is a nested Replica
+                                if owner_only {
+                                    panic!("owner_only is not supported on nested structs");
+                                }
+                                fields.push(Property::nested(
+                                    variable_name.clone(),
+                                    field.ty.clone(),
+                                ));
                                 continue;
                             } else if let PathArguments::AngleBracketed(angle_args) =
                                 &property_seg.arguments
@@ -168,6 +376,8 @@ fn properties(input: &DeriveInput) -> Vec<Property> {
                                     fields.push(Property::normal(
                                         variable_name.clone(),
                                         inner_type.clone(),
+                                        property_codec(field),
+                                        owner_only,
                                     ));
                                     continue;
                                 }
@@ -182,6 +392,51 @@ fn properties(input: &DeriveInput) -> Vec<Property> {
     fields
 }
 
+/// Reads the codec given in a `#[property(with = "MyCodec")]` attribute, used
+/// to read & write the Property in place of `Serde`
+fn property_codec(field: &Field) -> Option<Path> {
+    for attr in &field.attrs {
+        if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+            if meta_list.path.is_ident("property") {
+                for nested in meta_list.nested {
+                    if let NestedMeta::Meta(Meta::NameValue(meta_name_value)) = nested {
+                        if meta_name_value.path.is_ident("with") {
+                            if let Lit::Str(lit_str) = &meta_name_value.lit {
+                                let codec: Path = lit_str
+                                    .parse()
+                                    .expect("'with' must be the path of a PropertyCodec");
+                                return Some(codec);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Reads whether the field is marked `#[property(owner_only)]`, in which case
+/// it is only written to the User which owns the Entity
+fn property_owner_only(field: &Field) -> bool {
+    for attr in &field.attrs {
+        if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+            if meta_list.path.is_ident("property") {
+                for nested in meta_list.nested {
+                    if let NestedMeta::Meta(Meta::Path(path)) = nested {
+                        if path.is_ident("owner_only") {
+                            return true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    false
+}
+
 fn protocol_path(input: &DeriveInput) -> (Path, Ident) {
     let mut path_result: Option<Result<Path>> = None;
 
@@ -215,6 +470,105 @@ fn protocol_path(input: &DeriveInput) -> (Path, Ident) {
     panic!("When deriving 'Replicate' you MUST specify the path of the accompanying protocol. IE: '#[protocol_path = \"crate::MyProtocol\"]'");
 }
 
+fn is_nested(input: &DeriveInput) -> bool {
+    replicate_options(input).iter().any(|option| {
+        if let Meta::Path(path) = option {
+            path.is_ident("nested")
+        } else {
+            false
+        }
+    })
+}
+
+/// Reads options given in a `#[replicate(..)]` attribute, such as
+/// `#[replicate(update_ticks = 4)]` or `#[replicate(update_millis = 500)]`
+fn replicate_options(input: &DeriveInput) -> Vec<Meta> {
+    let mut options = Vec::new();
+
+    for attr in &input.attrs {
+        if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+            if meta_list.path.is_ident("replicate") {
+                for nested in meta_list.nested {
+                    if let NestedMeta::Meta(meta) = nested {
+                        options.push(meta);
+                    }
+                }
+            }
+        }
+    }
+
+    options
+}
+
+fn update_rate_method(input: &DeriveInput) -> TokenStream {
+    let mut update_rate = quote! { UpdateRate::Immediate };
+
+    for option in replicate_options(input) {
+        if let Meta::NameValue(meta_name_value) = option {
+            if let Lit::Int(lit_int) = &meta_name_value.lit {
+                if meta_name_value.path.is_ident("update_ticks") {
+                    let ticks: u16 = lit_int
+                        .base10_parse()
+                        .expect("'update_ticks' must be a valid u16");
+                    update_rate = quote! { UpdateRate::Ticks(#ticks) };
+                } else if meta_name_value.path.is_ident("update_millis") {
+                    let millis: u32 = lit_int
+                        .base10_parse()
+                        .expect("'update_millis' must be a valid u32");
+                    update_rate = quote! { UpdateRate::Millis(#millis) };
+                }
+            }
+        }
+    }
+
+    quote! {
+        fn update_rate(&self) -> UpdateRate {
+            #update_rate
+        }
+    }
+}
+
+fn is_static_method(input: &DeriveInput) -> TokenStream {
+    let is_static = replicate_options(input).iter().any(|option| {
+        if let Meta::Path(path) = option {
+            path.is_ident("static")
+        } else {
+            false
+        }
+    });
+
+    quote! {
+        fn is_static(&self) -> bool {
+            #is_static
+        }
+    }
+}
+
+/// Gets the index of each Property's bit in the DiffMask, along with the total
+/// number of bits. A nested struct takes up one bit for each of its own
+/// Properties, which is only known once its `ReplicateNested` impl is compiled
+fn property_offsets(properties: &[Property]) -> (Vec<TokenStream>, TokenStream) {
+    let mut offsets = Vec::new();
+    let mut flat_count: u8 = 0;
+    let mut nested_counts = quote! {};
+
+    for property in properties.iter() {
+        offsets.push(quote! { #flat_count #nested_counts });
+
+        if let Property::Nested(property) = property {
+            let nested_type = &property.nested_type;
+            let new_output_result = quote! {
+                #nested_counts + <#nested_type as ReplicateNested>::PROPERTY_COUNT
+            };
+            nested_counts = new_output_result;
+        } else {
+            flat_count += 1;
+        }
+    }
+
+    (offsets, quote! { #flat_count #nested_counts })
+}
+
 fn property_enum(enum_name: &Ident, properties: &[Property]) -> TokenStream {
     if properties.is_empty() {
         return quote! {
@@ -226,11 +580,13 @@ fn property_enum(enum_name: &Ident, properties: &[Property]) -> TokenStream {
 
     let mut variant_list = quote! {};
 
-    for (index, property) in properties.iter().enumerate() {
+    let (offsets, _) = property_offsets(properties);
+
+    for (property, offset) in properties.iter().zip(offsets.iter()) {
         let uppercase_variant_name = property.uppercase_variable_name();
 
         let new_output_right = quote! {
-            #uppercase_variant_name = #index as u8,
+            #uppercase_variant_name = #offset,
         };
         let new_output_result = quote! {
             #variant_list
@@ -307,6 +663,28 @@ fn clone_method(replica_name: &Ident, properties: &[Property]) -> TokenStream {
                 };
                 entity_property_output = new_output_result;
             }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                let new_output_right = quote! {
+                    (*self.#field_name).clone(),
+                };
+                let new_output_result = quote! {
+                    #output
+                    #new_output_right
+                };
+                output = new_output_result;
+            }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                let new_output_right = quote! {
+                    self.#field_name.clone(),
+                };
+                let new_output_result = quote! {
+                    #output
+                    #new_output_right
+                };
+                output = new_output_result;
+            }
         };
     }
 
@@ -319,11 +697,7 @@ fn clone_method(replica_name: &Ident, properties: &[Property]) -> TokenStream {
     };
 }
 
-fn mirror_method(
-    protocol_name: &Ident,
-    replica_name: &Ident,
-    properties: &[Property],
-) -> TokenStream {
+fn mirror_body(properties: &[Property]) -> TokenStream {
     let mut output = quote! {};
 
     for property in properties.iter() {
@@ -338,6 +712,16 @@ fn mirror_method(
         output = new_output_result;
     }
 
+    output
+}
+
+fn mirror_method(
+    protocol_name: &Ident,
+    replica_name: &Ident,
+    properties: &[Property],
+) -> TokenStream {
+    let output = mirror_body(properties);
+
     return quote! {
         fn mirror(&mut self, other: &#protocol_name) {
             if let #protocol_name::#replica_name(replica) = other {
@@ -347,13 +731,23 @@ fn mirror_method(
     };
 }
 
-fn set_mutator_method(properties: &[Property]) -> TokenStream {
+fn set_mutator_method(enum_name: &Ident, properties: &[Property]) -> TokenStream {
     let mut output = quote! {};
 
     for property in properties.iter() {
         let field_name = property.variable_name();
-        let new_output_right = quote! {
-            self.#field_name.set_mutator(mutator);
+        let new_output_right = match property {
+            Property::Nested(property) => {
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    self.#field_name.set_mutator(&mutator.with_offset(#enum_name::#uppercase_variant_name as u8));
+                }
+            }
+            _ => {
+                quote! {
+                    self.#field_name.set_mutator(mutator);
+                }
+            }
         };
         let new_output_result = quote! {
             #output
@@ -393,6 +787,32 @@ pub fn new_complete_method(
             Property::Entity(_) => {
                 continue;
             }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                let value_type = &property.value_type;
+
+                let new_output_right = quote! {
+                    #field_name: #value_type,
+                };
+
+                let new_output_result = quote! {
+                    #args #new_output_right
+                };
+                args = new_output_result;
+            }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                let nested_type = &property.nested_type;
+
+                let new_output_right = quote! {
+                    #field_name: #nested_type,
+                };
+
+                let new_output_result = quote! {
+                    #args #new_output_right
+                };
+                args = new_output_result;
+            }
         };
     }
 
@@ -409,9 +829,24 @@ pub fn new_complete_method(
             }
             Property::Entity(property) => {
                 let field_name = &property.variable_name;
+                let property_type = &property.property_type;
                 let uppercase_variant_name = &property.uppercase_variable_name;
                 quote! {
-                    #field_name: EntityProperty::new(#enum_name::#uppercase_variant_name as u8)
+                    #field_name: <#property_type>::new(#enum_name::#uppercase_variant_name as u8)
+                }
+            }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                let collection_type = &property.collection_type;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    #field_name: <#collection_type>::new(#field_name, #enum_name::#uppercase_variant_name as u8)
+                }
+            }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    #field_name
                 }
             }
         };
@@ -432,12 +867,9 @@ pub fn new_complete_method(
     };
 }
 
-pub fn read_method(
-    protocol_name: &Ident,
-    replica_name: &Ident,
-    enum_name: &Ident,
-    properties: &[Property],
-) -> TokenStream {
+/// Gets the statements which read every Property into a local variable, along
+/// with the list of those variables
+fn read_body(enum_name: &Ident, properties: &[Property]) -> (TokenStream, TokenStream) {
     let mut prop_names = quote! {};
     for property in properties.iter() {
         let field_name = property.variable_name();
@@ -458,17 +890,55 @@ pub fn read_method(
                 let field_name = &property.variable_name;
                 let field_type = &property.inner_type;
                 let uppercase_variant_name = &property.uppercase_variable_name;
-                quote! {
-                    let #field_name = Property::<#field_type>::new_read(bit_reader, #enum_name::#uppercase_variant_name as u8);
+                if let Some(codec) = &property.codec {
+                    quote! {
+                        let #field_name = Property::<#field_type>::new_read_with::<#codec>(bit_reader, #enum_name::#uppercase_variant_name as u8)?;
+                    }
+                } else {
+                    quote! {
+                        let #field_name = Property::<#field_type>::new_read(bit_reader, #enum_name::#uppercase_variant_name as u8)?;
+                    }
                 }
             }
             Property::Entity(property) => {
                 let field_name = &property.variable_name;
+                let property_type = &property.property_type;
                 let uppercase_variant_name = &property.uppercase_variable_name;
                 quote! {
-                    let #field_name = EntityProperty::new_read(bit_reader, #enum_name::#uppercase_variant_name as u8, converter);
+                    let #field_name = <#property_type>::new_read(bit_reader, #enum_name::#uppercase_variant_name as u8, converter)?;
                 }
             }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                let collection_type = &property.collection_type;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    let #field_name = <#collection_type>::new_read(bit_reader, #enum_name::#uppercase_variant_name as u8)?;
+                }
+            }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                let nested_type = &property.nested_type;
+                quote! {
+                    let #field_name = <#nested_type as ReplicateNested>::read(bit_reader, converter)?;
+                }
+            }
+        };
+
+        // an owner-only Property the User doesn't own is left at its default
+        let new_output_right = if property.owner_only() {
+            let field_name = property.variable_name();
+            let default_value = owner_only_default(enum_name, property);
+            quote! {
+                let #field_name = if bool::de(bit_reader)? {
+                    #new_output_right
+                    #field_name
+                } else {
+                    #default_value
+                };
+            }
+        } else {
+            new_output_right
         };
 
         let new_output_result = quote! {
@@ -478,44 +948,81 @@ pub fn read_method(
         prop_reads = new_output_result;
     }
 
+    (prop_reads, prop_names)
+}
+
+pub fn read_method(
+    protocol_name: &Ident,
+    replica_name: &Ident,
+    enum_name: &Ident,
+    properties: &[Property],
+) -> TokenStream {
+    let (prop_reads, prop_names) = read_body(enum_name, properties);
+
     return quote! {
-        pub fn read(bit_reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) -> #protocol_name {
+        pub fn read(bit_reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) -> Result<#protocol_name, SerdeErr> {
             #prop_reads
 
-            return #protocol_name::#replica_name(#replica_name {
+            return Ok(#protocol_name::#replica_name(#replica_name {
                 #prop_names
-            });
+            }));
         }
     };
 }
 
-pub fn read_create_update_method(
-    replica_name: &Ident,
-    kind_name: &Ident,
-    properties: &[Property],
-) -> TokenStream {
+fn read_write_body(properties: &[Property]) -> TokenStream {
     let mut prop_read_writes = quote! {};
     for property in properties.iter() {
         let new_output_right = match property {
             Property::Normal(property) => {
                 let field_type = &property.inner_type;
+                let read_write = if let Some(codec) = &property.codec {
+                    quote! { Property::<#field_type>::read_write_with::<#codec>(bit_reader, update_writer)?; }
+                } else {
+                    quote! { Property::<#field_type>::read_write(bit_reader, update_writer)?; }
+                };
                 quote! {
                     {
-                        let should_read = bool::de(bit_reader).unwrap();
-                        should_read.ser(&mut update_writer);
+                        let should_read = bool::de(bit_reader)?;
+                        should_read.ser(update_writer);
                         if should_read {
-                            Property::<#field_type>::read_write(bit_reader, &mut update_writer);
+                            #read_write
                         }
                     }
                 }
             }
-            Property::Entity(_) => {
+            Property::Entity(property) => {
+                let property_type = &property.property_type;
+                quote! {
+                    {
+                        let should_read = bool::de(bit_reader)?;
+                        should_read.ser(update_writer);
+                        if should_read {
+                            <#property_type>::read_write(bit_reader, update_writer)?;
+                        }
+                    }
+                }
+            }
+            Property::Collection(property) => {
+                let collection_type = &property.collection_type;
+                quote! {
+                    {
+                        let should_read = bool::de(bit_reader)?;
+                        should_read.ser(update_writer);
+                        if should_read {
+                            <#collection_type>::read_write(bit_reader, update_writer)?;
+                        }
+                    }
+                }
+            }
+            Property::Nested(property) => {
+                let nested_type = &property.nested_type;
                 quote! {
                     {
-                        let should_read = bool::de(bit_reader).unwrap();
-                        should_read.ser(&mut update_writer);
+                        let should_read = bool::de(bit_reader)?;
+                        should_read.ser(update_writer);
                         if should_read {
-                            EntityProperty::read_write(bit_reader, &mut update_writer);
+                            <#nested_type as ReplicateNested>::read_write(bit_reader, update_writer)?;
                         }
                     }
                 }
@@ -529,31 +1036,49 @@ pub fn read_create_update_method(
         prop_read_writes = new_output_result;
     }
 
+    prop_read_writes
+}
+
+pub fn read_create_update_method(
+    replica_name: &Ident,
+    kind_name: &Ident,
+    properties: &[Property],
+) -> TokenStream {
+    let prop_read_writes = read_write_body(properties);
+
     return quote! {
-        pub fn read_create_update(bit_reader: &mut BitReader) -> ComponentUpdate::<#kind_name> {
+        pub fn read_create_update(bit_reader: &mut BitReader) -> Result<ComponentUpdate::<#kind_name>, SerdeErr> {
 
-            let mut update_writer = BitWriter::default();
+            let mut buffer_writer = BitWriter::default();
 
-            #prop_read_writes
+            {
+                let update_writer = &mut buffer_writer;
+                #prop_read_writes
+            }
 
-            let (length, buffer) = update_writer.flush();
+            let (length, buffer) = buffer_writer.flush();
             let owned_reader = OwnedBitReader::new(&buffer[..length]);
 
-            return ComponentUpdate::new(#kind_name::#replica_name, owned_reader);
+            return Ok(ComponentUpdate::new(#kind_name::#replica_name, owned_reader));
         }
     };
 }
 
-fn read_apply_update_method(kind_name: &Ident, properties: &[Property]) -> TokenStream {
+fn read_apply_update_body(properties: &[Property]) -> TokenStream {
     let mut output = quote! {};
 
     for property in properties.iter() {
         let new_output_right = match property {
             Property::Normal(property) => {
                 let field_name = &property.variable_name;
+                let read = if let Some(codec) = &property.codec {
+                    quote! { Property::read_with::<#codec>(&mut self.#field_name, reader); }
+                } else {
+                    quote! { Property::read(&mut self.#field_name, reader); }
+                };
                 quote! {
                     if bool::de(reader).unwrap() {
-                        Property::read(&mut self.#field_name, reader);
+                        #read
                     }
                 }
             }
@@ -561,7 +1086,23 @@ fn read_apply_update_method(kind_name: &Ident, properties: &[Property]) -> Token
                 let field_name = &property.variable_name;
                 quote! {
                     if bool::de(reader).unwrap() {
-                        EntityProperty::read(&mut self.#field_name, reader, converter);
+                        self.#field_name.read(reader, converter);
+                    }
+                }
+            }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    if bool::de(reader).unwrap() {
+                        self.#field_name.read(reader);
+                    }
+                }
+            }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    if bool::de(reader).unwrap() {
+                        ReplicateNested::read_apply_update(&mut self.#field_name, reader, converter);
                     }
                 }
             }
@@ -574,6 +1115,12 @@ fn read_apply_update_method(kind_name: &Ident, properties: &[Property]) -> Token
         output = new_output_result;
     }
 
+    output
+}
+
+fn read_apply_update_method(kind_name: &Ident, properties: &[Property]) -> TokenStream {
+    let output = read_apply_update_body(properties);
+
     return quote! {
         fn read_apply_update(&mut self, converter: &dyn NetEntityHandleConverter, mut update: ComponentUpdate<#kind_name>) {
             let reader = &mut update.reader();
@@ -582,23 +1129,54 @@ fn read_apply_update_method(kind_name: &Ident, properties: &[Property]) -> Token
     };
 }
 
-fn write_method(properties: &[Property]) -> TokenStream {
+fn write_body(properties: &[Property]) -> TokenStream {
     let mut property_writes = quote! {};
 
     for property in properties.iter() {
         let new_output_right = match property {
             Property::Normal(property) => {
                 let field_name = &property.variable_name;
-                quote! {
-                    Property::write(&self.#field_name, bit_writer);
+                if let Some(codec) = &property.codec {
+                    quote! {
+                        Property::write_with::<#codec>(&self.#field_name, bit_writer);
+                    }
+                } else {
+                    quote! {
+                        Property::write(&self.#field_name, bit_writer);
+                    }
                 }
             }
             Property::Entity(property) => {
                 let field_name = &property.variable_name;
                 quote! {
-                    EntityProperty::write(&self.#field_name, bit_writer, converter);
+                    self.#field_name.write(bit_writer, converter);
+                }
+            }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    self.#field_name.write(bit_writer);
+                }
+            }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    ReplicateNested::write(&self.#field_name, bit_writer, converter);
+                }
+            }
+        };
+
+        let new_output_right = if property.owner_only() {
+            quote! {
+                if is_owner {
+                    true.ser(bit_writer);
+                    #new_output_right
+                } else {
+                    false.ser(bit_writer);
                 }
             }
+        } else {
+            new_output_right
         };
 
         let new_output_result = quote! {
@@ -608,15 +1186,25 @@ fn write_method(properties: &[Property]) -> TokenStream {
         property_writes = new_output_result;
     }
 
+    property_writes
+}
+
+fn write_method(properties: &[Property]) -> TokenStream {
+    let property_writes = write_body(properties);
+    let is_owner = is_owner_param(properties);
+
     return quote! {
         fn write(&self, bit_writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter) {
+            self.write_for_user(bit_writer, converter, true);
+        }
+        fn write_for_user(&self, bit_writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter, #is_owner: bool) {
             self.kind().ser(bit_writer);
             #property_writes
         }
     };
 }
 
-fn write_update_method(enum_name: &Ident, properties: &[Property]) -> TokenStream {
+fn write_update_body(enum_name: &Ident, properties: &[Property]) -> TokenStream {
     let mut output = quote! {};
 
     for property in properties.iter() {
@@ -624,10 +1212,15 @@ fn write_update_method(enum_name: &Ident, properties: &[Property]) -> TokenStrea
             Property::Normal(property) => {
                 let field_name = &property.variable_name;
                 let uppercase_variant_name = &property.uppercase_variable_name;
+                let write = if let Some(codec) = &property.codec {
+                    quote! { Property::write_with::<#codec>(&self.#field_name, writer); }
+                } else {
+                    quote! { Property::write(&self.#field_name, writer); }
+                };
                 quote! {
                     if let Some(true) = diff_mask.bit(#enum_name::#uppercase_variant_name as u8) {
                         true.ser(writer);
-                        Property::write(&self.#field_name, writer);
+                        #write
                     } else {
                         false.ser(writer);
                     }
@@ -639,12 +1232,55 @@ fn write_update_method(enum_name: &Ident, properties: &[Property]) -> TokenStrea
                 quote! {
                     if let Some(true) = diff_mask.bit(#enum_name::#uppercase_variant_name as u8) {
                         true.ser(writer);
-                        EntityProperty::write(&self.#field_name, writer, converter);
+                        self.#field_name.write(writer, converter);
+                    } else {
+                        false.ser(writer);
+                    }
+                }
+            }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    if let Some(true) = diff_mask.bit(#enum_name::#uppercase_variant_name as u8) {
+                        true.ser(writer);
+                        self.#field_name.write_update(diff_mask, writer);
                     } else {
                         false.ser(writer);
                     }
                 }
             }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                let nested_type = &property.nested_type;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    {
+                        let nested_diff_mask = diff_mask.sub_mask(
+                            #enum_name::#uppercase_variant_name as u8,
+                            <#nested_type as ReplicateNested>::PROPERTY_COUNT,
+                        );
+                        if !nested_diff_mask.is_clear() {
+                            true.ser(writer);
+                            ReplicateNested::write_update(&self.#field_name, &nested_diff_mask, writer, converter);
+                        } else {
+                            false.ser(writer);
+                        }
+                    }
+                }
+            }
+        };
+
+        let new_output_right = if property.owner_only() {
+            quote! {
+                if is_owner {
+                    #new_output_right
+                } else {
+                    false.ser(writer);
+                }
+            }
+        } else {
+            new_output_right
         };
 
         let new_output_result = quote! {
@@ -654,27 +1290,88 @@ fn write_update_method(enum_name: &Ident, properties: &[Property]) -> TokenStrea
         output = new_output_result;
     }
 
+    output
+}
+
+fn write_update_method(enum_name: &Ident, properties: &[Property]) -> TokenStream {
+    let output = write_update_body(enum_name, properties);
+    let is_owner = is_owner_param(properties);
+
     return quote! {
         fn write_update(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter) {
+            self.write_update_for_user(diff_mask, writer, converter, true);
+        }
+        fn write_update_for_user(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter, #is_owner: bool) {
             #output
         }
     };
 }
 
+// the name of the `is_owner` parameter, which is unused without any owner-only
+// Properties
+fn is_owner_param(properties: &[Property]) -> Ident {
+    if properties.iter().any(|property| property.owner_only()) {
+        format_ident!("is_owner")
+    } else {
+        format_ident!("_is_owner")
+    }
+}
+
+/// Gets the value of an owner-only Property for a User which doesn't own the
+/// Entity
+fn owner_only_default(enum_name: &Ident, property: &Property) -> TokenStream {
+    let uppercase_variant_name = property.uppercase_variable_name();
+    match property {
+        Property::Normal(property) => {
+            let field_type = &property.inner_type;
+            quote! {
+                Property::<#field_type>::new(Default::default(), #enum_name::#uppercase_variant_name as u8)
+            }
+        }
+        Property::Entity(property) => {
+            let property_type = &property.property_type;
+            quote! {
+                <#property_type>::new(#enum_name::#uppercase_variant_name as u8)
+            }
+        }
+        Property::Collection(property) => {
+            let collection_type = &property.collection_type;
+            quote! {
+                <#collection_type>::new(Default::default(), #enum_name::#uppercase_variant_name as u8)
+            }
+        }
+        Property::Nested(_) => {
+            panic!("owner_only is not supported on nested structs");
+        }
+    }
+}
+
 fn has_entity_properties_method(properties: &[Property]) -> TokenStream {
+    let mut nested_output = quote! { false };
+
     for property in properties.iter() {
-        if let Property::Entity(_) = property {
-            return quote! {
-                fn has_entity_properties(&self) -> bool {
-                    return true;
-                }
-            };
+        match property {
+            Property::Entity(_) => {
+                return quote! {
+                    fn has_entity_properties(&self) -> bool {
+                        return true;
+                    }
+                };
+            }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                let new_output_result = quote! {
+                    #nested_output || self.#field_name.has_entity_properties()
+                };
+                nested_output = new_output_result;
+            }
+            Property::Normal(_) | Property::Collection(_) => {}
         }
     }
 
     return quote! {
         fn has_entity_properties(&self) -> bool {
-            return false;
+            return #nested_output;
         }
     };
 }
@@ -683,19 +1380,36 @@ fn entities_method(properties: &[Property]) -> TokenStream {
     let mut body = quote! {};
 
     for property in properties.iter() {
-        if let Property::Entity(entity_prop) = property {
-            let field_name = &entity_prop.variable_name;
-            let body_add_right = quote! {
-                if let Some(handle) = self.#field_name.handle() {
-                    output.push(handle);
+        let body_add_right = match property {
+            Property::Entity(entity_prop) => {
+                let field_name = &entity_prop.variable_name;
+                if entity_prop.is_collection {
+                    quote! {
+                        output.append(&mut self.#field_name.handles());
+                    }
+                } else {
+                    quote! {
+                        if let Some(handle) = self.#field_name.handle() {
+                            output.push(handle);
+                        }
+                    }
                 }
-            };
-            let new_body = quote! {
-                #body
-                #body_add_right
-            };
-            body = new_body;
-        }
+            }
+            Property::Nested(nested_prop) => {
+                let field_name = &nested_prop.variable_name;
+                quote! {
+                    output.append(&mut self.#field_name.entities());
+                }
+            }
+            Property::Normal(_) | Property::Collection(_) => {
+                continue;
+            }
+        };
+        let new_body = quote! {
+            #body
+            #body_add_right
+        };
+        body = new_body;
     }
 
     return quote! {
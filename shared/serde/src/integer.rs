@@ -110,7 +110,7 @@ impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> Serde
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let mut negative: bool = false;
         if SIGNED {
-            negative = reader.read_bit();
+            negative = reader.read_bit()?;
         }
 
         if VARIABLE {
@@ -118,14 +118,17 @@ impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> Serde
             let mut output: u128 = 0;
 
             loop {
-                let proceed = reader.read_bit();
+                let proceed = reader.read_bit()?;
 
                 for _ in 0..BITS {
                     total_bits += 1;
+                    if total_bits > 128 {
+                        return Err(SerdeErr {});
+                    }
 
                     output <<= 1;
 
-                    if reader.read_bit() {
+                    if reader.read_bit()? {
                         output |= 1;
                     }
                 }
@@ -148,7 +151,7 @@ impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> Serde
             for _ in 0..BITS {
                 output <<= 1;
 
-                if reader.read_bit() {
+                if reader.read_bit()? {
                     output |= 1;
                 }
             }
@@ -296,4 +299,22 @@ mod tests {
         assert_eq!(in_2, out_2);
         assert_eq!(in_3, out_3);
     }
+
+    #[test]
+    fn read_overlong_variable() {
+        use crate::reader_writer::BitWrite;
+
+        let mut writer = BitWriter::default();
+
+        // every group says there is another one to follow
+        for _ in 0..30 {
+            writer.write_byte(u8::MAX);
+        }
+
+        let (buffer_length, buffer) = writer.flush();
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        assert!(UnsignedVariableInteger::<3>::de(&mut reader).is_err());
+    }
 }
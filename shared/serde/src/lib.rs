@@ -4,10 +4,12 @@ mod consts;
 mod error;
 mod impls;
 mod integer;
+mod quantized;
 mod reader_writer;
 mod serde;
 
 pub use error::SerdeErr;
 pub use integer::{SignedInteger, SignedVariableInteger, UnsignedInteger, UnsignedVariableInteger};
-pub use reader_writer::{BitCounter, BitReader, BitWrite, BitWriter, OwnedBitReader};
+pub use quantized::{FixedPoint, NormalizedVector, QuantizedFloat, QuantizedQuaternion};
+pub use reader_writer::{BitCounter, BitReader, BitVecWriter, BitWrite, BitWriter, OwnedBitReader};
 pub use serde::Serde;
@@ -0,0 +1,445 @@
+use crate::{
+    error::SerdeErr,
+    integer::{SignedInteger, UnsignedInteger},
+    reader_writer::{BitReader, BitWrite},
+    serde::Serde,
+};
+
+// Maps a value within the given range onto an integer of the given bits.
+// This is done in f64, and the result clamped, as the largest integer of more
+// than 24 bits would round up to the next power of two as an f32
+fn quantize(value: f32, min: f32, max: f32, bits: u8) -> u64 {
+    let max_quantized = (1_u64 << bits) - 1;
+    let (value, min, max) = (value as f64, min as f64, max as f64);
+    let clamped = value.max(min).min(max);
+    let quantized = (((clamped - min) / (max - min)) * max_quantized as f64).round() as u64;
+    quantized.min(max_quantized)
+}
+
+// Maps an integer of the given bits back onto the given range
+fn dequantize(quantized: u64, min: f32, max: f32, bits: u8) -> f32 {
+    let steps = ((1_u64 << bits) - 1) as f64;
+    (min as f64 + (quantized as f64 / steps) * (max as f64 - min as f64)) as f32
+}
+
+// QuantizedFloat
+
+/// A float within the range `MIN..=MAX`, written with `BITS` bits. Values
+/// outside the range are clamped. The largest error after a round trip is
+/// half of `(MAX - MIN) / (2^BITS - 1)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuantizedFloat<const MIN: i32, const MAX: i32, const BITS: u8> {
+    quantized: u64,
+}
+
+impl<const MIN: i32, const MAX: i32, const BITS: u8> QuantizedFloat<MIN, MAX, BITS> {
+    pub fn new(value: f32) -> Self {
+        if MIN >= MAX {
+            panic!("a QuantizedFloat's MIN must be less than its MAX");
+        }
+        if BITS == 0 || BITS > 32 {
+            panic!("a QuantizedFloat must have between 1 and 32 bits");
+        }
+
+        Self {
+            quantized: quantize(value, MIN as f32, MAX as f32, BITS),
+        }
+    }
+
+    pub fn get(&self) -> f32 {
+        dequantize(self.quantized, MIN as f32, MAX as f32, BITS)
+    }
+}
+
+impl<const MIN: i32, const MAX: i32, const BITS: u8> Serde for QuantizedFloat<MIN, MAX, BITS> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        UnsignedInteger::<BITS>::new(self.quantized).ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let quantized = UnsignedInteger::<BITS>::de(reader)?.get() as u64;
+        Ok(Self { quantized })
+    }
+}
+
+// FixedPoint
+
+/// A signed float with `FRACTION_BITS` bits after the binary point, written
+/// with a sign bit and `BITS` bits of magnitude. Values can be no larger than
+/// `2^(BITS - FRACTION_BITS)` and are clamped. The largest error after a round
+/// trip is `2^-(FRACTION_BITS + 1)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedPoint<const BITS: u8, const FRACTION_BITS: u8> {
+    fixed: i64,
+}
+
+impl<const BITS: u8, const FRACTION_BITS: u8> FixedPoint<BITS, FRACTION_BITS> {
+    pub fn new(value: f32) -> Self {
+        if FRACTION_BITS > BITS {
+            panic!("a FixedPoint can't have more FRACTION_BITS than BITS");
+        }
+        if BITS == 0 || BITS > 62 {
+            panic!("a FixedPoint must have between 1 and 62 bits");
+        }
+
+        // the cast saturates, so the clamp is done on integers, where the
+        // limit is exact
+        let limit = (1_i64 << BITS) - 1;
+        let scaled = (value as f64 * (1_i64 << FRACTION_BITS) as f64).round() as i64;
+        Self {
+            fixed: scaled.clamp(-limit, limit),
+        }
+    }
+
+    pub fn get(&self) -> f32 {
+        (self.fixed as f64 / (1_i64 << FRACTION_BITS) as f64) as f32
+    }
+}
+
+impl<const BITS: u8, const FRACTION_BITS: u8> Serde for FixedPoint<BITS, FRACTION_BITS> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        SignedInteger::<BITS>::new(self.fixed).ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let fixed = SignedInteger::<BITS>::de(reader)?.get() as i64;
+        Ok(Self { fixed })
+    }
+}
+
+// NormalizedVector
+
+/// A 3D vector of length 1, such as a direction, written with `BITS` bits for
+/// each of two components using an octahedral encoding
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalizedVector<const BITS: u8> {
+    u: u64,
+    v: u64,
+}
+
+impl<const BITS: u8> NormalizedVector<BITS> {
+    /// Creates a NormalizedVector pointing in the direction of the given
+    /// vector, which must not be of length 0
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        if BITS == 0 || BITS > 32 {
+            panic!("a NormalizedVector must have between 1 and 32 bits");
+        }
+
+        // project onto the octahedron |x| + |y| + |z| = 1, then fold the
+        // lower half over the upper half
+        let length = x.abs() + y.abs() + z.abs();
+        let (mut u, mut v) = (x / length, y / length);
+        if z < 0.0 {
+            let folded_u = (1.0 - v.abs()) * u.signum();
+            let folded_v = (1.0 - u.abs()) * v.signum();
+            u = folded_u;
+            v = folded_v;
+        }
+
+        Self {
+            u: quantize(u, -1.0, 1.0, BITS),
+            v: quantize(v, -1.0, 1.0, BITS),
+        }
+    }
+
+    /// Gets the x, y & z components of the vector
+    pub fn get(&self) -> (f32, f32, f32) {
+        let u = dequantize(self.u, -1.0, 1.0, BITS);
+        let v = dequantize(self.v, -1.0, 1.0, BITS);
+
+        let z = 1.0 - u.abs() - v.abs();
+        let (x, y) = if z < 0.0 {
+            ((1.0 - v.abs()) * u.signum(), (1.0 - u.abs()) * v.signum())
+        } else {
+            (u, v)
+        };
+
+        let length = (x * x + y * y + z * z).sqrt();
+        (x / length, y / length, z / length)
+    }
+}
+
+impl<const BITS: u8> Serde for NormalizedVector<BITS> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        UnsignedInteger::<BITS>::new(self.u).ser(writer);
+        UnsignedInteger::<BITS>::new(self.v).ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let u = UnsignedInteger::<BITS>::de(reader)?.get() as u64;
+        let v = UnsignedInteger::<BITS>::de(reader)?.get() as u64;
+        Ok(Self { u, v })
+    }
+}
+
+// QuantizedQuaternion
+
+const SMALLEST_THREE_LIMIT: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// A rotation, written with the "smallest three" encoding: 2 bits for which
+/// component is largest, and `BITS` bits for each of the other three. The
+/// largest is rebuilt from the others, as the quaternion is of length 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuantizedQuaternion<const BITS: u8> {
+    largest_index: u8,
+    smallest_three: [u64; 3],
+}
+
+impl<const BITS: u8> QuantizedQuaternion<BITS> {
+    /// Creates a QuantizedQuaternion from the x, y, z & w components of a
+    /// quaternion, which is normalized first
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        if BITS == 0 || BITS > 32 {
+            panic!("a QuantizedQuaternion must have between 1 and 32 bits");
+        }
+
+        let length = (x * x + y * y + z * z + w * w).sqrt();
+        let mut components = [x / length, y / length, z / length, w / length];
+
+        let mut largest_index = 0;
+        for index in 1..4 {
+            if components[index].abs() > components[largest_index].abs() {
+                largest_index = index;
+            }
+        }
+
+        // a quaternion & its negation are the same rotation, so the largest
+        // component can always be made positive and needn't carry a sign
+        if components[largest_index] < 0.0 {
+            for component in components.iter_mut() {
+                *component = -*component;
+            }
+        }
+
+        let mut smallest_three = [0; 3];
+        let mut next = 0;
+        for (index, component) in components.iter().enumerate() {
+            if index != largest_index {
+                smallest_three[next] = quantize(
+                    *component,
+                    -SMALLEST_THREE_LIMIT,
+                    SMALLEST_THREE_LIMIT,
+                    BITS,
+                );
+                next += 1;
+            }
+        }
+
+        Self {
+            largest_index: largest_index as u8,
+            smallest_three,
+        }
+    }
+
+    /// Gets the x, y, z & w components of the quaternion
+    pub fn get(&self) -> (f32, f32, f32, f32) {
+        let mut components = [0.0; 4];
+        let mut sum_of_squares = 0.0;
+        let mut next = 0;
+        for (index, component) in components.iter_mut().enumerate() {
+            if index != self.largest_index as usize {
+                *component = dequantize(
+                    self.smallest_three[next],
+                    -SMALLEST_THREE_LIMIT,
+                    SMALLEST_THREE_LIMIT,
+                    BITS,
+                );
+                sum_of_squares += *component * *component;
+                next += 1;
+            }
+        }
+        components[self.largest_index as usize] = (1.0 - sum_of_squares).max(0.0).sqrt();
+
+        (components[0], components[1], components[2], components[3])
+    }
+}
+
+impl<const BITS: u8> Serde for QuantizedQuaternion<BITS> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        UnsignedInteger::<2>::new(self.largest_index).ser(writer);
+        for component in self.smallest_three {
+            UnsignedInteger::<BITS>::new(component).ser(writer);
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let largest_index = UnsignedInteger::<2>::de(reader)?.get() as u8;
+        let mut smallest_three = [0; 3];
+        for component in smallest_three.iter_mut() {
+            *component = UnsignedInteger::<BITS>::de(reader)?.get() as u64;
+        }
+        Ok(Self {
+            largest_index,
+            smallest_three,
+        })
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        quantized::{FixedPoint, NormalizedVector, QuantizedFloat, QuantizedQuaternion},
+        reader_writer::{BitReader, BitWrite, BitWriter},
+        serde::Serde,
+    };
+
+    fn round_trip<T: Serde>(value: &T) -> (T, u16) {
+        let mut writer = BitWriter::default();
+        value.ser(&mut writer);
+        let bit_count = writer.bit_count();
+        let (buffer_length, buffer) = writer.flush();
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+        (T::de(&mut reader).unwrap(), bit_count)
+    }
+
+    #[test]
+    fn quantized_float_precision() {
+        type Position = QuantizedFloat<-512, 512, 16>;
+        let max_error = (1024.0 / 65535.0) / 2.0;
+
+        for step in 0..=1000 {
+            let value = -512.0 + step as f32 * 1.0237;
+            let (out, bit_count) = round_trip(&Position::new(value));
+
+            assert_eq!(bit_count, 16);
+            assert!((out.get() - value).abs() <= max_error + f32::EPSILON * 512.0);
+        }
+    }
+
+    #[test]
+    fn quantized_float_clamps() {
+        type Percent = QuantizedFloat<0, 1, 7>;
+
+        assert_eq!(round_trip(&Percent::new(-3.0)).0.get(), 0.0);
+        assert_eq!(round_trip(&Percent::new(1.5)).0.get(), 1.0);
+    }
+
+    #[test]
+    fn fixed_point_precision() {
+        type Height = FixedPoint<20, 8>;
+        let max_error = 1.0 / 512.0;
+
+        for step in 0..=1000 {
+            let value = -2000.0 + step as f32 * 3.917;
+            let (out, bit_count) = round_trip(&Height::new(value));
+
+            assert_eq!(bit_count, 21);
+            assert!((out.get() - value).abs() <= max_error);
+        }
+    }
+
+    #[test]
+    fn fixed_point_clamps() {
+        type Small = FixedPoint<4, 2>;
+
+        assert_eq!(round_trip(&Small::new(100.0)).0.get(), 3.75);
+        assert_eq!(round_trip(&Small::new(-100.0)).0.get(), -3.75);
+    }
+
+    #[test]
+    fn quantized_float_wide_limits() {
+        fn check<const BITS: u8>() {
+            let min = round_trip(&QuantizedFloat::<-1000, 1000, BITS>::new(-1000.0));
+            let max = round_trip(&QuantizedFloat::<-1000, 1000, BITS>::new(1000.0));
+            assert_eq!(min.0.get(), -1000.0);
+            assert_eq!(max.0.get(), 1000.0);
+            assert_eq!(max.1, BITS as u16);
+
+            let percent = round_trip(&QuantizedFloat::<0, 1, BITS>::new(1.0)).0;
+            assert_eq!(percent.get(), 1.0);
+        }
+
+        check::<24>();
+        check::<25>();
+        check::<32>();
+    }
+
+    #[test]
+    fn fixed_point_wide_limits() {
+        fn check<const BITS: u8, const FRACTION_BITS: u8>() {
+            let limit = ((1_i64 << BITS) - 1) as f64 / (1_i64 << FRACTION_BITS) as f64;
+            for value in [f32::MAX, 1e30, -1e30, f32::MIN] {
+                let fixed = FixedPoint::<BITS, FRACTION_BITS>::new(value);
+                let (out, _) = round_trip(&fixed);
+                assert_eq!(out, fixed);
+                assert_eq!(out.get(), (limit * value.signum() as f64) as f32);
+            }
+        }
+
+        check::<24, 0>();
+        check::<25, 4>();
+        check::<32, 8>();
+        check::<62, 0>();
+    }
+
+    #[test]
+    fn normalized_vector_wide_limits() {
+        for (x, y, z) in [(1.0, 0.0, 0.0), (-1.0, 0.0, 0.0), (0.0, 1.0, 0.0)] {
+            let (out, _) = round_trip(&NormalizedVector::<28>::new(x, y, z));
+            let (out_x, out_y, out_z) = out.get();
+            assert!((out_x - x).abs() < 0.0001);
+            assert!((out_y - y).abs() < 0.0001);
+            assert!((out_z - z).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn normalized_vector_precision() {
+        // for the octahedral encoding, each component is within a couple of
+        // quantization steps of the original
+        let max_error = 2.0 * (2.0 / 1023.0);
+
+        for step in 0..500 {
+            let angle_a = step as f32 * 0.137;
+            let angle_b = step as f32 * 0.0611;
+            let x = angle_a.cos() * angle_b.sin();
+            let y = angle_a.sin() * angle_b.sin();
+            let z = angle_b.cos();
+
+            let (out, bit_count) = round_trip(&NormalizedVector::<10>::new(x, y, z));
+            let (out_x, out_y, out_z) = out.get();
+
+            assert_eq!(bit_count, 20);
+            assert!((out_x * out_x + out_y * out_y + out_z * out_z - 1.0).abs() < 0.0001);
+            assert!((out_x - x).abs() <= max_error);
+            assert!((out_y - y).abs() <= max_error);
+            assert!((out_z - z).abs() <= max_error);
+        }
+    }
+
+    #[test]
+    fn quaternion_precision() {
+        let max_error = std::f32::consts::SQRT_2 / 1023.0;
+
+        for step in 0..500 {
+            let angle = step as f32 * 0.0251;
+            let (sin, cos) = (angle / 2.0).sin_cos();
+            let axis_length = (1.0_f32 + 4.0 + 9.0).sqrt();
+            let (x, y, z, w) = (
+                sin * 1.0 / axis_length,
+                sin * -2.0 / axis_length,
+                sin * 3.0 / axis_length,
+                cos,
+            );
+
+            let (out, bit_count) = round_trip(&QuantizedQuaternion::<10>::new(x, y, z, w));
+            let (out_x, out_y, out_z, out_w) = out.get();
+
+            assert_eq!(bit_count, 32);
+
+            // the decoded quaternion may be negated, which is the same rotation
+            let sign = if x * out_x + y * out_y + z * out_z + w * out_w < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            assert!((sign * out_x - x).abs() <= max_error);
+            assert!((sign * out_y - y).abs() <= max_error);
+            assert!((sign * out_z - z).abs() <= max_error);
+            assert!((sign * out_w - w).abs() <= max_error);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::consts::MAX_BUFFER_SIZE;
+use crate::{consts::MAX_BUFFER_SIZE, error::SerdeErr};
 
 // BitWrite
 
@@ -9,9 +9,18 @@ pub trait BitWrite {
 }
 
 // BitCounter
+
+/// Counts the bits written to it, without keeping them. `bit_count` stops
+/// counting at `u16::MAX`, use `byte_count` for the full length.
 #[derive(Default)]
 pub struct BitCounter {
-    count: u16,
+    count: usize,
+}
+
+impl BitCounter {
+    pub fn byte_count(&self) -> usize {
+        self.count.div_ceil(8)
+    }
 }
 
 impl BitWrite for BitCounter {
@@ -24,7 +33,7 @@ impl BitWrite for BitCounter {
     }
 
     fn bit_count(&self) -> u16 {
-        self.count
+        self.count.min(u16::MAX as usize) as u16
     }
 }
 
@@ -101,6 +110,69 @@ impl BitWrite for BitWriter {
     }
 }
 
+// BitVecWriter
+
+/// A BitWriter which grows as it is written to, for data which is not sent
+/// in a single packet. `bit_count` stops counting at `u16::MAX`, use
+/// `byte_count` for the full length.
+#[derive(Default)]
+pub struct BitVecWriter {
+    scratch: u8,
+    scratch_index: u8,
+    buffer: Vec<u8>,
+}
+
+impl BitVecWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn byte_count(&self) -> usize {
+        self.buffer.len() + usize::from(self.scratch_index > 0)
+    }
+
+    pub fn to_bytes(mut self) -> Vec<u8> {
+        if self.scratch_index > 0 {
+            self.buffer
+                .push((self.scratch << (8 - self.scratch_index)).reverse_bits());
+        }
+
+        self.buffer
+    }
+}
+
+impl BitWrite for BitVecWriter {
+    fn write_bit(&mut self, bit: bool) {
+        self.scratch <<= 1;
+
+        if bit {
+            self.scratch |= 1;
+        }
+
+        self.scratch_index += 1;
+
+        if self.scratch_index >= 8 {
+            self.buffer.push(self.scratch.reverse_bits());
+
+            self.scratch_index -= 8;
+            self.scratch = 0;
+        }
+    }
+
+    fn write_byte(&mut self, byte: u8) {
+        let mut temp = byte;
+        for _ in 0..8 {
+            self.write_bit(temp & 1 != 0);
+            temp >>= 1;
+        }
+    }
+
+    fn bit_count(&self) -> u16 {
+        let bit_count = (self.buffer.len() * 8) + (self.scratch_index as usize);
+        bit_count.min(u16::MAX as usize) as u16
+    }
+}
+
 // BitReader
 
 pub struct BitReader<'b> {
@@ -127,10 +199,16 @@ impl<'b> BitReader<'b> {
         }
     }
 
-    pub(crate) fn read_bit(&mut self) -> bool {
+    /// Returns the number of bits which have not been read yet, including
+    /// any padding at the end of the buffer
+    pub fn bits_remaining(&self) -> usize {
+        ((self.buffer.len() - self.state.buffer_index) * 8) + (self.state.scratch_index as usize)
+    }
+
+    pub(crate) fn read_bit(&mut self) -> Result<bool, SerdeErr> {
         if self.state.scratch_index == 0 {
             if self.state.buffer_index == self.buffer.len() {
-                panic!("no more bytes to read");
+                return Err(SerdeErr {});
             }
 
             self.state.scratch = self.buffer[self.state.buffer_index];
@@ -145,21 +223,21 @@ impl<'b> BitReader<'b> {
 
         self.state.scratch_index -= 1;
 
-        value != 0
+        Ok(value != 0)
     }
 
-    pub(crate) fn read_byte(&mut self) -> u8 {
+    pub(crate) fn read_byte(&mut self) -> Result<u8, SerdeErr> {
         let mut output = 0;
         for _ in 0..7 {
-            if self.read_bit() {
+            if self.read_bit()? {
                 output |= 128;
             }
             output >>= 1;
         }
-        if self.read_bit() {
+        if self.read_bit()? {
             output |= 128;
         }
-        output
+        Ok(output)
     }
 }
 
@@ -212,7 +290,7 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert!(reader.read_bit());
+        assert!(reader.read_bit().unwrap());
     }
 
     #[test]
@@ -229,9 +307,9 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(reader.read_bit());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
     }
 
     #[test]
@@ -254,15 +332,15 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
 
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
     }
 
     #[test]
@@ -292,22 +370,22 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
 
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
 
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(reader.read_bit());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
 
-        assert!(reader.read_bit());
+        assert!(reader.read_bit().unwrap());
     }
 
     #[test]
@@ -340,25 +418,25 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
-
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(reader.read_bit());
-
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(reader.read_bit());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
     }
 
     #[test]
@@ -373,7 +451,7 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert_eq!(123, reader.read_byte());
+        assert_eq!(123, reader.read_byte().unwrap());
     }
 
     #[test]
@@ -392,10 +470,73 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert_eq!(48, reader.read_byte());
-        assert_eq!(151, reader.read_byte());
-        assert_eq!(62, reader.read_byte());
-        assert_eq!(34, reader.read_byte());
-        assert_eq!(2, reader.read_byte());
+        assert_eq!(48, reader.read_byte().unwrap());
+        assert_eq!(151, reader.read_byte().unwrap());
+        assert_eq!(62, reader.read_byte().unwrap());
+        assert_eq!(34, reader.read_byte().unwrap());
+        assert_eq!(2, reader.read_byte().unwrap());
+    }
+
+    #[test]
+    fn read_write_past_max_buffer_size() {
+        use crate::{
+            consts::MAX_BUFFER_SIZE,
+            reader_writer::{BitReader, BitVecWriter, BitWrite},
+        };
+
+        let mut writer = BitVecWriter::new();
+
+        writer.write_bit(true);
+        for index in 0..(MAX_BUFFER_SIZE * 2) {
+            writer.write_byte(index as u8);
+        }
+
+        assert_eq!(writer.byte_count(), (MAX_BUFFER_SIZE * 2) + 1);
+
+        let buffer = writer.to_bytes();
+
+        let mut reader = BitReader::new(&buffer);
+
+        assert!(reader.read_bit().unwrap());
+        for index in 0..(MAX_BUFFER_SIZE * 2) {
+            assert_eq!(index as u8, reader.read_byte().unwrap());
+        }
+    }
+
+    #[test]
+    fn read_bits_remaining() {
+        use crate::reader_writer::{BitReader, BitWrite, BitWriter};
+
+        let mut writer = BitWriter::default();
+
+        writer.write_bit(true);
+        writer.write_byte(123);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        assert_eq!(reader.bits_remaining(), 16);
+        assert!(reader.read_bit().unwrap());
+        assert_eq!(reader.bits_remaining(), 15);
+        assert_eq!(123, reader.read_byte().unwrap());
+        assert_eq!(reader.bits_remaining(), 7);
+    }
+
+    #[test]
+    fn read_past_end() {
+        use crate::reader_writer::{BitReader, BitWrite, BitWriter};
+
+        let mut writer = BitWriter::default();
+
+        writer.write_byte(123);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        assert_eq!(123, reader.read_byte().unwrap());
+        assert!(reader.read_bit().is_err());
+        assert!(reader.read_byte().is_err());
     }
 }
@@ -0,0 +1,69 @@
+/// Identifies the contents of a blob, so that a Client which already has a
+/// copy can skip downloading it again
+pub type BlobHash = u64;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Computes a `BlobHash` over data which arrives a piece at a time. Uses
+/// 64-bit FNV-1a, which gives the same result on every platform, but is not
+/// meant to hold up against deliberately colliding data
+pub struct BlobHasher {
+    hash: u64,
+}
+
+impl Default for BlobHasher {
+    fn default() -> Self {
+        Self {
+            hash: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl BlobHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn finish(&self) -> BlobHash {
+        self.hash
+    }
+}
+
+/// Computes the `BlobHash` of a whole blob
+pub fn blob_hash(bytes: &[u8]) -> BlobHash {
+    let mut hasher = BlobHasher::new();
+    hasher.update(bytes);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{blob_hash, BlobHasher};
+
+    #[test]
+    fn known_values() {
+        assert_eq!(blob_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(blob_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(blob_hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn incremental_matches_whole() {
+        let bytes: Vec<u8> = (0..1000).map(|i| (i % 256) as u8).collect();
+
+        let mut hasher = BlobHasher::new();
+        for chunk in bytes.chunks(77) {
+            hasher.update(chunk);
+        }
+
+        assert_eq!(hasher.finish(), blob_hash(&bytes));
+    }
+}
@@ -8,8 +8,8 @@ use crate::{
 };
 
 use super::{
-    packet_notifiable::PacketNotifiable, packet_type::PacketType, sequence_buffer::SequenceBuffer,
-    standard_header::StandardHeader,
+    packet_loss_monitor::PacketLossMonitor, packet_notifiable::PacketNotifiable,
+    packet_type::PacketType, sequence_buffer::SequenceBuffer, standard_header::StandardHeader,
 };
 
 pub const REDUNDANT_PACKET_ACKS_SIZE: u16 = 32;
@@ -28,6 +28,8 @@ pub struct AckManager {
     // However, we can only reasonably ack up to `REDUNDANT_PACKET_ACKS_SIZE + 1` packets on each
     // message we send so this should be that large.
     received_packets: SequenceBuffer<ReceivedPacket>,
+    // Whether each of the most recently acknowledged packets was delivered or dropped
+    packet_loss_monitor: PacketLossMonitor,
 }
 
 impl Default for AckManager {
@@ -37,6 +39,7 @@ impl Default for AckManager {
             last_recv_packet_index: u16::MAX,
             sent_packets: HashMap::with_capacity(DEFAULT_SEND_PACKETS_SIZE),
             received_packets: SequenceBuffer::with_capacity(REDUNDANT_PACKET_ACKS_SIZE + 1),
+            packet_loss_monitor: PacketLossMonitor::default(),
         }
     }
 }
@@ -47,13 +50,19 @@ impl AckManager {
         self.next_packet_index
     }
 
+    /// Gets the fraction of recently sent packets which were dropped, between
+    /// 0.0 and 1.0
+    pub fn packet_loss(&self) -> f32 {
+        self.packet_loss_monitor.packet_loss()
+    }
+
     /// Process an incoming packet, handle notifications of delivered / dropped
     /// packets
     pub fn process_incoming_header<P: Protocolize, C: ChannelIndex>(
         &mut self,
         header: &StandardHeader,
         message_manager: &mut MessageManager<P, C>,
-        packet_notifiable: &mut Option<&mut dyn PacketNotifiable>,
+        packet_notifiables: &mut [&mut dyn PacketNotifiable],
     ) {
         let sender_packet_index = header.sender_packet_index;
         let sender_ack_index = header.sender_ack_index;
@@ -71,9 +80,10 @@ impl AckManager {
         // the current `sender_ack_index` was (clearly) received so we should remove it
         if let Some(sent_packet) = self.sent_packets.get(&sender_ack_index) {
             if sent_packet.packet_type == PacketType::Data {
-                self.notify_packet_delivered(sender_ack_index, message_manager, packet_notifiable);
+                self.notify_packet_delivered(sender_ack_index, message_manager, packet_notifiables);
             }
 
+            self.packet_loss_monitor.record_delivered();
             self.sent_packets.remove(&sender_ack_index);
         }
 
@@ -88,12 +98,14 @@ impl AckManager {
                         self.notify_packet_delivered(
                             sent_packet_index,
                             message_manager,
-                            packet_notifiable,
+                            packet_notifiables,
                         );
                     }
 
+                    self.packet_loss_monitor.record_delivered();
                     self.sent_packets.remove(&sent_packet_index);
                 } else {
+                    self.packet_loss_monitor.record_dropped();
                     self.sent_packets.remove(&sent_packet_index);
                 }
             }
@@ -133,10 +145,10 @@ impl AckManager {
         &self,
         sent_packet_index: PacketIndex,
         message_manager: &mut MessageManager<P, C>,
-        packet_notifiable: &mut Option<&mut dyn PacketNotifiable>,
+        packet_notifiables: &mut [&mut dyn PacketNotifiable],
     ) {
         message_manager.notify_packet_delivered(sent_packet_index);
-        if let Some(notifiable) = packet_notifiable {
+        for notifiable in packet_notifiables.iter_mut() {
             notifiable.notify_packet_delivered(sent_packet_index);
         }
     }
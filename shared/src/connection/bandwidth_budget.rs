@@ -0,0 +1,92 @@
+use naia_socket_shared::Instant;
+
+use super::bandwidth_budget_config::BandwidthBudgetConfig;
+
+/// A token bucket which limits the number of bytes that can be sent to a
+/// remote host. Bytes are added to the budget at a constant rate, and spent
+/// whenever a packet is sent. A packet may be sent as long as any budget
+/// remains, so the budget can briefly go negative by up to one packet, which
+/// is then paid off before anything else is sent.
+pub struct BandwidthBudget {
+    bytes_per_second: f32,
+    max_bytes: f32,
+    available_bytes: f32,
+    last_refill: Instant,
+}
+
+impl BandwidthBudget {
+    pub fn new(config: &BandwidthBudgetConfig) -> Self {
+        BandwidthBudget {
+            bytes_per_second: config.bytes_per_second as f32,
+            max_bytes: config.max_burst_bytes as f32,
+            available_bytes: config.max_burst_bytes as f32,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Adds to the budget all bytes accumulated since the last refill
+    pub fn refill(&mut self) {
+        let elapsed_secs = self.last_refill.elapsed().as_secs_f32();
+        self.last_refill = Instant::now();
+
+        self.available_bytes =
+            (self.available_bytes + elapsed_secs * self.bytes_per_second).min(self.max_bytes);
+    }
+
+    /// Returns whether there is any budget left to send another packet with
+    pub fn can_send(&self) -> bool {
+        self.available_bytes > 0.0
+    }
+
+    /// Removes the size of a sent packet from the budget
+    pub fn spend(&mut self, bytes: usize) {
+        self.available_bytes -= bytes as f32;
+    }
+
+    /// Changes the rate at which bytes are added to the budget
+    pub fn set_bytes_per_second(&mut self, bytes_per_second: u32) {
+        self.bytes_per_second = bytes_per_second as f32;
+    }
+
+    /// Gets the number of bytes which can currently be sent
+    pub fn available_bytes(&self) -> u32 {
+        self.available_bytes.max(0.0) as u32
+    }
+}
+
+#[cfg(test)]
+mod bandwidth_budget_tests {
+    use super::*;
+
+    #[test]
+    fn starts_full() {
+        let budget = BandwidthBudget::new(&BandwidthBudgetConfig::new(1000, 500));
+
+        assert!(budget.can_send());
+        assert_eq!(budget.available_bytes(), 500);
+    }
+
+    #[test]
+    fn spending_beyond_budget_blocks_sending() {
+        let mut budget = BandwidthBudget::new(&BandwidthBudgetConfig::new(1000, 500));
+
+        budget.spend(300);
+        assert!(budget.can_send());
+        assert_eq!(budget.available_bytes(), 200);
+
+        budget.spend(300);
+        assert!(!budget.can_send());
+        assert_eq!(budget.available_bytes(), 0);
+    }
+
+    #[test]
+    fn refill_is_capped() {
+        let mut budget = BandwidthBudget::new(&BandwidthBudgetConfig::new(u32::MAX, 500));
+
+        budget.spend(400);
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        budget.refill();
+
+        assert_eq!(budget.available_bytes(), 500);
+    }
+}
@@ -0,0 +1,32 @@
+use std::default::Default;
+
+/// Contains Config properties which will be used to limit the rate at which
+/// packets are sent to a remote host
+#[derive(Clone, Debug)]
+pub struct BandwidthBudgetConfig {
+    /// The number of bytes which are added to the budget every second
+    pub bytes_per_second: u32,
+    /// The maximum number of bytes the budget can hold. Bytes left unspent
+    /// will accumulate up to this amount, allowing short bursts above the
+    /// average rate
+    pub max_burst_bytes: u32,
+}
+
+impl BandwidthBudgetConfig {
+    /// Creates a new BandwidthBudgetConfig, used to limit outgoing bandwidth
+    pub fn new(bytes_per_second: u32, max_burst_bytes: u32) -> Self {
+        BandwidthBudgetConfig {
+            bytes_per_second,
+            max_burst_bytes,
+        }
+    }
+}
+
+impl Default for BandwidthBudgetConfig {
+    fn default() -> Self {
+        Self {
+            bytes_per_second: 64_000,
+            max_burst_bytes: 16_000,
+        }
+    }
+}
@@ -79,12 +79,12 @@ impl<P: Protocolize, C: ChannelIndex> BaseConnection<P, C> {
     pub fn process_incoming_header(
         &mut self,
         header: &StandardHeader,
-        packet_notifiable: &mut Option<&mut dyn PacketNotifiable>,
+        packet_notifiables: &mut [&mut dyn PacketNotifiable],
     ) {
         self.ack_manager.process_incoming_header(
             header,
             &mut self.message_manager,
-            packet_notifiable,
+            packet_notifiables,
         );
     }
 
@@ -98,6 +98,12 @@ impl<P: Protocolize, C: ChannelIndex> BaseConnection<P, C> {
             .ser(writer);
     }
 
+    /// Gets the fraction of recently sent packets which were dropped, between
+    /// 0.0 and 1.0
+    pub fn packet_loss(&self) -> f32 {
+        self.ack_manager.packet_loss()
+    }
+
     /// Get the next outgoing packet's index
     pub fn next_packet_index(&self) -> PacketIndex {
         self.ack_manager.next_sender_packet_index()
@@ -0,0 +1,60 @@
+use std::{default::Default, time::Duration};
+
+/// Contains Config properties which will be used to detect congestion on a
+/// connection, and to back off the rate at which packets are sent while it
+/// lasts
+#[derive(Clone, Debug)]
+pub struct CongestionConfig {
+    /// Round trip time (plus jitter) above which the connection is considered
+    /// congested
+    pub rtt_threshold: Duration,
+    /// Fraction of recently sent packets which must be dropped for the
+    /// connection to be considered congested, between 0.0 and 1.0
+    pub packet_loss_threshold: f32,
+    /// The duration for which the connection must stay below both thresholds
+    /// before it is no longer considered congested
+    pub recovery_duration: Duration,
+    /// Factor the send rate is multiplied by each round trip while the
+    /// connection is congested
+    pub rate_decrease_factor: f32,
+    /// Number of bytes per second the send rate grows by every second while
+    /// the connection is not congested
+    pub rate_increase_per_second: u32,
+    /// The send rate will never be lowered below this many bytes per second
+    pub min_bytes_per_second: u32,
+}
+
+impl CongestionConfig {
+    /// Creates a new CongestionConfig, used to adapt the send rate of a
+    /// connection to network conditions
+    pub fn new(
+        rtt_threshold: Duration,
+        packet_loss_threshold: f32,
+        recovery_duration: Duration,
+        rate_decrease_factor: f32,
+        rate_increase_per_second: u32,
+        min_bytes_per_second: u32,
+    ) -> Self {
+        CongestionConfig {
+            rtt_threshold,
+            packet_loss_threshold,
+            recovery_duration,
+            rate_decrease_factor,
+            rate_increase_per_second,
+            min_bytes_per_second,
+        }
+    }
+}
+
+impl Default for CongestionConfig {
+    fn default() -> Self {
+        Self {
+            rtt_threshold: Duration::from_millis(250),
+            packet_loss_threshold: 0.1,
+            recovery_duration: Duration::from_secs(5),
+            rate_decrease_factor: 0.5,
+            rate_increase_per_second: 4_000,
+            min_bytes_per_second: 4_000,
+        }
+    }
+}
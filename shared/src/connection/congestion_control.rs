@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use naia_socket_shared::Instant;
+
+use super::congestion_config::CongestionConfig;
+
+/// Detects congestion on a connection from its round trip time and packet
+/// loss, and adapts the rate at which bytes are sent to it. The rate follows
+/// an AIMD scheme: it is cut by a constant factor (at most once per round
+/// trip) while network conditions are bad, and grows by a constant amount
+/// while the connection is not congested.
+pub struct CongestionControl {
+    config: CongestionConfig,
+    max_bytes_per_second: f32,
+    bytes_per_second: f32,
+    congested: bool,
+    recovering_since: Option<Instant>,
+    last_decrease: Option<Instant>,
+    last_update: Instant,
+}
+
+impl CongestionControl {
+    pub fn new(config: &CongestionConfig, max_bytes_per_second: u32) -> Self {
+        CongestionControl {
+            config: config.clone(),
+            max_bytes_per_second: max_bytes_per_second as f32,
+            bytes_per_second: max_bytes_per_second as f32,
+            congested: false,
+            recovering_since: None,
+            last_decrease: None,
+            last_update: Instant::now(),
+        }
+    }
+
+    /// Evaluates the latest network conditions and adjusts the send rate.
+    /// Returns Some(true) when the connection has just become congested,
+    /// and Some(false) when it has just recovered.
+    pub fn update(
+        &mut self,
+        rtt_millis: f32,
+        jitter_millis: f32,
+        packet_loss: f32,
+    ) -> Option<bool> {
+        let elapsed_secs = self.last_update.elapsed().as_secs_f32();
+        self.last_update = Instant::now();
+
+        let rtt_threshold_millis = self.config.rtt_threshold.as_secs_f32() * 1000.0;
+        let conditions_bad = rtt_millis + jitter_millis > rtt_threshold_millis
+            || packet_loss > self.config.packet_loss_threshold;
+
+        let mut changed = None;
+
+        if conditions_bad {
+            self.recovering_since = None;
+            if !self.congested {
+                self.congested = true;
+                changed = Some(true);
+            }
+        } else if self.congested {
+            match &self.recovering_since {
+                None => {
+                    self.recovering_since = Some(Instant::now());
+                }
+                Some(recovering_since) => {
+                    if recovering_since.elapsed() >= self.config.recovery_duration {
+                        self.congested = false;
+                        self.recovering_since = None;
+                        changed = Some(false);
+                    }
+                }
+            }
+        }
+
+        if conditions_bad {
+            // multiplicative decrease, at most once per round trip so that the
+            // effect of the last decrease can be observed first
+            let round_trip = Duration::from_millis(rtt_millis as u64);
+            let should_decrease = match &self.last_decrease {
+                None => true,
+                Some(last_decrease) => last_decrease.elapsed() >= round_trip,
+            };
+            if should_decrease {
+                self.bytes_per_second = (self.bytes_per_second * self.config.rate_decrease_factor)
+                    .max(self.config.min_bytes_per_second as f32);
+                self.last_decrease = Some(Instant::now());
+            }
+        } else if !self.congested {
+            // additive increase
+            self.bytes_per_second = (self.bytes_per_second
+                + self.config.rate_increase_per_second as f32 * elapsed_secs)
+                .min(self.max_bytes_per_second);
+        }
+
+        changed
+    }
+
+    /// Returns whether the connection is currently considered congested
+    pub fn is_congested(&self) -> bool {
+        self.congested
+    }
+
+    /// Gets the number of bytes per second which should currently be sent
+    pub fn bytes_per_second(&self) -> u32 {
+        self.bytes_per_second as u32
+    }
+}
+
+#[cfg(test)]
+mod congestion_control_tests {
+    use std::time::Duration;
+
+    use super::{CongestionConfig, CongestionControl};
+
+    fn config() -> CongestionConfig {
+        CongestionConfig {
+            rtt_threshold: Duration::from_millis(250),
+            packet_loss_threshold: 0.1,
+            recovery_duration: Duration::ZERO,
+            rate_decrease_factor: 0.5,
+            rate_increase_per_second: 1_000,
+            min_bytes_per_second: 1_000,
+        }
+    }
+
+    #[test]
+    fn good_conditions() {
+        let mut congestion = CongestionControl::new(&config(), 8_000);
+
+        assert_eq!(congestion.update(50.0, 5.0, 0.0), None);
+        assert!(!congestion.is_congested());
+        assert_eq!(congestion.bytes_per_second(), 8_000);
+    }
+
+    #[test]
+    fn high_rtt_is_congestion() {
+        let mut congestion = CongestionControl::new(&config(), 8_000);
+
+        assert_eq!(congestion.update(300.0, 5.0, 0.0), Some(true));
+        assert!(congestion.is_congested());
+        assert_eq!(congestion.bytes_per_second(), 4_000);
+    }
+
+    #[test]
+    fn packet_loss_is_congestion() {
+        let mut congestion = CongestionControl::new(&config(), 8_000);
+
+        assert_eq!(congestion.update(50.0, 5.0, 0.25), Some(true));
+        assert_eq!(congestion.bytes_per_second(), 4_000);
+    }
+
+    #[test]
+    fn rate_never_below_min() {
+        let mut congestion = CongestionControl::new(&config(), 8_000);
+
+        for _ in 0..10 {
+            congestion.update(300.0, 0.0, 0.0);
+            std::thread::sleep(Duration::from_millis(1));
+            congestion.update(0.0, 0.0, 0.5);
+        }
+
+        assert_eq!(congestion.bytes_per_second(), 1_000);
+    }
+
+    #[test]
+    fn recovery() {
+        let mut congestion = CongestionControl::new(&config(), 8_000);
+
+        assert_eq!(congestion.update(300.0, 5.0, 0.0), Some(true));
+        assert_eq!(congestion.update(50.0, 5.0, 0.0), None);
+        assert_eq!(congestion.update(50.0, 5.0, 0.0), Some(false));
+        assert!(!congestion.is_congested());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::PingConfig;
+use crate::{BandwidthBudgetConfig, CongestionConfig, PingConfig};
 use std::{default::Default, time::Duration};
 
 /// Contains Config properties which will be used by a Server or Client
@@ -13,6 +13,16 @@ pub struct ConnectionConfig {
     /// The duration over which to measure bandwidth. Set to None to avoid
     /// measure bandwidth at all.
     pub bandwidth_measure_duration: Option<Duration>,
+    /// Limits the number of bytes the Server sends to each Client. Messages,
+    /// Entity actions and Component updates which don't fit in the budget are
+    /// deferred to later frames. Set to None to send everything as soon as
+    /// possible.
+    pub bandwidth_budget: Option<BandwidthBudgetConfig>,
+    /// Detects congestion on each connection from its round trip time and
+    /// packet loss, and lowers the rate of the bandwidth budget while it
+    /// lasts. If no bandwidth budget is set, the default one is used. Set to
+    /// None to never back off.
+    pub congestion: Option<CongestionConfig>,
     /// Configuration used to monitor the ping & jitter on the network
     pub ping: PingConfig,
 }
@@ -23,12 +33,16 @@ impl ConnectionConfig {
         disconnection_timeout_duration: Duration,
         heartbeat_interval: Duration,
         bandwidth_measure_duration: Option<Duration>,
+        bandwidth_budget: Option<BandwidthBudgetConfig>,
+        congestion: Option<CongestionConfig>,
         ping: PingConfig,
     ) -> Self {
         ConnectionConfig {
             disconnection_timeout_duration,
             heartbeat_interval,
             bandwidth_measure_duration,
+            bandwidth_budget,
+            congestion,
             ping,
         }
     }
@@ -40,6 +54,8 @@ impl Default for ConnectionConfig {
             disconnection_timeout_duration: Duration::from_secs(30),
             heartbeat_interval: Duration::from_secs(4),
             bandwidth_measure_duration: None,
+            bandwidth_budget: None,
+            congestion: None,
             ping: PingConfig::default(),
         }
     }
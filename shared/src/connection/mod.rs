@@ -1,10 +1,15 @@
 pub mod ack_manager;
+pub mod bandwidth_budget;
+pub mod bandwidth_budget_config;
 pub mod bandwidth_monitor;
 pub mod base_connection;
 pub mod compression_config;
+pub mod congestion_config;
+pub mod congestion_control;
 pub mod connection_config;
 pub mod decoder;
 pub mod encoder;
+pub mod packet_loss_monitor;
 pub mod packet_notifiable;
 pub mod packet_type;
 pub mod ping_config;
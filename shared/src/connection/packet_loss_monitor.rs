@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+
+const PACKET_LOSS_SAMPLE_SIZE: usize = 64;
+
+/// Keeps track of whether each of the most recently sent packets was
+/// delivered or dropped, in order to estimate packet loss
+pub struct PacketLossMonitor {
+    samples: VecDeque<bool>,
+    dropped_count: usize,
+}
+
+impl Default for PacketLossMonitor {
+    fn default() -> Self {
+        Self {
+            samples: VecDeque::with_capacity(PACKET_LOSS_SAMPLE_SIZE),
+            dropped_count: 0,
+        }
+    }
+}
+
+impl PacketLossMonitor {
+    pub fn record_delivered(&mut self) {
+        self.record(false);
+    }
+
+    pub fn record_dropped(&mut self) {
+        self.record(true);
+    }
+
+    /// Gets the fraction of recently sent packets which were dropped, between
+    /// 0.0 and 1.0
+    pub fn packet_loss(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.dropped_count as f32 / self.samples.len() as f32
+    }
+
+    fn record(&mut self, dropped: bool) {
+        if self.samples.len() >= PACKET_LOSS_SAMPLE_SIZE {
+            if let Some(true) = self.samples.pop_front() {
+                self.dropped_count -= 1;
+            }
+        }
+        if dropped {
+            self.dropped_count += 1;
+        }
+        self.samples.push_back(dropped);
+    }
+}
+
+#[cfg(test)]
+mod packet_loss_tests {
+    use super::{PacketLossMonitor, PACKET_LOSS_SAMPLE_SIZE};
+
+    #[test]
+    fn no_samples() {
+        let monitor = PacketLossMonitor::default();
+
+        assert_eq!(monitor.packet_loss(), 0.0);
+    }
+
+    #[test]
+    fn half_dropped() {
+        let mut monitor = PacketLossMonitor::default();
+        for _ in 0..10 {
+            monitor.record_delivered();
+            monitor.record_dropped();
+        }
+
+        assert_eq!(monitor.packet_loss(), 0.5);
+    }
+
+    #[test]
+    fn old_samples_expire() {
+        let mut monitor = PacketLossMonitor::default();
+        for _ in 0..PACKET_LOSS_SAMPLE_SIZE {
+            monitor.record_dropped();
+        }
+        for _ in 0..PACKET_LOSS_SAMPLE_SIZE {
+            monitor.record_delivered();
+        }
+
+        assert_eq!(monitor.packet_loss(), 0.0);
+    }
+}
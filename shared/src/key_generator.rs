@@ -2,38 +2,86 @@ use std::marker::PhantomData;
 
 use std::collections::VecDeque;
 
-/// Simple implementation of a store that manages a recycling pool of u16 keys
-pub struct KeyGenerator<K: From<u16> + Into<u16> + Copy> {
-    recycled_local_keys: VecDeque<u16>,
-    next_new_local_key: u16,
+/// Simple implementation of a store that manages a recycling pool of keys, up
+/// to a maximum key
+pub struct KeyGenerator<K: From<u32> + Into<u32> + Copy> {
+    recycled_local_keys: VecDeque<u32>,
+    next_new_local_key: u64,
+    max_key: u32,
     phantom: PhantomData<K>,
 }
 
-impl<K: From<u16> + Into<u16> + Copy> Default for KeyGenerator<K> {
+impl<K: From<u32> + Into<u32> + Copy> Default for KeyGenerator<K> {
     fn default() -> Self {
+        Self::new(u16::MAX as u32)
+    }
+}
+
+impl<K: From<u32> + Into<u32> + Copy> KeyGenerator<K> {
+    /// Create a new KeyGenerator, which hands out keys from 0 up to and
+    /// including `max_key`
+    pub fn new(max_key: u32) -> Self {
         Self {
             recycled_local_keys: VecDeque::default(),
             next_new_local_key: 0,
+            max_key,
             phantom: PhantomData,
         }
     }
-}
 
-impl<K: From<u16> + Into<u16> + Copy> KeyGenerator<K> {
-    /// Get a new, unused key
-    pub fn generate(&mut self) -> K {
+    /// Get a new, unused key, or None if every key is already in use
+    pub fn generate(&mut self) -> Option<K> {
         if let Some(local_key) = self.recycled_local_keys.pop_front() {
-            return K::from(local_key);
+            return Some(K::from(local_key));
         }
 
-        let output = self.next_new_local_key;
+        if self.next_new_local_key > self.max_key as u64 {
+            return None;
+        }
+
+        let output = self.next_new_local_key as u32;
         self.next_new_local_key += 1;
-        K::from(output)
+        Some(K::from(output))
+    }
+
+    /// Returns whether every key is in use, so that `generate` would fail
+    pub fn is_exhausted(&self) -> bool {
+        self.recycled_local_keys.is_empty() && self.next_new_local_key > self.max_key as u64
     }
 
     /// Recycle a used key, freeing it up
     pub fn recycle_key(&mut self, local_key: &K) {
-        let local_key_u16: u16 = Into::<u16>::into(*local_key);
-        self.recycled_local_keys.push_back(local_key_u16);
+        let local_key_u32: u32 = Into::<u32>::into(*local_key);
+        self.recycled_local_keys.push_back(local_key_u32);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyGenerator;
+
+    #[test]
+    fn exhausts_at_max_key() {
+        let mut generator = KeyGenerator::<u32>::new(2);
+
+        assert_eq!(generator.generate(), Some(0));
+        assert_eq!(generator.generate(), Some(1));
+        assert_eq!(generator.generate(), Some(2));
+        assert!(generator.is_exhausted());
+        assert_eq!(generator.generate(), None);
+
+        generator.recycle_key(&1);
+        assert!(!generator.is_exhausted());
+        assert_eq!(generator.generate(), Some(1));
+        assert_eq!(generator.generate(), None);
+    }
+
+    #[test]
+    fn full_u32_range() {
+        let mut generator = KeyGenerator::<u32>::new(u32::MAX);
+        generator.next_new_local_key = u32::MAX as u64;
+
+        assert_eq!(generator.generate(), Some(u32::MAX));
+        assert_eq!(generator.generate(), None);
     }
 }
@@ -31,6 +31,7 @@ mod messages;
 mod protocol;
 
 mod bigmap;
+mod blob_hash;
 mod constants;
 mod key_generator;
 mod shared_config;
@@ -41,9 +42,13 @@ mod wrapping_number;
 pub use backends::{Timer, Timestamp};
 pub use connection::{
     ack_manager::AckManager,
+    bandwidth_budget::BandwidthBudget,
+    bandwidth_budget_config::BandwidthBudgetConfig,
     bandwidth_monitor::BandwidthMonitor,
     base_connection::BaseConnection,
     compression_config::{CompressionConfig, CompressionMode},
+    congestion_config::CongestionConfig,
+    congestion_control::CongestionControl,
     connection_config::ConnectionConfig,
     decoder::Decoder,
     encoder::Encoder,
@@ -54,11 +59,14 @@ pub use connection::{
     standard_header::StandardHeader,
 };
 pub use messages::{
+    blob_message::{BlobId, BlobMessage, BLOB_CHUNK_SIZE_BYTES, BLOB_WINDOW_SIZE},
+    bulk_channel::BulkChannel,
     channel_config::{
         Channel, ChannelConfig, ChannelDirection, ChannelIndex, ChannelMode, DefaultChannels,
         ReliableSettings, TickBufferSettings,
     },
     message_channel::{ChannelReader, ChannelReceiver, ChannelSender, ChannelWriter},
+    message_fragment::message_size_bytes,
     message_list_header,
     message_manager::MessageManager,
     ordered_reliable_receiver::OrderedReliableReceiver,
@@ -69,16 +77,20 @@ pub use protocol::{
     component_update::ComponentUpdate,
     diff_mask::DiffMask,
     entity_action::EntityAction,
+    entity_action_event::EntityActionEvent,
     entity_action_receiver::EntityActionReceiver,
     entity_action_type::EntityActionType,
     entity_handle::EntityHandle,
+    entity_list_property::EntityListProperty,
     entity_property::{
         EntityConverter, EntityHandleConverter, EntityProperty, FakeEntityConverter,
         NetEntityConverter, NetEntityHandleConverter,
     },
-    net_entity::NetEntity,
+    entity_set_property::EntitySetProperty,
+    net_entity::{NetEntity, NetEntityWidth},
     property::Property,
-    property_mutate::{PropertyMutate, PropertyMutator},
+    property_codec::PropertyCodec,
+    property_mutate::{PropertyMutate, PropertyMutator, StaticMutator},
     protocol_io::ProtocolIo,
     protocolize::{ProtocolInserter, ProtocolKindType, Protocolize},
     replica_ref::{
@@ -86,10 +98,14 @@ pub use protocol::{
         ReplicaDynRefWrapper, ReplicaMutTrait, ReplicaMutWrapper, ReplicaRefTrait,
         ReplicaRefWrapper,
     },
-    replicate::{Replicate, ReplicateSafe},
+    replicate::{Replicate, ReplicateNested, ReplicateSafe},
+    replicated_map::ReplicatedMap,
+    replicated_vec::ReplicatedVec,
+    update_rate::UpdateRate,
 };
 
 pub use bigmap::{BigMap, BigMapKey};
+pub use blob_hash::{blob_hash, BlobHash, BlobHasher};
 pub use constants::{MESSAGE_HISTORY_SIZE, MTU_SIZE_BITS, MTU_SIZE_BYTES};
 pub use key_generator::KeyGenerator;
 pub use shared_config::SharedConfig;
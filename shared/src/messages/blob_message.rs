@@ -0,0 +1,43 @@
+use crate::{blob_hash::BlobHash, derive_serde, serde};
+
+/// Identifies a blob transfer on a connection
+pub type BlobId = u16;
+
+/// The number of bytes of a blob carried by each chunk. Small enough that
+/// chunks can fill the space left over in packets carrying gameplay traffic
+pub const BLOB_CHUNK_SIZE_BYTES: usize = 192;
+
+/// The number of chunks which may be waiting for acknowledgement at once
+pub const BLOB_WINDOW_SIZE: usize = 128;
+
+// Messages sent over the BulkChannel to transfer blobs from the Server to a
+// Client
+#[derive(Debug)]
+#[derive_serde]
+pub enum BlobMessage {
+    // Server offers a blob, so the Client can check its cache
+    Offer {
+        blob_id: BlobId,
+        name: String,
+        size: u32,
+        hash: BlobHash,
+    },
+    // Client wants the blob downloaded
+    Accept {
+        blob_id: BlobId,
+    },
+    // Client already has a blob with the same hash
+    Cached {
+        blob_id: BlobId,
+    },
+    // Part of a blob's contents, starting at the given offset
+    Chunk {
+        blob_id: BlobId,
+        offset: u32,
+        bytes: Vec<u8>,
+    },
+    // Either side has given up on the transfer
+    Cancel {
+        blob_id: BlobId,
+    },
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde};
+use naia_socket_shared::Instant;
+
+use crate::{
+    connection::packet_notifiable::PacketNotifiable,
+    types::{MessageId, PacketIndex},
+};
+
+use super::{
+    blob_message::BlobMessage,
+    channel_config::ReliableSettings,
+    message_channel::{ChannelReader, ChannelReceiver, ChannelSender, ChannelWriter},
+    reliable_sender::ReliableSender,
+    unordered_reliable_receiver::UnorderedReliableReceiver,
+};
+
+/// A reliable, unordered channel used to transfer blobs. It is written after
+/// everything else in a packet, so it only gets the room gameplay traffic
+/// leaves behind
+pub struct BulkChannel {
+    sender: ReliableSender<BlobMessage>,
+    receiver: UnorderedReliableReceiver<BlobMessage>,
+    packet_to_message_map: HashMap<PacketIndex, Vec<MessageId>>,
+    delivered_messages: Vec<BlobMessage>,
+}
+
+impl Default for BulkChannel {
+    fn default() -> Self {
+        Self {
+            sender: ReliableSender::new(ReliableSettings::default().rtt_resend_factor),
+            receiver: UnorderedReliableReceiver::default(),
+            packet_to_message_map: HashMap::new(),
+            delivered_messages: Vec::new(),
+        }
+    }
+}
+
+impl BulkChannel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Outgoing Messages
+
+    pub fn send_message(&mut self, message: BlobMessage) {
+        self.sender.send_message(message);
+    }
+
+    /// Swaps every undelivered Message for the replacement returned for it,
+    /// if any, returning how many were replaced
+    pub fn replace_messages(
+        &mut self,
+        replace: impl Fn(&BlobMessage) -> Option<BlobMessage>,
+    ) -> usize {
+        self.sender.replace_messages(replace)
+    }
+
+    /// Counts the undelivered Messages the predicate holds for
+    pub fn count_messages(&self, predicate: impl Fn(&BlobMessage) -> bool) -> usize {
+        self.sender.count_messages(predicate)
+    }
+
+    pub fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+        self.sender.collect_messages(now, rtt_millis);
+    }
+
+    pub fn has_outgoing_messages(&self) -> bool {
+        self.sender.has_messages()
+    }
+
+    pub fn write_messages(&mut self, bit_writer: &mut BitWriter, packet_index: PacketIndex) {
+        if let Some(message_ids) = self.sender.write_messages(&BlobMessageIo, bit_writer) {
+            if !message_ids.is_empty() {
+                self.packet_to_message_map.insert(packet_index, message_ids);
+            }
+        }
+    }
+
+    /// Returns the Messages the remote host has acknowledged since the last
+    /// call
+    pub fn take_delivered_messages(&mut self) -> Vec<BlobMessage> {
+        std::mem::take(&mut self.delivered_messages)
+    }
+
+    // Incoming Messages
+
+    pub fn read_messages(&mut self, bit_reader: &mut BitReader) {
+        self.receiver.read_messages(&BlobMessageIo, bit_reader);
+    }
+
+    pub fn receive_messages(&mut self) -> Vec<BlobMessage> {
+        ChannelReceiver::receive_messages(&mut self.receiver)
+    }
+}
+
+impl PacketNotifiable for BulkChannel {
+    fn notify_packet_delivered(&mut self, packet_index: PacketIndex) {
+        if let Some(message_ids) = self.packet_to_message_map.remove(&packet_index) {
+            for message_id in message_ids {
+                if let Some(message) = self.sender.deliver_message(&message_id) {
+                    self.delivered_messages.push(message);
+                }
+            }
+        }
+    }
+}
+
+struct BlobMessageIo;
+
+impl ChannelWriter<BlobMessage> for BlobMessageIo {
+    fn write(&self, writer: &mut dyn BitWrite, data: &BlobMessage) {
+        data.ser(writer);
+    }
+}
+
+impl ChannelReader<BlobMessage> for BlobMessageIo {
+    fn read(&self, reader: &mut BitReader) -> BlobMessage {
+        BlobMessage::de(reader).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::{BitReader, BitWriter};
+    use naia_socket_shared::Instant;
+
+    use crate::{
+        connection::packet_notifiable::PacketNotifiable, messages::blob_message::BlobMessage,
+    };
+
+    use super::BulkChannel;
+
+    #[test]
+    fn send_and_deliver() {
+        let mut sender = BulkChannel::new();
+        let mut receiver = BulkChannel::new();
+
+        sender.send_message(BlobMessage::Chunk {
+            blob_id: 3,
+            offset: 192,
+            bytes: vec![7; 192],
+        });
+        sender.collect_messages(&Instant::now(), &0.0);
+        assert!(sender.has_outgoing_messages());
+
+        let mut writer = BitWriter::default();
+        sender.write_messages(&mut writer, 10);
+        assert!(!sender.has_outgoing_messages());
+
+        let (length, buffer) = writer.flush();
+        let mut reader = BitReader::new(&buffer[..length]);
+        receiver.read_messages(&mut reader);
+        assert_eq!(
+            receiver.receive_messages(),
+            vec![BlobMessage::Chunk {
+                blob_id: 3,
+                offset: 192,
+                bytes: vec![7; 192],
+            }]
+        );
+
+        sender.notify_packet_delivered(10);
+        assert_eq!(sender.take_delivered_messages().len(), 1);
+        sender.notify_packet_delivered(10);
+        assert!(sender.take_delivered_messages().is_empty());
+    }
+
+    #[test]
+    fn replaced_messages_keep_their_ids() {
+        let mut sender = BulkChannel::new();
+        let mut receiver = BulkChannel::new();
+
+        for blob_id in [1, 2, 1] {
+            sender.send_message(BlobMessage::Chunk {
+                blob_id,
+                offset: 0,
+                bytes: vec![7; 192],
+            });
+        }
+        sender.collect_messages(&Instant::now(), &0.0);
+
+        let replaced = sender.replace_messages(|message| match message {
+            BlobMessage::Chunk { blob_id: 1, .. } => Some(BlobMessage::Cancel { blob_id: 1 }),
+            _ => None,
+        });
+        assert_eq!(replaced, 2);
+
+        let mut writer = BitWriter::default();
+        sender.write_messages(&mut writer, 10);
+        let (length, buffer) = writer.flush();
+        let mut reader = BitReader::new(&buffer[..length]);
+        receiver.read_messages(&mut reader);
+        assert_eq!(
+            receiver.receive_messages(),
+            vec![
+                BlobMessage::Cancel { blob_id: 1 },
+                BlobMessage::Chunk {
+                    blob_id: 2,
+                    offset: 0,
+                    bytes: vec![7; 192],
+                },
+                BlobMessage::Cancel { blob_id: 1 },
+            ]
+        );
+    }
+}
@@ -64,6 +64,7 @@ impl<C: ChannelIndex> Channel<C> {
     pub fn reliable(&self) -> bool {
         match &self.mode {
             ChannelMode::UnorderedUnreliable => false,
+            ChannelMode::SequencedUnreliable => false,
             ChannelMode::UnorderedReliable(_) => true,
             ChannelMode::OrderedReliable(_) => true,
             ChannelMode::TickBuffered(_) => false,
@@ -74,6 +75,17 @@ impl<C: ChannelIndex> Channel<C> {
         self.mode.tick_buffered()
     }
 
+    /// The largest Message, in bytes, which may be sent on the Channel, if
+    /// it has a limit
+    pub fn max_message_size(&self) -> Option<usize> {
+        match &self.mode {
+            ChannelMode::UnorderedReliable(settings) | ChannelMode::OrderedReliable(settings) => {
+                Some(settings.max_message_size)
+            }
+            _ => None,
+        }
+    }
+
     pub fn can_send_to_server(&self) -> bool {
         match &self.direction {
             ChannelDirection::ClientToServer => true,
@@ -94,12 +106,17 @@ impl<C: ChannelIndex> Channel<C> {
 #[derive(Clone)]
 pub struct ReliableSettings {
     pub rtt_resend_factor: f32,
+    /// The largest Message, in bytes, which may be sent on the Channel.
+    /// Messages too large to fit into a single packet are split into
+    /// fragments and reassembled by the receiver
+    pub max_message_size: usize,
 }
 
 impl ReliableSettings {
     pub const fn default() -> Self {
         Self {
             rtt_resend_factor: 1.5,
+            max_message_size: 1024 * 1024,
         }
     }
 }
@@ -121,6 +138,9 @@ impl TickBufferSettings {
 #[derive(Clone)]
 pub enum ChannelMode {
     UnorderedUnreliable,
+    /// Unreliable, and any Message older than the newest one already sent or
+    /// received is dropped, so only the latest state gets through
+    SequencedUnreliable,
     UnorderedReliable(ReliableSettings),
     OrderedReliable(ReliableSettings),
     TickBuffered(TickBufferSettings),
@@ -0,0 +1,212 @@
+use std::{collections::HashMap, mem};
+
+use naia_serde::BitReader;
+
+use crate::types::MessageId;
+
+use super::{
+    message_channel::{ChannelReader, ChannelReceiver},
+    message_fragment::{
+        FragmentReader, FragmentedMessage, MessageFragment, FRAGMENT_SIZE_BYTES,
+        MAX_FRAGMENTED_MESSAGES_IN_FLIGHT,
+    },
+};
+
+/// Wraps a reliable receiver, putting Messages which were split up by a
+/// `FragmentingSender` back together before they are received
+pub struct FragmentReceiver<P> {
+    receiver: Box<dyn ChannelReceiver<FragmentedMessage<P>>>,
+    max_message_size: usize,
+    incoming_fragments: HashMap<MessageId, Vec<Option<Vec<u8>>>>,
+    received_messages: Vec<P>,
+}
+
+impl<P> FragmentReceiver<P> {
+    pub fn new(
+        receiver: Box<dyn ChannelReceiver<FragmentedMessage<P>>>,
+        max_message_size: usize,
+    ) -> Self {
+        Self {
+            receiver,
+            max_message_size,
+            incoming_fragments: HashMap::new(),
+            received_messages: Vec::new(),
+        }
+    }
+
+    /// Stores a fragment, returning the bytes of the whole Message once all
+    /// of its fragments have arrived
+    fn reassemble(&mut self, fragment: MessageFragment) -> Option<Vec<u8>> {
+        let count = fragment.count as usize;
+
+        // discard fragments of Messages which could exceed the maximum size
+        if fragment.index >= fragment.count
+            || (count - 1) * FRAGMENT_SIZE_BYTES >= self.max_message_size
+            || fragment.bytes.len() > FRAGMENT_SIZE_BYTES
+        {
+            return None;
+        }
+
+        // a sender never has more fragmented Messages in flight than this, so
+        // only a remote host which breaks that limit has fragments refused.
+        // Partly received Messages are never dropped to make room, as their
+        // fragments have already been acknowledged.
+        if !self.incoming_fragments.contains_key(&fragment.fragment_id)
+            && self.incoming_fragments.len() >= MAX_FRAGMENTED_MESSAGES_IN_FLIGHT
+        {
+            return None;
+        }
+
+        let fragments = self
+            .incoming_fragments
+            .entry(fragment.fragment_id)
+            .or_insert_with(|| vec![None; count]);
+        if fragments.len() != count {
+            return None;
+        }
+        fragments[fragment.index as usize] = Some(fragment.bytes);
+
+        if fragments.iter().any(|bytes| bytes.is_none()) {
+            return None;
+        }
+
+        let fragments = self.incoming_fragments.remove(&fragment.fragment_id)?;
+        Some(fragments.into_iter().flatten().flatten().collect())
+    }
+}
+
+impl<P: Send + Sync> ChannelReceiver<P> for FragmentReceiver<P> {
+    fn read_messages(&mut self, channel_reader: &dyn ChannelReader<P>, bit_reader: &mut BitReader) {
+        let fragment_reader = FragmentReader::new(channel_reader);
+        self.receiver.read_messages(&fragment_reader, bit_reader);
+
+        for message in self.receiver.receive_messages() {
+            match message {
+                FragmentedMessage::Whole(message) => {
+                    self.received_messages.push(message);
+                }
+                FragmentedMessage::Fragment(fragment) => {
+                    if let Some(bytes) = self.reassemble(fragment) {
+                        let mut reader = BitReader::new(&bytes);
+                        self.received_messages
+                            .push(channel_reader.read(&mut reader));
+                    }
+                }
+            }
+        }
+    }
+
+    fn receive_messages(&mut self) -> Vec<P> {
+        mem::take(&mut self.received_messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::{BitReader, BitWrite, BitWriter, Serde};
+    use naia_socket_shared::Instant;
+
+    use crate::messages::{
+        fragmenting_sender::FragmentingSender,
+        message_channel::{ChannelReader, ChannelReceiver, ChannelSender, ChannelWriter},
+        message_fragment::{MessageFragment, MAX_FRAGMENTED_MESSAGES_IN_FLIGHT},
+        unordered_reliable_receiver::UnorderedReliableReceiver,
+    };
+
+    use super::FragmentReceiver;
+
+    struct BytesChannel;
+
+    impl ChannelWriter<Vec<u8>> for BytesChannel {
+        fn write(&self, writer: &mut dyn BitWrite, data: &Vec<u8>) {
+            data.ser(writer);
+        }
+    }
+
+    impl ChannelReader<Vec<u8>> for BytesChannel {
+        fn read(&self, reader: &mut BitReader) -> Vec<u8> {
+            Vec::<u8>::de(reader).unwrap()
+        }
+    }
+
+    fn fragment(fragment_id: u16, index: u16) -> MessageFragment {
+        MessageFragment {
+            fragment_id,
+            index,
+            count: 2,
+            bytes: vec![index as u8],
+        }
+    }
+
+    #[test]
+    fn refuse_new_partial_message_over_limit() {
+        let mut receiver = FragmentReceiver::<Vec<u8>>::new(
+            Box::new(UnorderedReliableReceiver::default()),
+            1024 * 1024,
+        );
+
+        // the first fragment of more Messages than a sender may have in flight
+        let message_count = MAX_FRAGMENTED_MESSAGES_IN_FLIGHT as u16 + 1;
+        for fragment_id in 0..message_count {
+            assert_eq!(receiver.reassemble(fragment(fragment_id * 2, 0)), None);
+        }
+        assert_eq!(
+            receiver.incoming_fragments.len(),
+            MAX_FRAGMENTED_MESSAGES_IN_FLIGHT
+        );
+
+        // Messages already partly received are kept, and can be completed
+        assert_eq!(receiver.reassemble(fragment(0, 1)), Some(vec![0, 1]));
+        assert_eq!(
+            receiver.reassemble(fragment((message_count - 1) * 2, 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn interleaved_fragmented_messages_all_arrive() {
+        let mut sender = FragmentingSender::<Vec<u8>>::new(1.5, 1024 * 1024);
+        let mut receiver =
+            FragmentReceiver::new(Box::new(UnorderedReliableReceiver::default()), 1024 * 1024);
+
+        let messages: Vec<Vec<u8>> = (0..MAX_FRAGMENTED_MESSAGES_IN_FLIGHT as u8 * 2)
+            .map(|index| vec![index; 1000])
+            .collect();
+        for message in &messages {
+            sender.send_message(message.clone());
+        }
+
+        let mut received = Vec::new();
+        for round in 0..1000 {
+            if received.len() == messages.len() {
+                break;
+            }
+
+            // every fragment not yet delivered is due to be resent each round
+            sender.collect_messages(&Instant::now(), &0.0);
+            let mut packets = Vec::new();
+            for _ in 0..8 {
+                let mut writer = BitWriter::default();
+                let message_ids = sender.write_messages(&BytesChannel, &mut writer).unwrap();
+                let (length, buffer) = writer.flush();
+                packets.push((message_ids, buffer[..length].to_vec()));
+            }
+
+            // packets arrive out of order, and one of them is lost
+            packets.reverse();
+            for (index, (message_ids, packet)) in packets.into_iter().enumerate() {
+                if index == round % 8 {
+                    continue;
+                }
+                receiver.read_messages(&BytesChannel, &mut BitReader::new(&packet));
+                for message_id in message_ids {
+                    sender.notify_message_delivered(&message_id);
+                }
+            }
+            received.append(&mut receiver.receive_messages());
+        }
+
+        received.sort();
+        assert_eq!(received, messages);
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::{HashMap, VecDeque};
+
+use naia_serde::{BitVecWriter, BitWriter};
+use naia_socket_shared::Instant;
+
+use crate::types::MessageId;
+
+use super::{
+    message_channel::{ChannelSender, ChannelWriter},
+    message_fragment::{
+        FragmentWriter, FragmentedMessage, MessageFragment, FRAGMENT_SIZE_BYTES,
+        MAX_FRAGMENTED_MESSAGES_IN_FLIGHT,
+    },
+    reliable_sender::ReliableSender,
+};
+
+/// A ReliableSender which splits Messages too large to fit into a single
+/// packet into fragments, to be put back together by a `FragmentReceiver`.
+/// Messages must already have been checked against the Channel's
+/// `max_message_size` with `message_size_bytes`.
+pub struct FragmentingSender<P: Send + Sync> {
+    sender: ReliableSender<FragmentedMessage<P>>,
+    outgoing_messages: VecDeque<P>,
+    // the fragment_id of each undelivered fragment, by its MessageId
+    fragments_in_flight: HashMap<MessageId, MessageId>,
+    // how many fragments of each fragmented Message are undelivered
+    fragmented_messages_in_flight: HashMap<MessageId, usize>,
+}
+
+impl<P: Clone + Send + Sync> FragmentingSender<P> {
+    pub fn new(rtt_resend_factor: f32, max_message_size: usize) -> Self {
+        // all fragments of a Message must fit within half of the MessageId space
+        if max_message_size / FRAGMENT_SIZE_BYTES >= (MessageId::MAX / 2) as usize {
+            panic!(
+                "max_message_size of {} bytes is too large, it must be less than {} bytes",
+                max_message_size,
+                (MessageId::MAX / 2) as usize * FRAGMENT_SIZE_BYTES
+            );
+        }
+
+        Self {
+            sender: ReliableSender::new(rtt_resend_factor),
+            outgoing_messages: VecDeque::new(),
+            fragments_in_flight: HashMap::new(),
+            fragmented_messages_in_flight: HashMap::new(),
+        }
+    }
+
+    /// Serializes queued Messages, handing them to the inner sender either
+    /// whole or as fragments to be written right away. Once too many
+    /// fragmented Messages are in flight, the rest of the queue waits, so
+    /// that Messages keep their order
+    fn fragment_messages(&mut self, channel_writer: &dyn ChannelWriter<P>) {
+        while let Some(message) = self.outgoing_messages.front() {
+            let mut bit_writer = BitVecWriter::new();
+            channel_writer.write(&mut bit_writer, message);
+
+            if bit_writer.byte_count() <= FRAGMENT_SIZE_BYTES {
+                let message = self.outgoing_messages.pop_front().unwrap();
+                self.sender
+                    .send_message_now(FragmentedMessage::Whole(message));
+                continue;
+            }
+
+            if self.fragmented_messages_in_flight.len() >= MAX_FRAGMENTED_MESSAGES_IN_FLIGHT {
+                return;
+            }
+            self.outgoing_messages.pop_front();
+
+            let bytes = bit_writer.to_bytes();
+            let fragment_id = self.sender.next_message_id();
+            let count = bytes.chunks(FRAGMENT_SIZE_BYTES).len() as u16;
+            for (index, chunk) in bytes.chunks(FRAGMENT_SIZE_BYTES).enumerate() {
+                self.fragments_in_flight
+                    .insert(self.sender.next_message_id(), fragment_id);
+                self.sender
+                    .send_message_now(FragmentedMessage::Fragment(MessageFragment {
+                        fragment_id,
+                        index: index as u16,
+                        count,
+                        bytes: chunk.to_vec(),
+                    }));
+            }
+            self.fragmented_messages_in_flight
+                .insert(fragment_id, count as usize);
+        }
+    }
+}
+
+impl<P: Clone + Send + Sync> ChannelSender<P> for FragmentingSender<P> {
+    fn send_message(&mut self, message: P) {
+        self.outgoing_messages.push_back(message);
+    }
+
+    fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+        self.sender.collect_messages(now, rtt_millis);
+    }
+
+    fn has_messages(&self) -> bool {
+        !self.outgoing_messages.is_empty() || self.sender.has_messages()
+    }
+
+    fn message_count(&self) -> usize {
+        self.outgoing_messages.len() + self.sender.message_count()
+    }
+
+    fn write_messages(
+        &mut self,
+        channel_writer: &dyn ChannelWriter<P>,
+        bit_writer: &mut BitWriter,
+    ) -> Option<Vec<MessageId>> {
+        self.fragment_messages(channel_writer);
+
+        let fragment_writer = FragmentWriter::new(channel_writer);
+        self.sender.write_messages(&fragment_writer, bit_writer)
+    }
+
+    fn notify_message_delivered(&mut self, message_id: &MessageId) {
+        self.sender.deliver_message(message_id);
+
+        if let Some(fragment_id) = self.fragments_in_flight.remove(message_id) {
+            let remaining = self
+                .fragmented_messages_in_flight
+                .get_mut(&fragment_id)
+                .expect("fragmented Message should be in flight");
+            *remaining -= 1;
+            if *remaining == 0 {
+                self.fragmented_messages_in_flight.remove(&fragment_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::{BitReader, BitWrite, BitWriter, Serde};
+    use naia_socket_shared::Instant;
+
+    use crate::messages::{
+        fragment_receiver::FragmentReceiver,
+        message_channel::{ChannelReader, ChannelReceiver, ChannelSender, ChannelWriter},
+        ordered_reliable_receiver::OrderedReliableReceiver,
+    };
+
+    use super::FragmentingSender;
+
+    struct BytesChannel;
+
+    impl ChannelWriter<Vec<u8>> for BytesChannel {
+        fn write(&self, writer: &mut dyn BitWrite, data: &Vec<u8>) {
+            data.ser(writer);
+        }
+    }
+
+    impl ChannelReader<Vec<u8>> for BytesChannel {
+        fn read(&self, reader: &mut BitReader) -> Vec<u8> {
+            Vec::<u8>::de(reader).unwrap()
+        }
+    }
+
+    #[test]
+    fn fragment_and_reassemble_in_order() {
+        let mut sender = FragmentingSender::<Vec<u8>>::new(1.5, 1024 * 1024);
+        let mut receiver =
+            FragmentReceiver::new(Box::new(OrderedReliableReceiver::default()), 1024 * 1024);
+
+        let large: Vec<u8> = (0..5000).map(|i| (i % 251) as u8).collect();
+        let small = vec![1, 2, 3];
+        sender.send_message(large.clone());
+        sender.send_message(small.clone());
+        sender.collect_messages(&Instant::now(), &0.0);
+
+        let mut received = Vec::new();
+        let mut packets = 0;
+        while sender.has_messages() {
+            let mut writer = BitWriter::default();
+            sender.write_messages(&BytesChannel, &mut writer);
+            let (length, buffer) = writer.flush();
+            packets += 1;
+
+            let mut reader = BitReader::new(&buffer[..length]);
+            receiver.read_messages(&BytesChannel, &mut reader);
+            received.append(&mut receiver.receive_messages());
+        }
+
+        assert!(packets > 1);
+        assert_eq!(received, vec![large, small]);
+    }
+}
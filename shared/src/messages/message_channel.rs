@@ -7,6 +7,7 @@ pub trait ChannelSender<P>: Send + Sync {
     fn send_message(&mut self, message: P);
     fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32);
     fn has_messages(&self) -> bool;
+    fn message_count(&self) -> usize;
     fn write_messages(
         &mut self,
         channel_writer: &dyn ChannelWriter<P>,
@@ -0,0 +1,166 @@
+use naia_serde::{BitCounter, BitReader, BitWrite, Serde, SerdeErr, UnsignedVariableInteger};
+
+use crate::{
+    types::MessageId, BigMapKey, EntityHandle, NetEntity, NetEntityHandleConverter, Protocolize,
+    ReplicateSafe,
+};
+
+use super::message_channel::{ChannelReader, ChannelWriter};
+
+/// The number of bytes of a serialized Message carried by each fragment.
+/// Leaves room for the packet header and the Channel's own headers within
+/// `MTU_SIZE_BYTES`
+pub const FRAGMENT_SIZE_BYTES: usize = 400;
+
+/// How many fragmented Messages may be waiting for acknowledgement at once.
+/// A `FragmentingSender` holds back further oversized Messages until one of
+/// these is delivered, so a `FragmentReceiver` never has more than this many
+/// partly received Messages from a well-behaved remote host
+pub const MAX_FRAGMENTED_MESSAGES_IN_FLIGHT: usize = 16;
+
+/// A Message as it is sent over a reliable Channel: either whole, or as one
+/// piece of a Message which was too large to fit into a single packet
+#[derive(Clone)]
+pub enum FragmentedMessage<P> {
+    Whole(P),
+    Fragment(MessageFragment),
+}
+
+/// One piece of an oversized Message. All fragments of a Message are sent
+/// with consecutive MessageIds, and share the MessageId of the first one
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MessageFragment {
+    pub fragment_id: MessageId,
+    pub index: u16,
+    pub count: u16,
+    pub bytes: Vec<u8>,
+}
+
+impl Serde for MessageFragment {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        self.fragment_id.ser(writer);
+        UnsignedVariableInteger::<7>::new(self.index).ser(writer);
+        UnsignedVariableInteger::<7>::new(self.count).ser(writer);
+        self.bytes.ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let fragment_id = MessageId::de(reader)?;
+        let index = UnsignedVariableInteger::<7>::de(reader)?.get() as u16;
+        let count = UnsignedVariableInteger::<7>::de(reader)?.get() as u16;
+        let bytes = Vec::<u8>::de(reader)?;
+
+        Ok(Self {
+            fragment_id,
+            index,
+            count,
+            bytes,
+        })
+    }
+}
+
+/// Returns the most bytes the Message can take up once written to a
+/// Channel. Entities it refers to are counted as the largest NetEntity, as
+/// which NetEntity they are given is not known until they are in scope.
+pub fn message_size_bytes<P: Protocolize, R: ReplicateSafe<P>>(message: &R) -> usize {
+    let mut counter = BitCounter::default();
+    message.write(&mut counter, &LargestEntityConverter);
+    counter.byte_count()
+}
+
+struct LargestEntityConverter;
+
+impl NetEntityHandleConverter for LargestEntityConverter {
+    fn handle_to_net_entity(&self, _: &EntityHandle) -> NetEntity {
+        NetEntity::from(u32::MAX)
+    }
+
+    fn net_entity_to_handle(&self, _: &NetEntity) -> EntityHandle {
+        EntityHandle::from_u64(0)
+    }
+
+    fn try_handle_to_net_entity(&self, _: &EntityHandle) -> Option<NetEntity> {
+        Some(NetEntity::from(u32::MAX))
+    }
+
+    fn try_net_entity_to_handle(&self, _: &NetEntity) -> Option<EntityHandle> {
+        Some(EntityHandle::from_u64(0))
+    }
+}
+
+// FragmentWriter
+
+/// Writes a `FragmentedMessage`, handing whole Messages to the Channel's
+/// own ChannelWriter
+pub struct FragmentWriter<'w, P> {
+    channel_writer: &'w dyn ChannelWriter<P>,
+}
+
+impl<'w, P> FragmentWriter<'w, P> {
+    pub fn new(channel_writer: &'w dyn ChannelWriter<P>) -> Self {
+        Self { channel_writer }
+    }
+}
+
+impl<'w, P> ChannelWriter<FragmentedMessage<P>> for FragmentWriter<'w, P> {
+    fn write(&self, writer: &mut dyn BitWrite, data: &FragmentedMessage<P>) {
+        match data {
+            FragmentedMessage::Whole(message) => {
+                false.ser(writer);
+                self.channel_writer.write(writer, message);
+            }
+            FragmentedMessage::Fragment(fragment) => {
+                true.ser(writer);
+                fragment.ser(writer);
+            }
+        }
+    }
+}
+
+// FragmentReader
+
+/// Reads a `FragmentedMessage`, handing whole Messages to the Channel's own
+/// ChannelReader
+pub struct FragmentReader<'r, P> {
+    channel_reader: &'r dyn ChannelReader<P>,
+}
+
+impl<'r, P> FragmentReader<'r, P> {
+    pub fn new(channel_reader: &'r dyn ChannelReader<P>) -> Self {
+        Self { channel_reader }
+    }
+}
+
+impl<'r, P> ChannelReader<FragmentedMessage<P>> for FragmentReader<'r, P> {
+    fn read(&self, reader: &mut BitReader) -> FragmentedMessage<P> {
+        if bool::de(reader).unwrap() {
+            FragmentedMessage::Fragment(MessageFragment::de(reader).unwrap())
+        } else {
+            FragmentedMessage::Whole(self.channel_reader.read(reader))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::{BitReader, BitVecWriter, Serde};
+
+    use super::MessageFragment;
+
+    #[test]
+    fn read_write_fragment() {
+        let fragment = MessageFragment {
+            fragment_id: 65530,
+            index: 3,
+            count: 700,
+            bytes: vec![0, 1, 2, 254, 255],
+        };
+
+        let mut writer = BitVecWriter::new();
+        fragment.ser(&mut writer);
+        let bytes = writer.to_bytes();
+
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(MessageFragment::de(&mut reader).unwrap(), fragment);
+    }
+}
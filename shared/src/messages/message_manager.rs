@@ -11,12 +11,14 @@ use crate::{
 
 use super::{
     channel_config::{ChannelConfig, ChannelIndex, ChannelMode},
+    fragment_receiver::FragmentReceiver,
+    fragmenting_sender::FragmentingSender,
     message_channel::{ChannelReader, ChannelReceiver, ChannelSender, ChannelWriter},
     ordered_reliable_receiver::OrderedReliableReceiver,
-    reliable_sender::ReliableSender,
+    sequenced_unreliable_receiver::SequencedUnreliableReceiver,
     unordered_reliable_receiver::UnorderedReliableReceiver,
     unordered_unreliable_receiver::UnorderedUnreliableReceiver,
-    unordered_unreliable_sender::UnorderedUnreliableSender,
+    unreliable_sender::UnreliableSender,
 };
 
 /// Handles incoming/outgoing messages, tracks the delivery status of Messages
@@ -52,19 +54,29 @@ impl<P: Protocolize, C: ChannelIndex> MessageManager<P, C> {
                 ChannelMode::UnorderedUnreliable => {
                     channel_senders.insert(
                         channel_index.clone(),
-                        Box::new(UnorderedUnreliableSender::new()),
+                        Box::new(UnreliableSender::new(false)),
                     );
                 }
+                ChannelMode::SequencedUnreliable => {
+                    channel_senders
+                        .insert(channel_index.clone(), Box::new(UnreliableSender::new(true)));
+                }
                 ChannelMode::UnorderedReliable(settings) => {
                     channel_senders.insert(
                         channel_index.clone(),
-                        Box::new(ReliableSender::new(settings.rtt_resend_factor)),
+                        Box::new(FragmentingSender::new(
+                            settings.rtt_resend_factor,
+                            settings.max_message_size,
+                        )),
                     );
                 }
                 ChannelMode::OrderedReliable(settings) => {
                     channel_senders.insert(
                         channel_index.clone(),
-                        Box::new(ReliableSender::new(settings.rtt_resend_factor)),
+                        Box::new(FragmentingSender::new(
+                            settings.rtt_resend_factor,
+                            settings.max_message_size,
+                        )),
                     );
                 }
                 _ => {}
@@ -94,16 +106,28 @@ impl<P: Protocolize, C: ChannelIndex> MessageManager<P, C> {
                         Box::new(UnorderedUnreliableReceiver::new()),
                     );
                 }
-                ChannelMode::UnorderedReliable(_) => {
+                ChannelMode::SequencedUnreliable => {
                     channel_receivers.insert(
                         channel_index.clone(),
-                        Box::new(UnorderedReliableReceiver::default()),
+                        Box::new(SequencedUnreliableReceiver::new()),
                     );
                 }
-                ChannelMode::OrderedReliable(_) => {
+                ChannelMode::UnorderedReliable(settings) => {
                     channel_receivers.insert(
                         channel_index.clone(),
-                        Box::new(OrderedReliableReceiver::default()),
+                        Box::new(FragmentReceiver::new(
+                            Box::new(UnorderedReliableReceiver::default()),
+                            settings.max_message_size,
+                        )),
+                    );
+                }
+                ChannelMode::OrderedReliable(settings) => {
+                    channel_receivers.insert(
+                        channel_index.clone(),
+                        Box::new(FragmentReceiver::new(
+                            Box::new(OrderedReliableReceiver::default()),
+                            settings.max_message_size,
+                        )),
                     );
                 }
                 _ => {}
@@ -143,6 +167,15 @@ impl<P: Protocolize, C: ChannelIndex> MessageManager<P, C> {
         false
     }
 
+    /// Returns the number of queued Messages waiting to be transmitted to the
+    /// remote host
+    pub fn outgoing_message_count(&self) -> usize {
+        self.channel_senders
+            .values()
+            .map(|channel| channel.message_count())
+            .sum()
+    }
+
     pub fn write_messages(
         &mut self,
         channel_writer: &dyn ChannelWriter<P>,
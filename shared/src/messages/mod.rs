@@ -1,10 +1,16 @@
+pub mod blob_message;
+pub mod bulk_channel;
 pub mod channel_config;
+pub mod fragment_receiver;
+pub mod fragmenting_sender;
 pub mod message_channel;
+pub mod message_fragment;
 pub mod message_list_header;
 pub mod message_manager;
 pub mod ordered_reliable_receiver;
 pub mod reliable_receiver;
 pub mod reliable_sender;
+pub mod sequenced_unreliable_receiver;
 pub mod unordered_reliable_receiver;
 pub mod unordered_unreliable_receiver;
-pub mod unordered_unreliable_sender;
+pub mod unreliable_sender;
@@ -16,7 +16,7 @@ impl<P> ReliableReceiver<P> {
         channel_reader: &dyn ChannelReader<P>,
         bit_reader: &mut BitReader,
     ) -> Vec<(MessageId, P)> {
-        let message_count = message_list_header::read(bit_reader);
+        let message_count = message_list_header::read(bit_reader).unwrap();
 
         let mut last_read_id: Option<MessageId> = None;
         let mut output = Vec::new();
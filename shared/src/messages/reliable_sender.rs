@@ -1,4 +1,8 @@
-use std::{collections::VecDeque, mem, time::Duration};
+use std::{
+    collections::{HashSet, VecDeque},
+    mem,
+    time::Duration,
+};
 
 use naia_serde::{BitCounter, BitWrite, BitWriter, Serde, UnsignedVariableInteger};
 
@@ -68,6 +72,52 @@ impl<P: Send + Sync> ReliableSender<P> {
         }
     }
 
+    /// Returns the MessageId which will be given to the next Message sent
+    pub fn next_message_id(&self) -> MessageId {
+        self.next_send_message_id
+    }
+
+    /// Sends a Message which is written with the next packet, rather than
+    /// waiting to be picked up by `collect_messages`
+    pub fn send_message_now(&mut self, message: P)
+    where
+        P: Clone,
+    {
+        let message_id = self.next_send_message_id;
+        self.sending_messages
+            .push_back(Some((message_id, Some(Instant::now()), message.clone())));
+        self.next_send_messages.push_back((message_id, message));
+        self.next_send_message_id = message_id.wrapping_add(1);
+    }
+
+    /// Swaps every undelivered Message for the replacement returned for it,
+    /// if any, returning how many were replaced. The MessageIds are kept, so
+    /// the receiver is not left waiting on a gap.
+    pub fn replace_messages(&mut self, replace: impl Fn(&P) -> Option<P>) -> usize {
+        let mut replaced = 0;
+        for (_, _, message) in self.sending_messages.iter_mut().flatten() {
+            if let Some(replacement) = replace(message) {
+                *message = replacement;
+                replaced += 1;
+            }
+        }
+        for (_, message) in self.next_send_messages.iter_mut() {
+            if let Some(replacement) = replace(message) {
+                *message = replacement;
+            }
+        }
+        replaced
+    }
+
+    /// Counts the undelivered Messages the predicate holds for
+    pub fn count_messages(&self, predicate: impl Fn(&P) -> bool) -> usize {
+        self.sending_messages
+            .iter()
+            .flatten()
+            .filter(|(_, _, message)| predicate(message))
+            .count()
+    }
+
     pub fn take_next_messages(&mut self) -> VecDeque<(MessageId, P)> {
         mem::take(&mut self.next_send_messages)
     }
@@ -116,7 +166,22 @@ impl<P: Clone + Send + Sync> ChannelSender<P> for ReliableSender<P> {
     fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32) {
         let resend_duration = Duration::from_millis((self.rtt_resend_factor * rtt_millis) as u64);
 
+        // messages which were not written last time are still waiting to be sent
+        let waiting_ids: HashSet<MessageId> = self
+            .next_send_messages
+            .drain(..)
+            .map(|(message_id, _)| message_id)
+            .collect();
+
+        // the queue is rebuilt in MessageId order, as the ids are written as
+        // differences from one another
         for (message_id, last_sent_opt, message) in self.sending_messages.iter_mut().flatten() {
+            if waiting_ids.contains(message_id) {
+                self.next_send_messages
+                    .push_back((*message_id, message.clone()));
+                continue;
+            }
+
             let mut should_send = false;
             if let Some(last_sent) = last_sent_opt {
                 if last_sent.elapsed() >= resend_duration {
@@ -137,6 +202,10 @@ impl<P: Clone + Send + Sync> ChannelSender<P> for ReliableSender<P> {
         !self.next_send_messages.is_empty()
     }
 
+    fn message_count(&self) -> usize {
+        self.next_send_messages.len()
+    }
+
     fn write_messages(
         &mut self,
         channel_writer: &dyn ChannelWriter<P>,
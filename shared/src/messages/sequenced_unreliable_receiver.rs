@@ -0,0 +1,148 @@
+use std::{collections::VecDeque, mem};
+
+use naia_serde::{BitReader, Serde, UnsignedVariableInteger};
+
+use crate::{types::MessageId, wrapping_number::sequence_greater_than};
+
+use super::{
+    message_channel::{ChannelReader, ChannelReceiver},
+    message_list_header::read,
+};
+
+/// Receives Messages from a sequenced `UnreliableSender`, dropping any which
+/// are older than the newest Message already received
+pub struct SequencedUnreliableReceiver<P> {
+    newest_received_message_id: Option<MessageId>,
+    incoming_messages: VecDeque<P>,
+}
+
+impl<P> SequencedUnreliableReceiver<P> {
+    pub fn new() -> Self {
+        Self {
+            newest_received_message_id: None,
+            incoming_messages: VecDeque::new(),
+        }
+    }
+
+    fn read_message(
+        &mut self,
+        channel_reader: &dyn ChannelReader<P>,
+        bit_reader: &mut BitReader,
+        last_read_id: &Option<MessageId>,
+    ) -> (MessageId, P) {
+        let message_id: MessageId = if let Some(last_id) = last_read_id {
+            let id_diff = UnsignedVariableInteger::<3>::de(bit_reader).unwrap().get() as MessageId;
+            last_id.wrapping_add(id_diff)
+        } else {
+            // read message id
+            MessageId::de(bit_reader).unwrap()
+        };
+
+        // read payload
+        let message = channel_reader.read(bit_reader);
+
+        (message_id, message)
+    }
+
+    fn recv_message(&mut self, message_id: MessageId, message: P) {
+        if let Some(newest_id) = self.newest_received_message_id {
+            if !sequence_greater_than(message_id, newest_id) {
+                // a newer message has already been received
+                return;
+            }
+        }
+
+        self.newest_received_message_id = Some(message_id);
+        self.incoming_messages.push_back(message);
+    }
+}
+
+impl<P: Send + Sync> ChannelReceiver<P> for SequencedUnreliableReceiver<P> {
+    fn read_messages(&mut self, channel_reader: &dyn ChannelReader<P>, bit_reader: &mut BitReader) {
+        let message_count = read(bit_reader).unwrap();
+        let mut last_read_id: Option<MessageId> = None;
+        for _x in 0..message_count {
+            let (message_id, message) =
+                self.read_message(channel_reader, bit_reader, &last_read_id);
+            last_read_id = Some(message_id);
+            self.recv_message(message_id, message);
+        }
+    }
+
+    fn receive_messages(&mut self) -> Vec<P> {
+        Vec::from(mem::take(&mut self.incoming_messages))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::{BitReader, BitWrite, BitWriter, Serde};
+
+    use crate::messages::{
+        message_channel::{ChannelReader, ChannelReceiver, ChannelSender, ChannelWriter},
+        unreliable_sender::UnreliableSender,
+    };
+
+    use super::SequencedUnreliableReceiver;
+
+    struct U32Channel;
+
+    impl ChannelWriter<u32> for U32Channel {
+        fn write(&self, writer: &mut dyn BitWrite, data: &u32) {
+            data.ser(writer);
+        }
+    }
+
+    impl ChannelReader<u32> for U32Channel {
+        fn read(&self, reader: &mut BitReader) -> u32 {
+            u32::de(reader).unwrap()
+        }
+    }
+
+    fn write_packet(sender: &mut UnreliableSender<u32>) -> Vec<u8> {
+        let mut writer = BitWriter::default();
+        sender.write_messages(&U32Channel, &mut writer);
+        let (length, buffer) = writer.flush();
+        buffer[..length].to_vec()
+    }
+
+    #[test]
+    fn drops_stale_messages() {
+        let mut sender = UnreliableSender::new(true);
+        let mut receiver = SequencedUnreliableReceiver::new();
+
+        sender.send_message(1);
+        sender.send_message(2);
+        let first_packet = write_packet(&mut sender);
+
+        sender.send_message(3);
+        let second_packet = write_packet(&mut sender);
+
+        // the newer packet arrives first
+        receiver.read_messages(&U32Channel, &mut BitReader::new(&second_packet));
+        assert_eq!(receiver.receive_messages(), vec![3]);
+
+        // so everything in the older packet is stale
+        receiver.read_messages(&U32Channel, &mut BitReader::new(&first_packet));
+        assert!(receiver.receive_messages().is_empty());
+
+        sender.send_message(4);
+        let third_packet = write_packet(&mut sender);
+        receiver.read_messages(&U32Channel, &mut BitReader::new(&third_packet));
+        assert_eq!(receiver.receive_messages(), vec![4]);
+    }
+
+    #[test]
+    fn newer_message_supersedes_queued_one() {
+        let mut sender = UnreliableSender::new(true);
+        let mut receiver = SequencedUnreliableReceiver::new();
+
+        sender.send_message(1);
+        sender.send_message(2);
+        assert_eq!(sender.message_count(), 1);
+
+        let packet = write_packet(&mut sender);
+        receiver.read_messages(&U32Channel, &mut BitReader::new(&packet));
+        assert_eq!(receiver.receive_messages(), vec![2]);
+    }
+}
@@ -1,6 +1,6 @@
 use std::{collections::VecDeque, mem};
 
-use naia_serde::BitReader;
+use naia_serde::{BitReader, BitWrite, Serde, SerdeErr};
 
 use crate::{sequence_less_than, types::MessageId};
 
@@ -94,6 +94,37 @@ impl<P> UnorderedReliableReceiver<P> {
         // return buffer
         mem::take(&mut self.received_messages)
     }
+
+    /// Writes which message ids have been received so far, so that another
+    /// receiver can be made to pick up where this one is
+    pub fn write_window(&self, writer: &mut dyn BitWrite) {
+        self.oldest_received_message_id.ser(writer);
+        let received: Vec<bool> = self.record.iter().map(|(_, received)| *received).collect();
+        received.ser(writer);
+    }
+
+    /// Creates a receiver which expects the same message ids as the one
+    /// which wrote the window
+    pub fn read_window(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let oldest_received_message_id = MessageId::de(reader)?;
+        let received = Vec::<bool>::de(reader)?;
+        let record = received
+            .into_iter()
+            .enumerate()
+            .map(|(index, received)| {
+                (
+                    oldest_received_message_id.wrapping_add(index as u16),
+                    received,
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            oldest_received_message_id,
+            record,
+            received_messages: Vec::new(),
+        })
+    }
 }
 
 impl<P: Send + Sync> ChannelReceiver<P> for UnorderedReliableReceiver<P> {
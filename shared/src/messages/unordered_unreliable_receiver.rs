@@ -35,7 +35,7 @@ impl<P> UnorderedUnreliableReceiver<P> {
 
 impl<P: Send + Sync> ChannelReceiver<P> for UnorderedUnreliableReceiver<P> {
     fn read_messages(&mut self, channel_reader: &dyn ChannelReader<P>, bit_reader: &mut BitReader) {
-        let message_count = read(bit_reader);
+        let message_count = read(bit_reader).unwrap();
         for _x in 0..message_count {
             let message = self.read_message(channel_reader, bit_reader);
             self.recv_message(message);
@@ -1,12 +1,18 @@
-use std::fmt;
+use std::{collections::HashMap, fmt};
+
+use naia_serde::{BitReader, BitWrite, Serde, SerdeErr};
 
 // The DiffMask is a variable-length byte array, where each bit represents
 // the current state of a Property owned by a Replica.
 // The Property tracks whether it has been updated and needs to be synced
-// with the remote Client
+// with the remote Client.
+// Replicated collections also record the version they were at before their
+// earliest unsent change, so only the changes since then need to be sent. A
+// set bit with no such version means the whole collection must be resent
 #[derive(PartialEq, Clone, Debug)]
 pub struct DiffMask {
     mask: Vec<u8>,
+    delta_bases: HashMap<u8, u64>,
 }
 
 impl DiffMask {
@@ -14,6 +20,16 @@ impl DiffMask {
     pub fn new(bytes: u8) -> DiffMask {
         DiffMask {
             mask: vec![0; bytes as usize],
+            delta_bases: HashMap::new(),
+        }
+    }
+
+    /// Create a new DiffMask with a given number of bytes, with every bit
+    /// set, so that the whole Replica is sent
+    pub fn new_full(bytes: u8) -> DiffMask {
+        DiffMask {
+            mask: vec![u8::MAX; bytes as usize],
+            delta_bases: HashMap::new(),
         }
     }
 
@@ -29,6 +45,7 @@ impl DiffMask {
 
     /// Sets the bit at the specified position within the DiffMask
     pub fn set_bit(&mut self, index: u8, value: bool) {
+        self.delta_bases.remove(&index);
         if let Some(byte) = self.mask.get_mut((index / 8) as usize) {
             let adjusted_index = index % 8;
             let bit_mask = 1 << adjusted_index;
@@ -40,10 +57,35 @@ impl DiffMask {
         }
     }
 
+    /// Sets the bit at the specified position within the DiffMask, recording
+    /// the version a replicated collection was at before it changed. If the
+    /// bit was already set, the earlier of the two versions is kept
+    pub fn set_delta_bit(&mut self, index: u8, base_version: u64) {
+        match self.bit(index) {
+            Some(false) => {
+                self.set_bit(index, true);
+                self.delta_bases.insert(index, base_version);
+            }
+            Some(true) => {
+                if let Some(current_base) = self.delta_bases.get_mut(&index) {
+                    *current_base = (*current_base).min(base_version);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Gets the version a replicated collection was at before its earliest
+    /// unsent change, if only the changes since then need to be sent
+    pub fn delta_base(&self, index: u8) -> Option<u64> {
+        self.delta_bases.get(&index).copied()
+    }
+
     /// Clears the whole DiffMask
     pub fn clear(&mut self) {
         let size = self.mask.len();
         self.mask = vec![0; size];
+        self.delta_bases.clear();
     }
 
     /// Returns whether any bit has been set in the DiffMask
@@ -66,19 +108,41 @@ impl DiffMask {
         self.mask[index]
     }
 
-    /// Performs a NAND operation on the DiffMask, with another DiffMask
+    /// Performs a NAND operation on the DiffMask, with another DiffMask.
+    /// A replicated collection's bit is kept if the other DiffMask only holds
+    /// changes made after this one's
     pub fn nand(&mut self, other: &DiffMask) {
         //if other diff mask has different capacity, do nothing
         if other.byte_number() != self.byte_number() {
             return;
         }
 
+        let mut kept_bases = HashMap::new();
+        for (index, other_base) in &other.delta_bases {
+            if let Some(true) = self.bit(*index) {
+                let is_covered =
+                    matches!(self.delta_base(*index), Some(base) if *other_base <= base);
+                if !is_covered {
+                    kept_bases.insert(*index, self.delta_base(*index));
+                }
+            }
+        }
+
         for n in 0..self.mask.len() {
             if let Some(my_byte) = self.mask.get_mut(n) {
                 let other_byte = !other.byte(n);
                 *my_byte &= other_byte;
             }
         }
+
+        self.delta_bases
+            .retain(|index, _| !matches!(other.bit(*index), Some(true)));
+        for (index, base) in kept_bases {
+            self.set_bit(index, true);
+            if let Some(base) = base {
+                self.delta_bases.insert(index, base);
+            }
+        }
     }
 
     /// Performs an OR operation on the DiffMask, with another DiffMask
@@ -88,12 +152,53 @@ impl DiffMask {
             return;
         }
 
+        let mut new_bases = HashMap::new();
+        for index in 0..(self.mask.len() * 8) {
+            let index = index as u8;
+            if let Some(true) = other.bit(index) {
+                let new_base = match (self.bit(index), self.delta_base(index)) {
+                    (Some(false), _) => other.delta_base(index),
+                    (_, Some(base)) => other.delta_base(index).map(|other| other.min(base)),
+                    _ => None,
+                };
+                new_bases.insert(index, new_base);
+            }
+        }
+
         for n in 0..self.mask.len() {
             if let Some(my_byte) = self.mask.get_mut(n) {
                 let other_byte = other.byte(n);
                 *my_byte |= other_byte;
             }
         }
+
+        for (index, new_base) in new_bases {
+            if let Some(base) = new_base {
+                self.delta_bases.insert(index, base);
+            } else {
+                self.delta_bases.remove(&index);
+            }
+        }
+    }
+
+    /// Creates a new DiffMask from `length` bits of this DiffMask, starting at
+    /// the given index. Used to get the DiffMask of a nested struct
+    pub fn sub_mask(&self, start: u8, length: u8) -> DiffMask {
+        let bytes = if length == 0 {
+            0
+        } else {
+            ((length - 1) / 8) + 1
+        };
+        let mut output = DiffMask::new(bytes);
+        for index in 0..length {
+            if let Some(true) = self.bit(start + index) {
+                output.set_bit(index, true);
+                if let Some(base) = self.delta_base(start + index) {
+                    output.delta_bases.insert(index, base);
+                }
+            }
+        }
+        output
     }
 
     /// Copies the DiffMask into another DiffMask
@@ -109,6 +214,20 @@ impl DiffMask {
                 *my_byte = other_byte;
             }
         }
+        self.delta_bases = other.delta_bases.clone();
+    }
+}
+
+impl Serde for DiffMask {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        self.mask.ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        Ok(Self {
+            mask: Vec::<u8>::de(reader)?,
+            delta_bases: HashMap::new(),
+        })
     }
 }
 
@@ -229,6 +348,63 @@ mod single_byte_tests {
         assert!(!mask_a.bit(4).unwrap());
     }
 
+    #[test]
+    fn delta_bit() {
+        let mut mask = DiffMask::new(1);
+
+        mask.set_delta_bit(3, 10);
+        mask.set_delta_bit(3, 12);
+        assert!(mask.bit(3).unwrap());
+        assert_eq!(mask.delta_base(3), Some(10));
+
+        // a plain change to the same Property means it must be resent whole
+        mask.set_bit(3, true);
+        assert!(mask.bit(3).unwrap());
+        assert_eq!(mask.delta_base(3), None);
+    }
+
+    #[test]
+    fn delta_or() {
+        let mut mask_a = DiffMask::new(1);
+        mask_a.set_delta_bit(1, 8);
+        mask_a.set_delta_bit(2, 8);
+
+        let mut mask_b = DiffMask::new(1);
+        mask_b.set_delta_bit(1, 5);
+        mask_b.set_bit(2, true);
+        mask_b.set_delta_bit(3, 7);
+
+        mask_a.or(&mask_b);
+
+        assert_eq!(mask_a.delta_base(1), Some(5));
+        assert!(mask_a.bit(2).unwrap());
+        assert_eq!(mask_a.delta_base(2), None);
+        assert_eq!(mask_a.delta_base(3), Some(7));
+    }
+
+    #[test]
+    fn delta_nand() {
+        let mut dropped = DiffMask::new(1);
+        dropped.set_delta_bit(1, 5);
+        dropped.set_delta_bit(2, 5);
+        dropped.set_bit(3, true);
+
+        // a later packet which resent the collection from an earlier version
+        // covers the dropped changes, one which sent only newer changes does not
+        let mut later = DiffMask::new(1);
+        later.set_delta_bit(1, 4);
+        later.set_delta_bit(2, 9);
+        later.set_delta_bit(3, 9);
+
+        dropped.nand(&later);
+
+        assert!(!dropped.bit(1).unwrap());
+        assert!(dropped.bit(2).unwrap());
+        assert_eq!(dropped.delta_base(2), Some(5));
+        assert!(dropped.bit(3).unwrap());
+        assert_eq!(dropped.delta_base(3), None);
+    }
+
     #[test]
     fn clone() {
         let mut mask_a = DiffMask::new(1);
@@ -247,6 +423,16 @@ mod single_byte_tests {
 mod double_byte_tests {
     use crate::DiffMask;
 
+    #[test]
+    fn new_full() {
+        let mask = DiffMask::new_full(2);
+
+        for index in 0..16 {
+            assert!(mask.bit(index).unwrap());
+        }
+        assert!(mask.bit(16).is_none());
+    }
+
     #[test]
     fn getset() {
         let mut mask = DiffMask::new(2);
@@ -365,4 +551,41 @@ mod double_byte_tests {
         assert!(!mask_b.bit(9).unwrap());
         assert!(mask_b.bit(10).unwrap());
     }
+
+    #[test]
+    fn sub_mask() {
+        let mut mask_a = DiffMask::new(2);
+        mask_a.set_bit(5, true);
+        mask_a.set_bit(7, true);
+        mask_a.set_bit(9, true);
+        mask_a.set_bit(12, true);
+
+        let mask_b = mask_a.sub_mask(6, 4);
+
+        assert!(mask_b.byte_number() == 1);
+        assert!(!mask_b.bit(0).unwrap());
+        assert!(mask_b.bit(1).unwrap());
+        assert!(!mask_b.bit(2).unwrap());
+        assert!(mask_b.bit(3).unwrap());
+        assert!(!mask_b.bit(4).unwrap());
+    }
+
+    #[test]
+    fn read_write() {
+        use naia_serde::{BitReader, BitWriter, Serde};
+
+        let mut mask_a = DiffMask::new(2);
+        mask_a.set_bit(3, true);
+        mask_a.set_bit(11, true);
+
+        let mut writer = BitWriter::default();
+        mask_a.ser(&mut writer);
+        let (buffer_length, buffer) = writer.flush();
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+        let mask_b = DiffMask::de(&mut reader).unwrap();
+
+        assert_eq!(mask_a, mask_b);
+        assert!(mask_b.byte_number() == 2);
+    }
 }
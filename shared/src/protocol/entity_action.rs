@@ -5,6 +5,17 @@ pub enum EntityAction<E: Copy, K: ProtocolKindType> {
     DespawnEntity(E),
     InsertComponent(E, K),
     RemoveComponent(E, K),
+    GrantAuthority(E),
+    RevokeAuthority(E),
+    DenyAuthority(E),
+    RequestAuthority(E),
+    ReleaseAuthority(E),
+    GrantComponentAuthority(E, K),
+    RevokeComponentAuthority(E, K),
+    GrantOwnership(E),
+    RevokeOwnership(E),
+    HideEntity(E),
+    RevealEntity(E),
     Noop,
 }
 
@@ -15,7 +26,58 @@ impl<E: Copy, K: ProtocolKindType> EntityAction<E, K> {
             EntityAction::DespawnEntity(entity) => Some(*entity),
             EntityAction::InsertComponent(entity, _) => Some(*entity),
             EntityAction::RemoveComponent(entity, _) => Some(*entity),
+            EntityAction::GrantAuthority(entity) => Some(*entity),
+            EntityAction::RevokeAuthority(entity) => Some(*entity),
+            EntityAction::DenyAuthority(entity) => Some(*entity),
+            EntityAction::RequestAuthority(entity) => Some(*entity),
+            EntityAction::ReleaseAuthority(entity) => Some(*entity),
+            EntityAction::GrantComponentAuthority(entity, _) => Some(*entity),
+            EntityAction::RevokeComponentAuthority(entity, _) => Some(*entity),
+            EntityAction::GrantOwnership(entity) => Some(*entity),
+            EntityAction::RevokeOwnership(entity) => Some(*entity),
+            EntityAction::HideEntity(entity) => Some(*entity),
+            EntityAction::RevealEntity(entity) => Some(*entity),
             EntityAction::Noop => None,
         }
     }
+
+    /// Returns whether the action concerns authority over an Entity, rather
+    /// than the Entity's existence or its Components
+    pub fn is_authority_action(&self) -> bool {
+        matches!(
+            self,
+            EntityAction::GrantAuthority(_)
+                | EntityAction::RevokeAuthority(_)
+                | EntityAction::DenyAuthority(_)
+                | EntityAction::RequestAuthority(_)
+                | EntityAction::ReleaseAuthority(_)
+        )
+    }
+
+    /// Returns whether the action concerns authority over a single Component
+    /// of an Entity
+    pub fn is_component_authority_action(&self) -> bool {
+        matches!(
+            self,
+            EntityAction::GrantComponentAuthority(_, _)
+                | EntityAction::RevokeComponentAuthority(_, _)
+        )
+    }
+
+    /// Returns whether the action concerns which User owns an Entity
+    pub fn is_ownership_action(&self) -> bool {
+        matches!(
+            self,
+            EntityAction::GrantOwnership(_) | EntityAction::RevokeOwnership(_)
+        )
+    }
+
+    /// Returns whether the action hides an Entity which has left scope, or
+    /// reveals it again
+    pub fn is_visibility_action(&self) -> bool {
+        matches!(
+            self,
+            EntityAction::HideEntity(_) | EntityAction::RevealEntity(_)
+        )
+    }
 }
@@ -0,0 +1,20 @@
+use super::protocolize::ProtocolKindType;
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum EntityActionEvent<E: Copy, K: ProtocolKindType> {
+    SpawnEntity(E),
+    DespawnEntity(E),
+    InsertComponent(E, K),
+    RemoveComponent(E, K),
+    GrantAuthority(E),
+    RevokeAuthority(E),
+    DenyAuthority(E),
+    RequestAuthority(E),
+    ReleaseAuthority(E),
+    GrantComponentAuthority(E, K),
+    RevokeComponentAuthority(E, K),
+    GrantOwnership(E),
+    RevokeOwnership(E),
+    HideEntity(E),
+    RevealEntity(E),
+}
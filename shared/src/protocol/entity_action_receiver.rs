@@ -4,14 +4,20 @@ use std::{
     marker::PhantomData,
 };
 
+use naia_serde::{BitReader, BitWrite, Serde, SerdeErr, UnsignedVariableInteger};
+
 use crate::{
-    sequence_less_than, EntityAction, MessageId as ActionId, ProtocolKindType,
-    UnorderedReliableReceiver,
+    sequence_greater_than, sequence_less_than, EntityAction, MessageId as ActionId,
+    ProtocolKindType, UnorderedReliableReceiver,
 };
 
 pub struct EntityActionReceiver<E: Copy + Hash + Eq, K: ProtocolKindType> {
     receiver: UnorderedReliableReceiver<EntityAction<E, K>>,
     entity_channels: HashMap<E, EntityChannel<E, K>>,
+    last_authority_ids: HashMap<E, ActionId>,
+    last_component_authority_ids: HashMap<(E, K), ActionId>,
+    last_ownership_ids: HashMap<E, ActionId>,
+    last_visibility_ids: HashMap<E, ActionId>,
 }
 
 impl<E: Copy + Hash + Eq, K: ProtocolKindType> Default for EntityActionReceiver<E, K> {
@@ -19,6 +25,10 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> Default for EntityActionReceiver<
         Self {
             receiver: UnorderedReliableReceiver::default(),
             entity_channels: HashMap::default(),
+            last_authority_ids: HashMap::default(),
+            last_component_authority_ids: HashMap::default(),
+            last_ownership_ids: HashMap::default(),
+            last_visibility_ids: HashMap::default(),
         }
     }
 }
@@ -32,6 +42,22 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityActionReceiver<E, K> {
         let mut outgoing_actions = Vec::new();
         let incoming_actions = self.receiver.receive_messages();
         for (action_id, action) in incoming_actions {
+            if action.is_authority_action() {
+                self.receive_authority_action(action_id, action, &mut outgoing_actions);
+                continue;
+            }
+            if action.is_component_authority_action() {
+                self.receive_component_authority_action(action_id, action, &mut outgoing_actions);
+                continue;
+            }
+            if action.is_ownership_action() {
+                self.receive_ownership_action(action_id, action, &mut outgoing_actions);
+                continue;
+            }
+            if action.is_visibility_action() {
+                self.receive_visibility_action(action_id, action, &mut outgoing_actions);
+                continue;
+            }
             if let Some(entity) = action.entity() {
                 self.entity_channels
                     .entry(entity)
@@ -42,6 +68,121 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityActionReceiver<E, K> {
         }
         outgoing_actions
     }
+
+    // Authority actions only ever reflect the latest state of authority over an
+    // Entity, so any action older than the last one received is discarded
+    fn receive_authority_action(
+        &mut self,
+        action_id: ActionId,
+        action: EntityAction<E, K>,
+        outgoing_actions: &mut Vec<EntityAction<E, K>>,
+    ) {
+        let entity = action.entity().unwrap();
+        if let Some(last_id) = self.last_authority_ids.get(&entity) {
+            if !sequence_greater_than(action_id, *last_id) {
+                return;
+            }
+        }
+        self.last_authority_ids.insert(entity, action_id);
+        outgoing_actions.push(action);
+    }
+
+    // Authority over a single Component is tracked apart from authority over
+    // the whole Entity, but likewise only the latest action for it is kept
+    fn receive_component_authority_action(
+        &mut self,
+        action_id: ActionId,
+        action: EntityAction<E, K>,
+        outgoing_actions: &mut Vec<EntityAction<E, K>>,
+    ) {
+        let key = match &action {
+            EntityAction::GrantComponentAuthority(entity, component)
+            | EntityAction::RevokeComponentAuthority(entity, component) => (*entity, *component),
+            _ => return,
+        };
+        if let Some(last_id) = self.last_component_authority_ids.get(&key) {
+            if !sequence_greater_than(action_id, *last_id) {
+                return;
+            }
+        }
+        self.last_component_authority_ids.insert(key, action_id);
+        outgoing_actions.push(action);
+    }
+
+    // Like authority, ownership actions only reflect the latest owner, so any
+    // action older than the last one received is discarded
+    fn receive_ownership_action(
+        &mut self,
+        action_id: ActionId,
+        action: EntityAction<E, K>,
+        outgoing_actions: &mut Vec<EntityAction<E, K>>,
+    ) {
+        let entity = action.entity().unwrap();
+        if let Some(last_id) = self.last_ownership_ids.get(&entity) {
+            if !sequence_greater_than(action_id, *last_id) {
+                return;
+            }
+        }
+        self.last_ownership_ids.insert(entity, action_id);
+        outgoing_actions.push(action);
+    }
+
+    // Only whether an Entity is currently hidden matters, so any hide or
+    // reveal older than the last one received is discarded
+    fn receive_visibility_action(
+        &mut self,
+        action_id: ActionId,
+        action: EntityAction<E, K>,
+        outgoing_actions: &mut Vec<EntityAction<E, K>>,
+    ) {
+        let entity = action.entity().unwrap();
+        if let Some(last_id) = self.last_visibility_ids.get(&entity) {
+            if !sequence_greater_than(action_id, *last_id) {
+                return;
+            }
+        }
+        self.last_visibility_ids.insert(entity, action_id);
+        outgoing_actions.push(action);
+    }
+}
+
+impl<E: Copy + Hash + Eq + Serde, K: ProtocolKindType> EntityActionReceiver<E, K> {
+    /// Writes everything the receiver knows about the actions received so
+    /// far, so that another receiver can be made to pick up where this one is
+    pub fn write_state(&self, writer: &mut dyn BitWrite) {
+        self.receiver.write_window(writer);
+
+        UnsignedVariableInteger::<5>::new(self.entity_channels.len() as u64).ser(writer);
+        for entity_channel in self.entity_channels.values() {
+            entity_channel.write_state(writer);
+        }
+
+        self.last_authority_ids.ser(writer);
+        self.last_component_authority_ids.ser(writer);
+        self.last_ownership_ids.ser(writer);
+        self.last_visibility_ids.ser(writer);
+    }
+
+    /// Creates a receiver from the state written by `write_state`
+    pub fn read_state(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let receiver = UnorderedReliableReceiver::read_window(reader)?;
+
+        let channel_count = UnsignedVariableInteger::<5>::de(reader)?.get();
+        let mut entity_channels = HashMap::new();
+        for _ in 0..channel_count {
+            let entity_channel = EntityChannel::read_state(reader)?;
+            entity_channels.insert(entity_channel.entity, entity_channel);
+        }
+
+        Ok(Self {
+            receiver,
+            entity_channels,
+            last_authority_ids: HashMap::de(reader)?,
+            last_component_authority_ids: HashMap::de(reader)?,
+            last_ownership_ids: HashMap::de(reader)?,
+            last_visibility_ids: HashMap::de(reader)?,
+        })
+    }
 }
 
 // Entity Channel
@@ -94,7 +235,18 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityChannel<E, K> {
                     outgoing_actions,
                 );
             }
-            EntityAction::Noop => {}
+            EntityAction::GrantAuthority(_)
+            | EntityAction::RevokeAuthority(_)
+            | EntityAction::DenyAuthority(_)
+            | EntityAction::RequestAuthority(_)
+            | EntityAction::ReleaseAuthority(_)
+            | EntityAction::GrantComponentAuthority(_, _)
+            | EntityAction::RevokeComponentAuthority(_, _)
+            | EntityAction::GrantOwnership(_)
+            | EntityAction::RevokeOwnership(_)
+            | EntityAction::HideEntity(_)
+            | EntityAction::RevealEntity(_)
+            | EntityAction::Noop => {}
         }
     }
 
@@ -113,11 +265,19 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityChannel<E, K> {
 
         if !self.spawned {
             self.spawned = true;
-            outgoing_actions.push(EntityAction::SpawnEntity(self.entity, components));
 
             // pop ALL waiting spawns, despawns, inserts, and removes OLDER than spawn_id
             self.receive_canonical(id);
 
+            // components which arrive with the spawn are inserted along with it
+            for component in &components {
+                self.components
+                    .entry(*component)
+                    .or_insert_with(|| ComponentChannel::new(Some(id)))
+                    .inserted = true;
+            }
+            outgoing_actions.push(EntityAction::SpawnEntity(self.entity, components));
+
             // process any waiting spawns
             if let Some((despawn_id, _)) = self.waiting_despawns.inner.pop_front() {
                 self.receive_despawn_entity_action(despawn_id, outgoing_actions);
@@ -274,6 +434,45 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityChannel<E, K> {
     }
 }
 
+impl<E: Copy + Hash + Eq + Serde, K: ProtocolKindType> EntityChannel<E, K> {
+    fn write_state(&self, writer: &mut dyn BitWrite) {
+        self.entity.ser(writer);
+        self.last_canonical_id.ser(writer);
+        self.spawned.ser(writer);
+
+        UnsignedVariableInteger::<5>::new(self.components.len() as u64).ser(writer);
+        for (component, component_state) in &self.components {
+            component.ser(writer);
+            component_state.write_state(writer);
+        }
+
+        self.waiting_spawns.write_state(writer);
+        self.waiting_despawns.write_state(writer);
+    }
+
+    fn read_state(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let entity = E::de(reader)?;
+        let last_canonical_id = Option::<ActionId>::de(reader)?;
+        let spawned = bool::de(reader)?;
+
+        let component_count = UnsignedVariableInteger::<5>::de(reader)?.get();
+        let mut components = HashMap::new();
+        for _ in 0..component_count {
+            let component = K::de(reader)?;
+            components.insert(component, ComponentChannel::read_state(reader)?);
+        }
+
+        Ok(Self {
+            entity,
+            last_canonical_id,
+            spawned,
+            components,
+            waiting_spawns: OrderedIds::read_state(reader)?,
+            waiting_despawns: OrderedIds::read_state(reader)?,
+        })
+    }
+}
+
 // Component Channel
 // most of this should be public, no methods here
 
@@ -307,6 +506,22 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> ComponentChannel<E, K> {
 
         self.last_canonical_id = Some(id);
     }
+
+    fn write_state(&self, writer: &mut dyn BitWrite) {
+        self.inserted.ser(writer);
+        self.last_canonical_id.ser(writer);
+        self.waiting_inserts.write_state(writer);
+        self.waiting_removes.write_state(writer);
+    }
+
+    fn read_state(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let inserted = bool::de(reader)?;
+        let mut component_state = Self::new(Option::<ActionId>::de(reader)?);
+        component_state.inserted = inserted;
+        component_state.waiting_inserts = OrderedIds::read_state(reader)?;
+        component_state.waiting_removes = OrderedIds::read_state(reader)?;
+        Ok(component_state)
+    }
 }
 
 pub struct OrderedIds<P> {
@@ -373,3 +588,15 @@ impl<P> OrderedIds<P> {
         }
     }
 }
+
+impl<P: Serde> OrderedIds<P> {
+    fn write_state(&self, writer: &mut dyn BitWrite) {
+        self.inner.ser(writer);
+    }
+
+    fn read_state(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        Ok(Self {
+            inner: VecDeque::de(reader)?,
+        })
+    }
+}
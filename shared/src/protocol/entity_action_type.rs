@@ -13,6 +13,28 @@ pub enum EntityActionType {
     InsertComponent,
     // Action indicating a Component to be deleted
     RemoveComponent,
+    // Action indicating a Client has been given authority over an Entity
+    GrantAuthority,
+    // Action indicating a Client's authority over an Entity has been taken away
+    RevokeAuthority,
+    // Action indicating a Client's request for authority over an Entity was denied
+    DenyAuthority,
+    // Action indicating a Client would like authority over an Entity
+    RequestAuthority,
+    // Action indicating a Client no longer needs authority over an Entity
+    ReleaseAuthority,
+    // Action indicating a Client has been given authority over one Component of an Entity
+    GrantComponentAuthority,
+    // Action indicating a Client's authority over one Component of an Entity has been taken away
+    RevokeComponentAuthority,
+    // Action indicating a Client has been made the owner of an Entity
+    GrantOwnership,
+    // Action indicating a Client is no longer the owner of an Entity
+    RevokeOwnership,
+    // Action indicating an Entity has left scope, but is kept on the Client
+    HideEntity,
+    // Action indicating a hidden Entity has come back into scope
+    RevealEntity,
     // Action indicating a non-operation
     Noop,
 }
@@ -0,0 +1,152 @@
+use std::hash::Hash;
+
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr};
+
+use crate::protocol::{
+    entity_handle::EntityHandle,
+    entity_property::{EntityHandleConverter, NetEntityHandleConverter},
+    net_entity::NetEntity,
+    property::Property,
+    property_mutate::PropertyMutator,
+};
+
+/// A Property which holds an ordered list of Entities, such as the members of
+/// a squad. Entities which are not in scope for the remote host are left out
+/// of the list it receives, until they enter scope
+#[derive(Clone)]
+pub struct EntityListProperty {
+    handles_prop: Property<Vec<EntityHandle>>,
+}
+
+impl EntityListProperty {
+    pub fn new(mutator_index: u8) -> Self {
+        Self {
+            handles_prop: Property::<Vec<EntityHandle>>::new(Vec::new(), mutator_index),
+        }
+    }
+
+    pub fn mirror(&mut self, other: &EntityListProperty) {
+        *self.handles_prop = other.handles();
+    }
+
+    pub fn handles(&self) -> Vec<EntityHandle> {
+        (*self.handles_prop).clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.handles_prop.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handles_prop.is_empty()
+    }
+
+    // Serialization / deserialization
+
+    pub fn write(&self, writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter) {
+        let net_entities: Vec<NetEntity> = self
+            .handles_prop
+            .iter()
+            .filter_map(|handle| converter.try_handle_to_net_entity(handle))
+            .collect();
+        net_entities.ser(writer);
+    }
+
+    pub fn new_read(
+        reader: &mut BitReader,
+        mutator_index: u8,
+        converter: &dyn NetEntityHandleConverter,
+    ) -> Result<Self, SerdeErr> {
+        let mut new_prop = Self::new(mutator_index);
+        *new_prop.handles_prop = Vec::<NetEntity>::de(reader)?
+            .iter()
+            .filter_map(|net_entity| converter.try_net_entity_to_handle(net_entity))
+            .collect();
+        Ok(new_prop)
+    }
+
+    pub fn read_write(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        Vec::<NetEntity>::de(bit_reader)?.ser(bit_writer);
+        Ok(())
+    }
+
+    pub fn read(&mut self, reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) {
+        *self.handles_prop = Vec::<NetEntity>::de(reader)
+            .unwrap()
+            .iter()
+            .filter_map(|net_entity| converter.try_net_entity_to_handle(net_entity))
+            .collect();
+    }
+
+    // Comparison
+
+    pub fn equals(&self, other: &EntityListProperty) -> bool {
+        self.handles_prop.equals(&other.handles_prop)
+    }
+
+    // Internal
+
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.handles_prop.set_mutator(mutator);
+    }
+
+    pub fn get<E: Copy + Eq + Hash>(&self, handler: &dyn EntityHandleConverter<E>) -> Vec<E> {
+        self.handles_prop
+            .iter()
+            .map(|handle| handler.handle_to_entity(handle))
+            .collect()
+    }
+
+    pub fn set<E: Copy + Eq + Hash>(
+        &mut self,
+        handler: &dyn EntityHandleConverter<E>,
+        entities: &[E],
+    ) {
+        *self.handles_prop = entities
+            .iter()
+            .map(|entity| handler.entity_to_handle(entity))
+            .collect();
+    }
+
+    pub fn push<E: Copy + Eq + Hash>(
+        &mut self,
+        handler: &dyn EntityHandleConverter<E>,
+        entity: &E,
+    ) {
+        let new_handle = handler.entity_to_handle(entity);
+        self.handles_prop.push(new_handle);
+    }
+
+    /// Removes the first occurrence of the Entity from the list, returning
+    /// whether it was present
+    pub fn remove<E: Copy + Eq + Hash>(
+        &mut self,
+        handler: &dyn EntityHandleConverter<E>,
+        entity: &E,
+    ) -> bool {
+        let handle = handler.entity_to_handle(entity);
+        if let Some(index) = self.handles_prop.iter().position(|item| *item == handle) {
+            self.handles_prop.remove(index);
+            return true;
+        }
+        false
+    }
+
+    pub fn contains<E: Copy + Eq + Hash>(
+        &self,
+        handler: &dyn EntityHandleConverter<E>,
+        entity: &E,
+    ) -> bool {
+        let handle = handler.entity_to_handle(entity);
+        self.handles_prop.contains(&handle)
+    }
+
+    pub fn clear(&mut self) {
+        if !self.handles_prop.is_empty() {
+            self.handles_prop.clear();
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::hash::Hash;
 
-use naia_serde::{BitReader, BitWrite, BitWriter, Serde};
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr};
 
 use crate::{
     bigmap::BigMapKey,
@@ -42,21 +42,25 @@ impl EntityProperty {
         reader: &mut BitReader,
         mutator_index: u8,
         converter: &dyn NetEntityHandleConverter,
-    ) -> Self {
-        if let Some(net_entity) = Option::<NetEntity>::de(reader).unwrap() {
+    ) -> Result<Self, SerdeErr> {
+        if let Some(net_entity) = Option::<NetEntity>::de(reader)? {
             let handle = converter.net_entity_to_handle(&net_entity);
             let mut new_prop = Self::new(mutator_index);
             *new_prop.handle_prop = Some(handle);
-            new_prop
+            Ok(new_prop)
         } else {
             let mut new_prop = Self::new(mutator_index);
             *new_prop.handle_prop = None;
-            new_prop
+            Ok(new_prop)
         }
     }
 
-    pub fn read_write(bit_reader: &mut BitReader, bit_writer: &mut BitWriter) {
-        Option::<NetEntity>::de(bit_reader).unwrap().ser(bit_writer);
+    pub fn read_write(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        Option::<NetEntity>::de(bit_reader)?.ser(bit_writer);
+        Ok(())
     }
 
     pub fn read(&mut self, reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) {
@@ -104,23 +108,39 @@ pub trait EntityHandleConverter<E: Copy + Eq + Hash> {
 pub trait NetEntityHandleConverter {
     fn handle_to_net_entity(&self, entity_handle: &EntityHandle) -> NetEntity;
     fn net_entity_to_handle(&self, net_entity: &NetEntity) -> EntityHandle;
+    /// Gets the NetEntity for the given handle, or None if the Entity is not
+    /// in scope for the remote host
+    fn try_handle_to_net_entity(&self, entity_handle: &EntityHandle) -> Option<NetEntity>;
+    /// Gets the handle for the given NetEntity, or None if no such Entity has
+    /// been replicated
+    fn try_net_entity_to_handle(&self, net_entity: &NetEntity) -> Option<EntityHandle>;
 }
 
 pub trait NetEntityConverter<E: Copy + Eq + Hash> {
     fn entity_to_net_entity(&self, entity: &E) -> NetEntity;
     fn net_entity_to_entity(&self, net_entity: &NetEntity) -> E;
+    fn try_entity_to_net_entity(&self, entity: &E) -> Option<NetEntity>;
+    fn try_net_entity_to_entity(&self, net_entity: &NetEntity) -> Option<E>;
 }
 
 pub struct FakeEntityConverter;
 
 impl NetEntityHandleConverter for FakeEntityConverter {
     fn handle_to_net_entity(&self, _: &EntityHandle) -> NetEntity {
-        NetEntity::from(0)
+        NetEntity::from(0_u32)
     }
 
     fn net_entity_to_handle(&self, _: &NetEntity) -> EntityHandle {
         EntityHandle::from_u64(0)
     }
+
+    fn try_handle_to_net_entity(&self, _: &EntityHandle) -> Option<NetEntity> {
+        Some(NetEntity::from(0_u32))
+    }
+
+    fn try_net_entity_to_handle(&self, _: &NetEntity) -> Option<EntityHandle> {
+        Some(EntityHandle::from_u64(0))
+    }
 }
 
 pub struct EntityConverter<'a, 'b, E: Eq + Copy + Hash> {
@@ -150,4 +170,16 @@ impl<'a, 'b, E: Copy + Eq + Hash> NetEntityHandleConverter for EntityConverter<'
         let entity = self.net_entity_converter.net_entity_to_entity(net_entity);
         self.handle_converter.entity_to_handle(&entity)
     }
+
+    fn try_handle_to_net_entity(&self, entity_handle: &EntityHandle) -> Option<NetEntity> {
+        let entity = self.handle_converter.handle_to_entity(entity_handle);
+        self.net_entity_converter.try_entity_to_net_entity(&entity)
+    }
+
+    fn try_net_entity_to_handle(&self, net_entity: &NetEntity) -> Option<EntityHandle> {
+        let entity = self
+            .net_entity_converter
+            .try_net_entity_to_entity(net_entity)?;
+        Some(self.handle_converter.entity_to_handle(&entity))
+    }
 }
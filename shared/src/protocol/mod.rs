@@ -1,14 +1,21 @@
 pub mod component_update;
 pub mod diff_mask;
 pub mod entity_action;
+pub mod entity_action_event;
 pub mod entity_action_receiver;
 pub mod entity_action_type;
 pub mod entity_handle;
+pub mod entity_list_property;
 pub mod entity_property;
+pub mod entity_set_property;
 pub mod net_entity;
 pub mod property;
+pub mod property_codec;
 pub mod property_mutate;
 pub mod protocol_io;
 pub mod protocolize;
 pub mod replica_ref;
 pub mod replicate;
+pub mod replicated_map;
+pub mod replicated_vec;
+pub mod update_rate;
@@ -6,17 +6,23 @@ use naia_serde::{BitReader, BitWrite, SerdeErr, UnsignedVariableInteger};
 // An Entity in the Client's scope, that is being
 // synced to the Client
 #[derive(Copy, Eq, Hash, Clone, PartialEq)]
-pub struct NetEntity(u16);
+pub struct NetEntity(u32);
 
-impl From<NetEntity> for u16 {
-    fn from(entity: NetEntity) -> u16 {
+impl From<NetEntity> for u32 {
+    fn from(entity: NetEntity) -> u32 {
         entity.0
     }
 }
 
+impl From<u32> for NetEntity {
+    fn from(value: u32) -> Self {
+        NetEntity(value)
+    }
+}
+
 impl From<u16> for NetEntity {
     fn from(value: u16) -> Self {
-        NetEntity(value)
+        NetEntity(value as u32)
     }
 }
 
@@ -26,7 +32,32 @@ impl serde::Serde for NetEntity {
     }
 
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
-        let value = UnsignedVariableInteger::<7>::de(reader).unwrap().get();
-        Ok(NetEntity(value as u16))
+        let value = UnsignedVariableInteger::<7>::de(reader)?.get();
+        if value > u32::MAX as i128 {
+            return Err(SerdeErr {});
+        }
+        Ok(NetEntity(value as u32))
+    }
+}
+
+/// How many NetEntities a single connection can address at once. NetEntities
+/// are always written with a variable-length encoding, so small ids cost the
+/// same number of bits at either width.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum NetEntityWidth {
+    /// Up to 65,536 Entities in scope at once
+    #[default]
+    U16,
+    /// Up to 4,294,967,296 Entities in scope at once
+    U32,
+}
+
+impl NetEntityWidth {
+    /// The largest NetEntity which can be handed out at this width
+    pub fn max_net_entity(&self) -> u32 {
+        match self {
+            NetEntityWidth::U16 => u16::MAX as u32,
+            NetEntityWidth::U32 => u32::MAX,
+        }
     }
 }
@@ -1,20 +1,20 @@
 use std::ops::{Deref, DerefMut};
 
-use naia_serde::{BitReader, BitWrite, BitWriter, Serde};
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr};
 
-use crate::protocol::property_mutate::PropertyMutator;
+use crate::protocol::{property_codec::PropertyCodec, property_mutate::PropertyMutator};
 
 /// A Property of an Component/Message, that contains data
 /// which must be tracked for updates
 #[derive(Clone)]
-pub struct Property<T: Serde> {
+pub struct Property<T: Clone + PartialEq> {
     inner: T,
     mutator: Option<PropertyMutator>,
     mutator_index: u8,
 }
 
 // should be shared
-impl<T: Serde> Property<T> {
+impl<T: Clone + PartialEq> Property<T> {
     /// Create a new Property
     pub fn new(value: T, mutator_index: u8) -> Property<T> {
         Property::<T> {
@@ -30,6 +30,59 @@ impl<T: Serde> Property<T> {
         **self = (**other).clone();
     }
 
+    // Serialization / deserialization with a PropertyCodec
+
+    /// Writes contained value into outgoing byte stream, using the given codec
+    pub fn write_with<C: PropertyCodec<T>>(&self, writer: &mut dyn BitWrite) {
+        C::write(&self.inner, writer);
+    }
+
+    /// Given a cursor into incoming packet data, initializes the Property with
+    /// the synced value, using the given codec
+    pub fn new_read_with<C: PropertyCodec<T>>(
+        reader: &mut BitReader,
+        mutator_index: u8,
+    ) -> Result<Self, SerdeErr> {
+        let inner = C::read(reader)?;
+
+        Ok(Property::<T> {
+            inner,
+            mutator: None,
+            mutator_index,
+        })
+    }
+
+    /// Reads from a stream and immediately writes to a stream, using the given
+    /// codec. Used to buffer updates for later
+    pub fn read_write_with<C: PropertyCodec<T>>(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        C::read_write(bit_reader, bit_writer)
+    }
+
+    /// Given a cursor into incoming packet data, updates the Property with the
+    /// synced value, using the given codec
+    pub fn read_with<C: PropertyCodec<T>>(&mut self, reader: &mut BitReader) {
+        self.inner = C::read(reader).expect("Property read error.");
+    }
+
+    // Comparison
+
+    /// Compare to another property
+    pub fn equals(&self, other: &Property<T>) -> bool {
+        self.inner == other.inner
+    }
+
+    // Internal
+
+    /// Set an PropertyMutator to track changes to the Property
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.mutator = Some(mutator.clone_new());
+    }
+}
+
+impl<T: Serde> Property<T> {
     // Serialization / deserialization
 
     /// Writes contained value into outgoing byte stream
@@ -39,22 +92,24 @@ impl<T: Serde> Property<T> {
 
     /// Given a cursor into incoming packet data, initializes the Property with
     /// the synced value
-    pub fn new_read(reader: &mut BitReader, mutator_index: u8) -> Self {
-        let inner = Self::read_inner(reader);
+    pub fn new_read(reader: &mut BitReader, mutator_index: u8) -> Result<Self, SerdeErr> {
+        let inner = T::de(reader)?;
 
-        Property::<T> {
+        Ok(Property::<T> {
             inner,
             mutator: None,
             mutator_index,
-        }
+        })
     }
 
     /// Reads from a stream and immediately writes to a stream
     /// Used to buffer updates for later
-    pub fn read_write(bit_reader: &mut BitReader, bit_writer: &mut BitWriter) {
-        T::de(bit_reader)
-            .expect("Property read error.")
-            .ser(bit_writer);
+    pub fn read_write(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        T::de(bit_reader)?.ser(bit_writer);
+        Ok(())
     }
 
     /// Given a cursor into incoming packet data, updates the Property with the
@@ -66,25 +121,11 @@ impl<T: Serde> Property<T> {
     fn read_inner(reader: &mut BitReader) -> T {
         T::de(reader).expect("Property read error.")
     }
-
-    // Comparison
-
-    /// Compare to another property
-    pub fn equals(&self, other: &Property<T>) -> bool {
-        self.inner == other.inner
-    }
-
-    // Internal
-
-    /// Set an PropertyMutator to track changes to the Property
-    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
-        self.mutator = Some(mutator.clone_new());
-    }
 }
 
 // It could be argued that Property here is a type of smart-pointer,
 // but honestly this is mainly for the convenience of type coercion
-impl<T: Serde> Deref for Property<T> {
+impl<T: Clone + PartialEq> Deref for Property<T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -92,7 +133,7 @@ impl<T: Serde> Deref for Property<T> {
     }
 }
 
-impl<T: Serde> DerefMut for Property<T> {
+impl<T: Clone + PartialEq> DerefMut for Property<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         // Just assume inner value will be changed, queue for update
         if let Some(mutator) = &mut self.mutator {
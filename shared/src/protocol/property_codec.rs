@@ -0,0 +1,18 @@
+use naia_serde::{BitReader, BitWrite, SerdeErr};
+
+/// Reads & writes a Property's value in place of `Serde`, for types which
+/// can't implement it, such as those from other crates. Used by marking a
+/// field with `#[property(with = "MyCodec")]` when deriving Replicate
+pub trait PropertyCodec<T> {
+    /// Writes the value into an outgoing byte stream
+    fn write(value: &T, writer: &mut dyn BitWrite);
+    /// Reads a value from an incoming byte stream
+    fn read(reader: &mut BitReader) -> Result<T, SerdeErr>;
+    /// Reads a value from a stream and immediately writes it to another,
+    /// used to buffer updates for later
+    fn read_write(reader: &mut BitReader, writer: &mut dyn BitWrite) -> Result<(), SerdeErr> {
+        let value = Self::read(reader)?;
+        Self::write(&value, writer);
+        Ok(())
+    }
+}
@@ -6,6 +6,12 @@ pub trait PropertyMutate: PropertyMutateClone + Send + Sync + 'static {
     /// Given the index of the Property whose value has changed, queue that
     /// Property for transmission to the Client
     fn mutate(&mut self, property_index: u8);
+    /// Given the index of a replicated collection which has changed, and the
+    /// version it was at beforehand, queue only its changes since that version
+    /// for transmission. By default, the whole collection is queued
+    fn mutate_delta(&mut self, property_index: u8, _base_version: u64) {
+        self.mutate(property_index);
+    }
 }
 
 pub trait PropertyMutateClone {
@@ -25,6 +31,36 @@ impl Clone for Box<dyn PropertyMutate> {
     }
 }
 
+/// Mutator given to static Components, which are only ever sent whole when
+/// inserted, and so must not be changed afterwards
+#[derive(Clone)]
+pub struct StaticMutator;
+
+impl PropertyMutate for StaticMutator {
+    fn mutate(&mut self, _property_index: u8) {
+        panic!("Attempted to mutate a static Component after it was inserted!");
+    }
+}
+
+/// Mutator given to the Properties of a nested struct, which shifts their
+/// indices to the range of bits the struct takes up in its parent's DiffMask
+#[derive(Clone)]
+struct OffsetMutator {
+    inner: PropertyMutator,
+    offset: u8,
+}
+
+impl PropertyMutate for OffsetMutator {
+    fn mutate(&mut self, property_index: u8) {
+        self.inner.mutate(self.offset + property_index);
+    }
+
+    fn mutate_delta(&mut self, property_index: u8, base_version: u64) {
+        self.inner
+            .mutate_delta(self.offset + property_index, base_version);
+    }
+}
+
 #[derive(Clone)]
 pub struct PropertyMutator {
     inner: Box<dyn PropertyMutate>,
@@ -43,6 +79,15 @@ impl PropertyMutator {
 
         Self { inner: new_inner }
     }
+
+    /// Creates a new PropertyMutator which adds the given offset to the index
+    /// of any Property it mutates
+    pub fn with_offset(&self, offset: u8) -> Self {
+        Self::new(OffsetMutator {
+            inner: self.clone_new(),
+            offset,
+        })
+    }
 }
 
 impl Deref for PropertyMutator {
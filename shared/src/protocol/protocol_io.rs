@@ -22,6 +22,6 @@ impl<'c, P: Protocolize> ChannelWriter<P> for ProtocolIo<'c> {
 
 impl<'c, P: Protocolize> ChannelReader<P> for ProtocolIo<'c> {
     fn read(&self, bit_reader: &mut BitReader) -> P {
-        P::read(bit_reader, self.converter)
+        P::read(bit_reader, self.converter).unwrap()
     }
 }
@@ -1,6 +1,6 @@
 use std::{any::TypeId, hash::Hash};
 
-use naia_serde::{BitReader, BitWrite, Serde};
+use naia_serde::{BitReader, BitWrite, Serde, SerdeErr};
 
 use crate::{protocol::component_update::ComponentUpdate, DiffMask, NetEntityHandleConverter};
 
@@ -19,9 +19,14 @@ pub trait Protocolize: Clone + Sized + Sync + Send + 'static {
     /// Get kind from a type_id
     fn type_to_kind(type_id: TypeId) -> Option<Self::Kind>;
     /// Read from a bit stream to create a new Replica
-    fn read(bit_reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) -> Self;
+    fn read(
+        bit_reader: &mut BitReader,
+        converter: &dyn NetEntityHandleConverter,
+    ) -> Result<Self, SerdeErr>;
     /// Read from a bit stream to create a new Component Update
-    fn read_create_update(bit_reader: &mut BitReader) -> ComponentUpdate<Self::Kind>;
+    fn read_create_update(
+        bit_reader: &mut BitReader,
+    ) -> Result<ComponentUpdate<Self::Kind>, SerdeErr>;
     /// Get an immutable reference to the inner Component/Message as a
     /// Replicate trait object
     fn dyn_ref(&self) -> ReplicaDynRef<'_, Self>;
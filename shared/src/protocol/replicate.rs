@@ -1,4 +1,4 @@
-use naia_serde::BitWrite;
+use naia_serde::{BitReader, BitWrite, BitWriter, SerdeErr};
 
 use super::{
     component_update::ComponentUpdate,
@@ -8,6 +8,7 @@ use super::{
     property_mutate::PropertyMutator,
     protocolize::Protocolize,
     replica_ref::{ReplicaDynMut, ReplicaDynRef},
+    update_rate::UpdateRate,
 };
 
 /// A struct that implements Replicate is a Message/Component, or otherwise,
@@ -20,8 +21,16 @@ pub trait ReplicateSafe<P: Protocolize>: ReplicateInner {
     /// Gets the TypeId of the Message/Component, used to map to a
     /// registered Protocolize
     fn kind(&self) -> P::Kind;
+    /// Gets the number of bits of the Message/Component's DiffMask which are
+    /// in use, one for each of its Properties
+    fn property_count(&self) -> u8;
     /// Gets the number of bytes of the Message/Component's DiffMask
     fn diff_mask_size(&self) -> u8;
+    /// Gets how often updates to the Component may be sent
+    fn update_rate(&self) -> UpdateRate;
+    /// Gets whether the Component is static, meaning it is only sent when
+    /// inserted and may never be mutated afterwards
+    fn is_static(&self) -> bool;
     /// Get an immutable reference to the inner Component/Message as a
     /// Replicate trait object
     fn dyn_ref(&self) -> ReplicaDynRef<'_, P>;
@@ -50,6 +59,23 @@ pub trait ReplicateSafe<P: Protocolize>: ReplicateInner {
         bit_writer: &mut dyn BitWrite,
         converter: &dyn NetEntityHandleConverter,
     );
+    /// Like `write`, but leaves out any owner-only Properties unless the
+    /// receiving User owns the Entity
+    fn write_for_user(
+        &self,
+        bit_writer: &mut dyn BitWrite,
+        converter: &dyn NetEntityHandleConverter,
+        is_owner: bool,
+    );
+    /// Like `write_update`, but leaves out any owner-only Properties unless
+    /// the receiving User owns the Entity
+    fn write_update_for_user(
+        &self,
+        diff_mask: &DiffMask,
+        bit_writer: &mut dyn BitWrite,
+        converter: &dyn NetEntityHandleConverter,
+        is_owner: bool,
+    );
     /// Reads data from an incoming packet, sufficient to sync the in-memory
     /// Component with it's replica on the Server
     fn read_apply_update(
@@ -63,6 +89,49 @@ pub trait ReplicateSafe<P: Protocolize>: ReplicateInner {
     fn entities(&self) -> Vec<EntityHandle>;
 }
 
+/// A struct of Properties which may itself be a field of a Replica, with each
+/// of its Properties synced individually rather than as one whole value
+pub trait ReplicateNested: Clone + Send + Sync + 'static {
+    /// The number of bits the struct takes up in its parent's DiffMask, one
+    /// for each of its Properties, including those of its own nested structs
+    const PROPERTY_COUNT: u8;
+    /// Set the PropertyMutator used by each of the struct's Properties
+    fn set_mutator(&mut self, mutator: &PropertyMutator);
+    /// Sets the struct to the state of another of the same type
+    fn mirror(&mut self, other: &Self);
+    /// Writes every Property of the struct into an outgoing byte stream
+    fn write(&self, bit_writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter);
+    /// Reads every Property of the struct from an incoming byte stream
+    fn read(
+        bit_reader: &mut BitReader,
+        converter: &dyn NetEntityHandleConverter,
+    ) -> Result<Self, SerdeErr>;
+    /// Reads an update from an incoming byte stream and immediately writes it
+    /// to another stream, used to buffer updates for later
+    fn read_write(
+        bit_reader: &mut BitReader,
+        update_writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr>;
+    /// Writes the Properties which are set in the given DiffMask, which holds
+    /// only the bits belonging to this struct
+    fn write_update(
+        &self,
+        diff_mask: &DiffMask,
+        writer: &mut dyn BitWrite,
+        converter: &dyn NetEntityHandleConverter,
+    );
+    /// Reads an update written by `write_update`, applying it to the struct
+    fn read_apply_update(
+        &mut self,
+        reader: &mut BitReader,
+        converter: &dyn NetEntityHandleConverter,
+    );
+    /// Returns whether has any EntityProperties
+    fn has_entity_properties(&self) -> bool;
+    /// Returns a list of Entities contained within the struct's properties
+    fn entities(&self) -> Vec<EntityHandle>;
+}
+
 cfg_if! {
     if #[cfg(feature = "bevy_support")]
     {
@@ -0,0 +1,346 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    ops::Deref,
+};
+
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr, UnsignedVariableInteger};
+
+use crate::protocol::{diff_mask::DiffMask, property_mutate::PropertyMutator};
+
+// How many removals are remembered, a Client which has missed more than this
+// is sent the whole map
+const REMOVAL_HISTORY_SIZE: usize = 64;
+
+/// A Property of a Component which holds a map of keys to values. Only the
+/// entries which have been inserted, changed or removed are sent to the
+/// Client, rather than the whole map
+#[derive(Clone)]
+pub struct ReplicatedMap<K: Serde + Eq + Hash, V: Serde> {
+    inner: HashMap<K, V>,
+    // the version at which each entry was last changed
+    versions: HashMap<K, u64>,
+    removals: VecDeque<(K, u64)>,
+    // removals made at or before this version have been forgotten
+    forgotten_version: u64,
+    version: u64,
+    mutator: Option<PropertyMutator>,
+    mutator_index: u8,
+}
+
+impl<K: Serde + Eq + Hash, V: Serde> ReplicatedMap<K, V> {
+    /// Create a new ReplicatedMap
+    pub fn new(entries: HashMap<K, V>, mutator_index: u8) -> Self {
+        let versions = entries.keys().map(|key| (key.clone(), 0)).collect();
+        Self {
+            inner: entries,
+            versions,
+            removals: VecDeque::new(),
+            forgotten_version: 0,
+            version: 0,
+            mutator: None,
+            mutator_index,
+        }
+    }
+
+    /// Set contents to those of another ReplicatedMap, queues the whole map for
+    /// update
+    pub fn mirror(&mut self, other: &ReplicatedMap<K, V>) {
+        self.inner = other.inner.clone();
+        self.next_version();
+        self.versions = self
+            .inner
+            .keys()
+            .map(|key| (key.clone(), self.version))
+            .collect();
+        // removals can't be known, so the whole map must be resent
+        self.removals.clear();
+        self.forgotten_version = self.version;
+        if let Some(mutator) = &mut self.mutator {
+            mutator.mutate(self.mutator_index);
+        }
+    }
+
+    // Mutation
+
+    /// Inserts a value for the given key, returning any previous value
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let base_version = self.next_version();
+        self.removals.retain(|(removed_key, _)| *removed_key != key);
+        self.versions.insert(key.clone(), self.version);
+        let old_value = self.inner.insert(key, value);
+        self.mutate(base_version);
+        old_value
+    }
+
+    /// Removes the entry for the given key, returning its value
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.inner.remove(key)?;
+        self.versions.remove(key);
+
+        let base_version = self.next_version();
+        self.removals.push_back((key.clone(), self.version));
+        if self.removals.len() > REMOVAL_HISTORY_SIZE {
+            if let Some((_, version)) = self.removals.pop_front() {
+                self.forgotten_version = version;
+            }
+        }
+        self.mutate(base_version);
+        Some(value)
+    }
+
+    /// Gets a mutable reference to the value for the given key, which is
+    /// queued for update
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        if !self.inner.contains_key(key) {
+            return None;
+        }
+        let base_version = self.next_version();
+        self.versions.insert(key.clone(), self.version);
+        self.mutate(base_version);
+        self.inner.get_mut(key)
+    }
+
+    /// Removes every entry from the map
+    pub fn clear(&mut self) {
+        let keys: Vec<K> = self.inner.keys().cloned().collect();
+        for key in keys {
+            self.remove(&key);
+        }
+    }
+
+    // Serialization / deserialization
+
+    /// Writes the whole map into an outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.inner.ser(writer);
+    }
+
+    /// Writes the entries changed since the version held in the DiffMask into
+    /// an outgoing byte stream, or the whole map if there is no such version or
+    /// the removals made since then have been forgotten
+    pub fn write_update(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite) {
+        match diff_mask.delta_base(self.mutator_index) {
+            Some(base_version) if base_version >= self.forgotten_version => {
+                // delta
+                false.ser(writer);
+
+                let removed: Vec<&K> = self
+                    .removals
+                    .iter()
+                    .filter(|(_, version)| *version > base_version)
+                    .map(|(key, _)| key)
+                    .collect();
+                UnsignedVariableInteger::<5>::new(removed.len() as u64).ser(writer);
+                for key in removed {
+                    key.ser(writer);
+                }
+
+                let changed: Vec<(&K, &V)> = self
+                    .inner
+                    .iter()
+                    .filter(|(key, _)| self.versions[*key] > base_version)
+                    .collect();
+                UnsignedVariableInteger::<5>::new(changed.len() as u64).ser(writer);
+                for (key, value) in changed {
+                    key.ser(writer);
+                    value.ser(writer);
+                }
+            }
+            _ => {
+                // full
+                true.ser(writer);
+                self.write(writer);
+            }
+        }
+    }
+
+    /// Given a cursor into incoming packet data, initializes the ReplicatedMap
+    /// with the synced map
+    pub fn new_read(reader: &mut BitReader, mutator_index: u8) -> Result<Self, SerdeErr> {
+        let entries = HashMap::<K, V>::de(reader)?;
+        Ok(Self::new(entries, mutator_index))
+    }
+
+    /// Reads an update from a stream and immediately writes it to a stream
+    /// Used to buffer updates for later
+    pub fn read_write(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        let is_full = bool::de(bit_reader)?;
+        is_full.ser(bit_writer);
+        if is_full {
+            HashMap::<K, V>::de(bit_reader)?.ser(bit_writer);
+            return Ok(());
+        }
+
+        let removed = UnsignedVariableInteger::<5>::de(bit_reader)?;
+        removed.ser(bit_writer);
+        for _ in 0..removed.get() {
+            K::de(bit_reader)?.ser(bit_writer);
+        }
+
+        let changed = UnsignedVariableInteger::<5>::de(bit_reader)?;
+        changed.ser(bit_writer);
+        for _ in 0..changed.get() {
+            K::de(bit_reader)?.ser(bit_writer);
+            V::de(bit_reader)?.ser(bit_writer);
+        }
+        Ok(())
+    }
+
+    /// Given a cursor into incoming packet data, applies the update written by
+    /// `write_update` to the map
+    pub fn read(&mut self, reader: &mut BitReader) {
+        let is_full = bool::de(reader).expect("ReplicatedMap read error.");
+        if is_full {
+            self.inner = HashMap::<K, V>::de(reader).expect("ReplicatedMap read error.");
+            self.versions = self.inner.keys().map(|key| (key.clone(), 0)).collect();
+            return;
+        }
+
+        let removed = UnsignedVariableInteger::<5>::de(reader)
+            .expect("ReplicatedMap read error.")
+            .get();
+        for _ in 0..removed {
+            let key = K::de(reader).expect("ReplicatedMap read error.");
+            self.inner.remove(&key);
+            self.versions.remove(&key);
+        }
+
+        let changed = UnsignedVariableInteger::<5>::de(reader)
+            .expect("ReplicatedMap read error.")
+            .get();
+        for _ in 0..changed {
+            let key = K::de(reader).expect("ReplicatedMap read error.");
+            let value = V::de(reader).expect("ReplicatedMap read error.");
+            self.versions.insert(key.clone(), 0);
+            self.inner.insert(key, value);
+        }
+    }
+
+    // Comparison
+
+    /// Compare to another ReplicatedMap
+    pub fn equals(&self, other: &ReplicatedMap<K, V>) -> bool {
+        self.inner == other.inner
+    }
+
+    // Internal
+
+    /// Set an PropertyMutator to track changes to the ReplicatedMap
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.mutator = Some(mutator.clone_new());
+    }
+
+    // returns the version before the change
+    fn next_version(&mut self) -> u64 {
+        let base_version = self.version;
+        self.version += 1;
+        base_version
+    }
+
+    fn mutate(&mut self, base_version: u64) {
+        if let Some(mutator) = &mut self.mutator {
+            mutator.mutate_delta(self.mutator_index, base_version);
+        }
+    }
+}
+
+impl<K: Serde + Eq + Hash, V: Serde> Deref for ReplicatedMap<K, V> {
+    type Target = HashMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod replicated_map_tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, RwLock},
+    };
+
+    use naia_serde::{BitReader, BitWriter};
+
+    use crate::{DiffMask, PropertyMutate, PropertyMutator, ReplicatedMap};
+
+    #[derive(Clone)]
+    struct TestMutator {
+        diff_mask: Arc<RwLock<DiffMask>>,
+    }
+
+    impl PropertyMutate for TestMutator {
+        fn mutate(&mut self, property_index: u8) {
+            self.diff_mask
+                .write()
+                .unwrap()
+                .set_bit(property_index, true);
+        }
+
+        fn mutate_delta(&mut self, property_index: u8, base_version: u64) {
+            self.diff_mask
+                .write()
+                .unwrap()
+                .set_delta_bit(property_index, base_version);
+        }
+    }
+
+    fn sync(
+        server: &ReplicatedMap<u8, u16>,
+        diff_mask: &DiffMask,
+        client: &mut ReplicatedMap<u8, u16>,
+    ) {
+        let mut writer = BitWriter::default();
+        server.write_update(diff_mask, &mut writer);
+        let (length, buffer) = writer.flush();
+
+        let mut reader = BitReader::new(&buffer[..length]);
+        client.read(&mut reader);
+    }
+
+    fn tracked(entries: HashMap<u8, u16>) -> (ReplicatedMap<u8, u16>, Arc<RwLock<DiffMask>>) {
+        let diff_mask = Arc::new(RwLock::new(DiffMask::new(1)));
+        let mut map = ReplicatedMap::new(entries, 0);
+        map.set_mutator(&PropertyMutator::new(TestMutator {
+            diff_mask: diff_mask.clone(),
+        }));
+        (map, diff_mask)
+    }
+
+    #[test]
+    fn delta_sync() {
+        let entries: HashMap<u8, u16> = (0..10).map(|key| (key, key as u16)).collect();
+        let (mut server, diff_mask) = tracked(entries.clone());
+        let mut client = ReplicatedMap::new(entries, 0);
+
+        server.insert(20, 200);
+        server.remove(&3);
+        *server.get_mut(&5).unwrap() = 500;
+
+        sync(&server, &diff_mask.read().unwrap(), &mut client);
+        assert_eq!(client.len(), 10);
+        assert_eq!(client.get(&20), Some(&200));
+        assert_eq!(client.get(&3), None);
+        assert_eq!(client.get(&5), Some(&500));
+        assert!(client.equals(&server));
+    }
+
+    #[test]
+    fn forgotten_removals_resync() {
+        let entries: HashMap<u8, u16> = (0..100).map(|key| (key, key as u16)).collect();
+        let (mut server, diff_mask) = tracked(entries.clone());
+        let mut client = ReplicatedMap::new(entries, 0);
+
+        for key in 0..80 {
+            server.remove(&key);
+        }
+        assert_eq!(diff_mask.read().unwrap().delta_base(0), Some(0));
+
+        sync(&server, &diff_mask.read().unwrap(), &mut client);
+        assert_eq!(client.len(), 20);
+        assert!(client.equals(&server));
+    }
+}
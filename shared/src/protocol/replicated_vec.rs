@@ -0,0 +1,339 @@
+use std::ops::Deref;
+
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr, UnsignedVariableInteger};
+
+use crate::protocol::{diff_mask::DiffMask, property_mutate::PropertyMutator};
+
+/// A Property of a Component which holds a list of values. Only the values
+/// which have changed are sent to the Client, rather than the whole list
+#[derive(Clone)]
+pub struct ReplicatedVec<T: Serde> {
+    inner: Vec<T>,
+    // the version at which each value was last changed
+    versions: Vec<u64>,
+    version: u64,
+    mutator: Option<PropertyMutator>,
+    mutator_index: u8,
+}
+
+impl<T: Serde> ReplicatedVec<T> {
+    /// Create a new ReplicatedVec
+    pub fn new(values: Vec<T>, mutator_index: u8) -> Self {
+        let versions = vec![0; values.len()];
+        Self {
+            inner: values,
+            versions,
+            version: 0,
+            mutator: None,
+            mutator_index,
+        }
+    }
+
+    /// Set contents to those of another ReplicatedVec, queues every value for
+    /// update
+    pub fn mirror(&mut self, other: &ReplicatedVec<T>) {
+        self.inner = other.inner.clone();
+        self.versions = vec![0; self.inner.len()];
+        self.mutate(0);
+    }
+
+    // Mutation
+
+    /// Appends a value to the end of the list
+    pub fn push(&mut self, value: T) {
+        self.inner.push(value);
+        self.versions.push(0);
+        self.mutate(self.inner.len() - 1);
+    }
+
+    /// Removes the last value from the list and returns it
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.inner.pop()?;
+        self.versions.pop();
+        self.mutate(self.inner.len());
+        Some(value)
+    }
+
+    /// Inserts a value at the given index, shifting all values after it
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.inner.insert(index, value);
+        self.versions.insert(index, 0);
+        self.mutate(index);
+    }
+
+    /// Removes and returns the value at the given index, shifting all values
+    /// after it
+    pub fn remove(&mut self, index: usize) -> T {
+        let value = self.inner.remove(index);
+        self.versions.remove(index);
+        self.mutate(index);
+        value
+    }
+
+    /// Sets the value at the given index
+    pub fn set(&mut self, index: usize, value: T) {
+        self.inner[index] = value;
+        self.mutate_one(index);
+    }
+
+    /// Gets a mutable reference to the value at the given index, which is
+    /// queued for update
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.inner.len() {
+            return None;
+        }
+        self.mutate_one(index);
+        self.inner.get_mut(index)
+    }
+
+    /// Shortens the list to the given length
+    pub fn truncate(&mut self, length: usize) {
+        if length >= self.inner.len() {
+            return;
+        }
+        self.inner.truncate(length);
+        self.versions.truncate(length);
+        self.mutate(length);
+    }
+
+    /// Removes all values from the list
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    // Serialization / deserialization
+
+    /// Writes the whole list into an outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.inner.ser(writer);
+    }
+
+    /// Writes the values changed since the version held in the DiffMask into an
+    /// outgoing byte stream, or the whole list if there is no such version
+    pub fn write_update(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite) {
+        if let Some(base_version) = diff_mask.delta_base(self.mutator_index) {
+            // delta
+            false.ser(writer);
+            UnsignedVariableInteger::<5>::new(self.inner.len() as u64).ser(writer);
+
+            let changed: Vec<usize> = (0..self.inner.len())
+                .filter(|index| self.versions[*index] > base_version)
+                .collect();
+            UnsignedVariableInteger::<5>::new(changed.len() as u64).ser(writer);
+            for index in changed {
+                UnsignedVariableInteger::<5>::new(index as u64).ser(writer);
+                self.inner[index].ser(writer);
+            }
+        } else {
+            // full
+            true.ser(writer);
+            self.write(writer);
+        }
+    }
+
+    /// Given a cursor into incoming packet data, initializes the ReplicatedVec
+    /// with the synced list
+    pub fn new_read(reader: &mut BitReader, mutator_index: u8) -> Result<Self, SerdeErr> {
+        let values = Vec::<T>::de(reader)?;
+        Ok(Self::new(values, mutator_index))
+    }
+
+    /// Reads an update from a stream and immediately writes it to a stream
+    /// Used to buffer updates for later
+    pub fn read_write(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        let is_full = bool::de(bit_reader)?;
+        is_full.ser(bit_writer);
+        if is_full {
+            Vec::<T>::de(bit_reader)?.ser(bit_writer);
+            return Ok(());
+        }
+
+        UnsignedVariableInteger::<5>::de(bit_reader)?.ser(bit_writer);
+        let changed = UnsignedVariableInteger::<5>::de(bit_reader)?;
+        changed.ser(bit_writer);
+        for _ in 0..changed.get() {
+            UnsignedVariableInteger::<5>::de(bit_reader)?.ser(bit_writer);
+            T::de(bit_reader)?.ser(bit_writer);
+        }
+        Ok(())
+    }
+
+    /// Given a cursor into incoming packet data, applies the update written by
+    /// `write_update` to the list
+    pub fn read(&mut self, reader: &mut BitReader) {
+        let is_full = bool::de(reader).expect("ReplicatedVec read error.");
+        if is_full {
+            self.inner = Vec::<T>::de(reader).expect("ReplicatedVec read error.");
+            self.versions = vec![0; self.inner.len()];
+            return;
+        }
+
+        let length = UnsignedVariableInteger::<5>::de(reader)
+            .expect("ReplicatedVec read error.")
+            .get() as usize;
+        let changed = UnsignedVariableInteger::<5>::de(reader)
+            .expect("ReplicatedVec read error.")
+            .get();
+        for _ in 0..changed {
+            let index = UnsignedVariableInteger::<5>::de(reader)
+                .expect("ReplicatedVec read error.")
+                .get() as usize;
+            let value = T::de(reader).expect("ReplicatedVec read error.");
+
+            // changed values are written in order, so any which extend the list
+            // arrive right after the last current value
+            if index < self.inner.len() {
+                self.inner[index] = value;
+            } else if index == self.inner.len() {
+                self.inner.push(value);
+                self.versions.push(0);
+            }
+        }
+        self.inner.truncate(length);
+        self.versions.truncate(length);
+    }
+
+    // Comparison
+
+    /// Compare to another ReplicatedVec
+    pub fn equals(&self, other: &ReplicatedVec<T>) -> bool {
+        self.inner == other.inner
+    }
+
+    // Internal
+
+    /// Set an PropertyMutator to track changes to the ReplicatedVec
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.mutator = Some(mutator.clone_new());
+    }
+
+    // mark every value from the given index onwards as changed
+    fn mutate(&mut self, from_index: usize) {
+        let base_version = self.version;
+        self.version += 1;
+        for version in self.versions.iter_mut().skip(from_index) {
+            *version = self.version;
+        }
+        if let Some(mutator) = &mut self.mutator {
+            mutator.mutate_delta(self.mutator_index, base_version);
+        }
+    }
+
+    fn mutate_one(&mut self, index: usize) {
+        let base_version = self.version;
+        self.version += 1;
+        self.versions[index] = self.version;
+        if let Some(mutator) = &mut self.mutator {
+            mutator.mutate_delta(self.mutator_index, base_version);
+        }
+    }
+}
+
+impl<T: Serde> Deref for ReplicatedVec<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod replicated_vec_tests {
+    use std::sync::{Arc, RwLock};
+
+    use naia_serde::{BitReader, BitWrite, BitWriter};
+
+    use crate::{DiffMask, PropertyMutate, PropertyMutator, ReplicatedVec};
+
+    #[derive(Clone)]
+    struct TestMutator {
+        diff_mask: Arc<RwLock<DiffMask>>,
+    }
+
+    impl PropertyMutate for TestMutator {
+        fn mutate(&mut self, property_index: u8) {
+            self.diff_mask
+                .write()
+                .unwrap()
+                .set_bit(property_index, true);
+        }
+
+        fn mutate_delta(&mut self, property_index: u8, base_version: u64) {
+            self.diff_mask
+                .write()
+                .unwrap()
+                .set_delta_bit(property_index, base_version);
+        }
+    }
+
+    fn sync(server: &ReplicatedVec<u16>, diff_mask: &DiffMask, client: &mut ReplicatedVec<u16>) {
+        let mut writer = BitWriter::default();
+        server.write_update(diff_mask, &mut writer);
+        let (length, buffer) = writer.flush();
+
+        let mut reader = BitReader::new(&buffer[..length]);
+        client.read(&mut reader);
+    }
+
+    fn tracked(values: Vec<u16>) -> (ReplicatedVec<u16>, Arc<RwLock<DiffMask>>) {
+        let diff_mask = Arc::new(RwLock::new(DiffMask::new(1)));
+        let mut vec = ReplicatedVec::new(values, 0);
+        vec.set_mutator(&PropertyMutator::new(TestMutator {
+            diff_mask: diff_mask.clone(),
+        }));
+        (vec, diff_mask)
+    }
+
+    #[test]
+    fn delta_sync() {
+        let (mut server, diff_mask) = tracked(vec![1, 2, 3, 4]);
+        let mut client = ReplicatedVec::new(vec![1, 2, 3, 4], 0);
+
+        server.set(1, 20);
+        server.push(5);
+        server.remove(0);
+        assert_eq!(diff_mask.read().unwrap().delta_base(0), Some(0));
+
+        sync(&server, &diff_mask.read().unwrap(), &mut client);
+        assert_eq!(*client, vec![20, 3, 4, 5]);
+
+        diff_mask.write().unwrap().clear();
+        server.pop();
+        server.pop();
+        *server.get_mut(0).unwrap() = 7;
+
+        sync(&server, &diff_mask.read().unwrap(), &mut client);
+        assert_eq!(*client, vec![7, 3]);
+    }
+
+    #[test]
+    fn delta_only_sends_changes() {
+        let (mut server, diff_mask) = tracked((0..100).collect());
+
+        server.set(50, 1000);
+
+        let mut delta_writer = BitWriter::default();
+        server.write_update(&diff_mask.read().unwrap(), &mut delta_writer);
+
+        let mut full_writer = BitWriter::default();
+        server.write_update(&DiffMask::new(1), &mut full_writer);
+
+        assert!(delta_writer.bit_count() < full_writer.bit_count() / 10);
+    }
+
+    #[test]
+    fn full_resync() {
+        let (mut server, diff_mask) = tracked(vec![1, 2, 3]);
+        let mut client = ReplicatedVec::new(vec![9], 0);
+
+        server.push(4);
+        // a plain change means the whole list must be resent
+        diff_mask.write().unwrap().set_bit(0, true);
+
+        sync(&server, &diff_mask.read().unwrap(), &mut client);
+        assert_eq!(*client, vec![1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,12 @@
+/// Limits how often updates to a Component are sent to each Client
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum UpdateRate {
+    /// Updates are sent as soon as changes are detected
+    #[default]
+    Immediate,
+    /// Updates are sent at most once every given number of Server Ticks.
+    /// Behaves as `Immediate` if the Server has no Tick interval configured.
+    Ticks(u16),
+    /// Updates are sent at most once every given number of milliseconds
+    Millis(u32),
+}
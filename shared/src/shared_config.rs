@@ -5,7 +5,7 @@ use naia_socket_shared::SocketConfig;
 use crate::{
     connection::compression_config::CompressionConfig,
     messages::channel_config::{ChannelConfig, ChannelIndex, DefaultChannels},
-    Channel,
+    Channel, NetEntityWidth,
 };
 
 /// Contains Config properties which will be shared by Server and Client
@@ -19,6 +19,9 @@ pub struct SharedConfig<C: ChannelIndex> {
     pub tick_interval: Option<Duration>,
     /// Configuration used to control compression parameters
     pub compression: Option<CompressionConfig>,
+    /// How many Entities can be in scope for a single connection at once.
+    /// Defaults to `NetEntityWidth::U16`
+    pub net_entity_width: NetEntityWidth,
 }
 
 impl<C: ChannelIndex> SharedConfig<C> {
@@ -35,6 +38,7 @@ impl<C: ChannelIndex> SharedConfig<C> {
             channel: channel_config,
             tick_interval,
             compression,
+            net_entity_width: NetEntityWidth::default(),
         }
     }
 }
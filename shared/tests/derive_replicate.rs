@@ -1,10 +1,22 @@
 mod some_protocol {
-    use super::some_replica::StringHolder;
+    use super::{
+        some_codec_replica::Transform, some_collection_replica::Inventory,
+        some_entity_replica::Squad, some_nested_replica::Unit, some_owned_replica::Weapon,
+        some_replica::StringHolder, some_slow_replica::SlowStringHolder,
+        some_static_replica::StaticString,
+    };
     use naia_shared::Protocolize;
 
     #[derive(Protocolize)]
     pub enum SomeProtocol {
         StringHolder(StringHolder),
+        SlowStringHolder(SlowStringHolder),
+        StaticString(StaticString),
+        Unit(Unit),
+        Inventory(Inventory),
+        Transform(Transform),
+        Squad(Squad),
+        Weapon(Weapon),
     }
 }
 
@@ -25,13 +37,286 @@ mod some_replica {
     }
 }
 
+mod some_slow_replica {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    #[replicate(update_ticks = 4)]
+    pub struct SlowStringHolder {
+        pub string: Property<String>,
+    }
+
+    impl SlowStringHolder {
+        pub fn new(string: &str) -> Self {
+            return SlowStringHolder::new_complete(string.to_string());
+        }
+    }
+}
+
+mod some_static_replica {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    #[replicate(static)]
+    pub struct StaticString {
+        pub string: Property<String>,
+    }
+
+    impl StaticString {
+        pub fn new(string: &str) -> Self {
+            StaticString::new_complete(string.to_string())
+        }
+    }
+}
+
+mod some_nested {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    #[replicate(nested)]
+    pub struct Position {
+        pub x: Property<i16>,
+        pub y: Property<i16>,
+    }
+
+    impl Position {
+        pub fn new(x: i16, y: i16) -> Self {
+            Position::new_complete(x, y)
+        }
+    }
+}
+
+mod some_nested_replica {
+    use super::some_nested::Position;
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Unit {
+        pub name: Property<String>,
+        pub position: Position,
+        pub health: Property<u8>,
+    }
+
+    impl Unit {
+        pub fn new(name: &str, x: i16, y: i16, health: u8) -> Self {
+            Unit::new_complete(name.to_string(), Position::new(x, y), health)
+        }
+    }
+}
+
+mod some_collection_replica {
+    use std::collections::HashMap;
+
+    use naia_shared::{Replicate, ReplicatedMap, ReplicatedVec};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Inventory {
+        pub slots: ReplicatedVec<u16>,
+        pub buffs: ReplicatedMap<u8, u32>,
+    }
+
+    impl Inventory {
+        pub fn new(slots: Vec<u16>, buffs: HashMap<u8, u32>) -> Self {
+            Inventory::new_complete(slots, buffs)
+        }
+    }
+}
+
+mod some_third_party {
+    // stands in for a type from another crate, which can't implement Serde
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct Vec2 {
+        pub x: f32,
+        pub y: f32,
+    }
+}
+
+mod some_codec {
+    use naia_shared::{
+        serde::{BitReader, BitWrite, Serde, SerdeErr},
+        PropertyCodec,
+    };
+
+    use super::some_third_party::Vec2;
+
+    pub struct Vec2Codec;
+
+    impl PropertyCodec<Vec2> for Vec2Codec {
+        fn write(value: &Vec2, writer: &mut dyn BitWrite) {
+            value.x.ser(writer);
+            value.y.ser(writer);
+        }
+
+        fn read(reader: &mut BitReader) -> Result<Vec2, SerdeErr> {
+            Ok(Vec2 {
+                x: f32::de(reader)?,
+                y: f32::de(reader)?,
+            })
+        }
+    }
+}
+
+mod some_codec_replica {
+    use naia_shared::{Property, Replicate};
+
+    use super::{some_codec::Vec2Codec, some_third_party::Vec2};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Transform {
+        #[property(with = "Vec2Codec")]
+        pub position: Property<Vec2>,
+        pub label: Property<String>,
+    }
+
+    impl Transform {
+        pub fn new(x: f32, y: f32, label: &str) -> Self {
+            Transform::new_complete(Vec2 { x, y }, label.to_string())
+        }
+    }
+}
+
+mod some_entity_replica {
+    use naia_shared::{EntityListProperty, EntitySetProperty, Replicate};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Squad {
+        pub members: EntityListProperty,
+        pub tagged: EntitySetProperty,
+    }
+
+    impl Squad {
+        pub fn new() -> Self {
+            Squad::new_complete()
+        }
+    }
+}
+
+mod some_owned_replica {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Weapon {
+        pub name: Property<String>,
+        #[property(owner_only)]
+        pub ammo: Property<u16>,
+    }
+
+    impl Weapon {
+        pub fn new(name: &str, ammo: u16) -> Self {
+            Weapon::new_complete(name.to_string(), ammo)
+        }
+    }
+}
+
+mod scoped_entity_converter {
+    use std::collections::HashSet;
+
+    use naia_shared::{
+        BigMapKey, EntityHandle, EntityHandleConverter, NetEntity, NetEntityHandleConverter,
+    };
+
+    // Converts between handles & NetEntities of the same value, for only those
+    // Entities which are in scope
+    pub struct ScopedEntityConverter {
+        pub in_scope: HashSet<u16>,
+    }
+
+    impl NetEntityHandleConverter for ScopedEntityConverter {
+        fn handle_to_net_entity(&self, entity_handle: &EntityHandle) -> NetEntity {
+            self.try_handle_to_net_entity(entity_handle).unwrap()
+        }
+
+        fn net_entity_to_handle(&self, net_entity: &NetEntity) -> EntityHandle {
+            self.try_net_entity_to_handle(net_entity).unwrap()
+        }
+
+        fn try_handle_to_net_entity(&self, entity_handle: &EntityHandle) -> Option<NetEntity> {
+            let value = entity_handle.to_u64() as u16;
+            if self.in_scope.contains(&value) {
+                Some(NetEntity::from(value))
+            } else {
+                None
+            }
+        }
+
+        fn try_net_entity_to_handle(&self, net_entity: &NetEntity) -> Option<EntityHandle> {
+            let value = u32::from(*net_entity) as u16;
+            if self.in_scope.contains(&value) {
+                Some(EntityHandle::from_u64(value as u64))
+            } else {
+                None
+            }
+        }
+    }
+
+    impl EntityHandleConverter<u16> for ScopedEntityConverter {
+        fn handle_to_entity(&self, entity_handle: &EntityHandle) -> u16 {
+            entity_handle.to_u64() as u16
+        }
+
+        fn entity_to_handle(&self, entity: &u16) -> EntityHandle {
+            EntityHandle::from_u64(*entity as u64)
+        }
+    }
+}
+
+mod diff_mask_mutator {
+    use std::sync::{Arc, RwLock};
+
+    use naia_shared::{DiffMask, PropertyMutate};
+
+    #[derive(Clone)]
+    pub struct DiffMaskMutator {
+        pub diff_mask: Arc<RwLock<DiffMask>>,
+    }
+
+    impl PropertyMutate for DiffMaskMutator {
+        fn mutate(&mut self, property_index: u8) {
+            self.diff_mask
+                .write()
+                .unwrap()
+                .set_bit(property_index, true);
+        }
+
+        fn mutate_delta(&mut self, property_index: u8, base_version: u64) {
+            self.diff_mask
+                .write()
+                .unwrap()
+                .set_delta_bit(property_index, base_version);
+        }
+    }
+}
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+
 use naia_shared::{
     serde::{BitReader, BitWriter},
-    FakeEntityConverter, Protocolize,
+    DiffMask, FakeEntityConverter, PropertyMutator, Protocolize, ReplicateSafe, StaticMutator,
+    UpdateRate,
 };
 
+use diff_mask_mutator::DiffMaskMutator;
+use scoped_entity_converter::ScopedEntityConverter;
+
+use some_codec_replica::Transform;
+use some_collection_replica::Inventory;
+use some_entity_replica::Squad;
+use some_nested_replica::Unit;
+use some_owned_replica::Weapon;
 use some_protocol::SomeProtocol;
 use some_replica::StringHolder;
+use some_slow_replica::SlowStringHolder;
+use some_static_replica::StaticString;
 
 #[test]
 fn read_write_protocol() {
@@ -48,7 +333,7 @@ fn read_write_protocol() {
 
     let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-    let out_1 = SomeProtocol::read(&mut reader, &FakeEntityConverter);
+    let out_1 = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
 
     let typed_in_1 = in_1.cast_ref::<StringHolder>().unwrap();
     let typed_out_1 = out_1.cast_ref::<StringHolder>().unwrap();
@@ -59,3 +344,348 @@ fn read_write_protocol() {
     assert_eq!(*typed_out_1.string_1, "hello world".to_string());
     assert_eq!(*typed_out_1.string_2, "goodbye world".to_string());
 }
+
+#[test]
+fn update_rate() {
+    let immediate = StringHolder::new("hello world", "goodbye world");
+    let slow = SlowStringHolder::new("hello world");
+
+    assert_eq!(immediate.update_rate(), UpdateRate::Immediate);
+    assert_eq!(slow.update_rate(), UpdateRate::Ticks(4));
+}
+
+#[test]
+fn is_static() {
+    let dynamic = StringHolder::new("hello world", "goodbye world");
+    let fixed = StaticString::new("hello world");
+
+    assert!(!dynamic.is_static());
+    assert!(fixed.is_static());
+}
+
+#[test]
+#[should_panic]
+fn static_mutation_panics() {
+    let mut fixed = StaticString::new("hello world");
+    fixed.set_mutator(&PropertyMutator::new(StaticMutator));
+
+    *fixed.string = "goodbye world".to_string();
+}
+
+#[test]
+fn nested_read_write() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeProtocol::Unit(Unit::new("knight", -4, 12, 100));
+
+    in_1.write(&mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+
+    let typed_out_1 = out_1.cast_ref::<Unit>().unwrap();
+    assert_eq!(*typed_out_1.name, "knight".to_string());
+    assert_eq!(*typed_out_1.position.x, -4);
+    assert_eq!(*typed_out_1.position.y, 12);
+    assert_eq!(*typed_out_1.health, 100);
+}
+
+#[test]
+fn nested_write_update() {
+    let mut server_unit = Unit::new("knight", -4, 12, 100);
+    let mut client_unit = server_unit.clone();
+
+    assert_eq!(server_unit.diff_mask_size(), 1);
+
+    let diff_mask = Arc::new(RwLock::new(DiffMask::new(server_unit.diff_mask_size())));
+    server_unit.set_mutator(&PropertyMutator::new(DiffMaskMutator {
+        diff_mask: diff_mask.clone(),
+    }));
+
+    // nested Properties take up the bits following the ones before them
+    *server_unit.position.y = 20;
+    *server_unit.health = 90;
+    {
+        let diff_mask = diff_mask.read().unwrap();
+        assert!(!diff_mask.bit(0).unwrap());
+        assert!(!diff_mask.bit(1).unwrap());
+        assert!(diff_mask.bit(2).unwrap());
+        assert!(diff_mask.bit(3).unwrap());
+    }
+
+    // Write
+    let mut writer = BitWriter::default();
+
+    server_unit.write_update(
+        &diff_mask.read().unwrap(),
+        &mut writer,
+        &FakeEntityConverter,
+    );
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let update = Unit::read_create_update(&mut reader).unwrap();
+    client_unit.read_apply_update(&FakeEntityConverter, update);
+
+    assert_eq!(*client_unit.name, "knight".to_string());
+    assert_eq!(*client_unit.position.x, -4);
+    assert_eq!(*client_unit.position.y, 20);
+    assert_eq!(*client_unit.health, 90);
+}
+
+#[test]
+fn collection_write_update() {
+    let buffs: HashMap<u8, u32> = (0..4).map(|key| (key, key as u32 * 10)).collect();
+    let mut server_inventory = Inventory::new((0..40).collect(), buffs);
+    let mut client_inventory = server_inventory.clone();
+
+    let diff_mask = Arc::new(RwLock::new(DiffMask::new(
+        server_inventory.diff_mask_size(),
+    )));
+    server_inventory.set_mutator(&PropertyMutator::new(DiffMaskMutator {
+        diff_mask: diff_mask.clone(),
+    }));
+
+    server_inventory.slots.set(12, 1200);
+    server_inventory.slots.push(40);
+    server_inventory.buffs.remove(&1);
+    server_inventory.buffs.insert(7, 70);
+
+    // Write
+    let mut writer = BitWriter::default();
+
+    server_inventory.write_update(
+        &diff_mask.read().unwrap(),
+        &mut writer,
+        &FakeEntityConverter,
+    );
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // only the changes are sent, rather than every slot
+    assert!(buffer_length < 20);
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let update = Inventory::read_create_update(&mut reader).unwrap();
+    client_inventory.read_apply_update(&FakeEntityConverter, update);
+
+    assert!(client_inventory.slots.equals(&server_inventory.slots));
+    assert!(client_inventory.buffs.equals(&server_inventory.buffs));
+    assert_eq!(client_inventory.slots[12], 1200);
+    assert_eq!(client_inventory.slots.len(), 41);
+    assert_eq!(client_inventory.buffs.get(&1), None);
+    assert_eq!(client_inventory.buffs.get(&7), Some(&70));
+}
+
+#[test]
+fn codec_read_write() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeProtocol::Transform(Transform::new(1.5, -2.0, "tree"));
+
+    in_1.write(&mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+
+    let typed_out_1 = out_1.cast_ref::<Transform>().unwrap();
+    assert_eq!(typed_out_1.position.x, 1.5);
+    assert_eq!(typed_out_1.position.y, -2.0);
+    assert_eq!(*typed_out_1.label, "tree".to_string());
+}
+
+#[test]
+fn codec_write_update() {
+    let mut server_transform = Transform::new(1.5, -2.0, "tree");
+    let mut client_transform = server_transform.clone();
+
+    let diff_mask = Arc::new(RwLock::new(DiffMask::new(
+        server_transform.diff_mask_size(),
+    )));
+    server_transform.set_mutator(&PropertyMutator::new(DiffMaskMutator {
+        diff_mask: diff_mask.clone(),
+    }));
+
+    server_transform.position.x = 8.0;
+
+    // Write
+    let mut writer = BitWriter::default();
+
+    server_transform.write_update(
+        &diff_mask.read().unwrap(),
+        &mut writer,
+        &FakeEntityConverter,
+    );
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let update = Transform::read_create_update(&mut reader).unwrap();
+    client_transform.read_apply_update(&FakeEntityConverter, update);
+
+    assert!(client_transform.position.equals(&server_transform.position));
+    assert_eq!(client_transform.position.x, 8.0);
+    assert_eq!(*client_transform.label, "tree".to_string());
+}
+
+#[test]
+fn entity_collections_read_write() {
+    let all_in_scope = ScopedEntityConverter {
+        in_scope: HashSet::from([1, 2, 3]),
+    };
+    let mut in_1 = Squad::new();
+    in_1.members.set(&all_in_scope, &[3, 1, 2]);
+    in_1.tagged.set(&all_in_scope, &HashSet::from([2, 3]));
+
+    assert!(in_1.has_entity_properties());
+    assert_eq!(in_1.entities().len(), 5);
+
+    // Write, with Entity 2 out of scope
+    let mut writer = BitWriter::default();
+
+    let server_converter = ScopedEntityConverter {
+        in_scope: HashSet::from([1, 3]),
+    };
+    SomeProtocol::Squad(in_1).write(&mut writer, &server_converter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = SomeProtocol::read(&mut reader, &all_in_scope).unwrap();
+
+    let typed_out_1 = out_1.cast_ref::<Squad>().unwrap();
+    assert_eq!(typed_out_1.members.get(&all_in_scope), vec![3, 1]);
+    assert_eq!(typed_out_1.tagged.get(&all_in_scope), HashSet::from([3]));
+}
+
+#[test]
+fn entity_collections_write_update() {
+    let converter = ScopedEntityConverter {
+        in_scope: HashSet::from([1, 2, 3]),
+    };
+    let mut server_squad = Squad::new();
+    server_squad.members.push(&converter, &1);
+    let mut client_squad = server_squad.clone();
+
+    let diff_mask = Arc::new(RwLock::new(DiffMask::new(server_squad.diff_mask_size())));
+    server_squad.set_mutator(&PropertyMutator::new(DiffMaskMutator {
+        diff_mask: diff_mask.clone(),
+    }));
+
+    server_squad.members.push(&converter, &2);
+    assert!(server_squad.tagged.insert(&converter, &3));
+    assert!(!server_squad.tagged.insert(&converter, &3));
+
+    // Write
+    let mut writer = BitWriter::default();
+
+    server_squad.write_update(&diff_mask.read().unwrap(), &mut writer, &converter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let update = Squad::read_create_update(&mut reader).unwrap();
+    client_squad.read_apply_update(&converter, update);
+
+    assert!(client_squad.members.equals(&server_squad.members));
+    assert!(client_squad.tagged.equals(&server_squad.tagged));
+    assert_eq!(client_squad.members.get(&converter), vec![1, 2]);
+    assert!(client_squad.tagged.contains(&converter, &3));
+}
+
+#[test]
+fn owner_only_read_write() {
+    let weapon = Weapon::new("rifle", 30);
+
+    for is_owner in [true, false] {
+        // Write
+        let mut writer = BitWriter::default();
+
+        weapon.write_for_user(&mut writer, &FakeEntityConverter, is_owner);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        let protocol = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+        let out_weapon = protocol.cast_ref::<Weapon>().unwrap();
+
+        assert_eq!(*out_weapon.name, "rifle".to_string());
+        if is_owner {
+            assert_eq!(*out_weapon.ammo, 30);
+        } else {
+            assert_eq!(*out_weapon.ammo, 0);
+        }
+    }
+}
+
+#[test]
+fn owner_only_write_update() {
+    let mut server_weapon = Weapon::new("rifle", 30);
+    let mut owner_weapon = server_weapon.clone();
+    let mut other_weapon = Weapon::new("rifle", 0);
+
+    let diff_mask = Arc::new(RwLock::new(DiffMask::new(server_weapon.diff_mask_size())));
+    server_weapon.set_mutator(&PropertyMutator::new(DiffMaskMutator {
+        diff_mask: diff_mask.clone(),
+    }));
+
+    *server_weapon.name = "shotgun".to_string();
+    *server_weapon.ammo = 8;
+
+    for (is_owner, client_weapon) in [(true, &mut owner_weapon), (false, &mut other_weapon)] {
+        // Write
+        let mut writer = BitWriter::default();
+
+        server_weapon.write_update_for_user(
+            &diff_mask.read().unwrap(),
+            &mut writer,
+            &FakeEntityConverter,
+            is_owner,
+        );
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        let update = Weapon::read_create_update(&mut reader).unwrap();
+        client_weapon.read_apply_update(&FakeEntityConverter, update);
+    }
+
+    assert_eq!(*owner_weapon.name, "shotgun".to_string());
+    assert_eq!(*owner_weapon.ammo, 8);
+    assert_eq!(*other_weapon.name, "shotgun".to_string());
+    assert_eq!(*other_weapon.ammo, 0);
+}
@@ -0,0 +1,37 @@
+use naia_shared::{Property, Replicate};
+
+use self::corrupt_codec::CorruptCodec;
+
+/// A Component which is written as normal, but can never be read back,
+/// standing in for a remote host which sends malformed data
+#[derive(Replicate)]
+#[protocol_path = "crate::protocol::Protocol"]
+pub struct Corrupt {
+    #[property(with = "CorruptCodec")]
+    pub value: Property<u8>,
+}
+
+impl Corrupt {
+    pub fn new(value: u8) -> Self {
+        Corrupt::new_complete(value)
+    }
+}
+
+mod corrupt_codec {
+    use naia_shared::{
+        serde::{BitReader, BitWrite, Serde, SerdeErr},
+        PropertyCodec,
+    };
+
+    pub struct CorruptCodec;
+
+    impl PropertyCodec<u8> for CorruptCodec {
+        fn write(value: &u8, writer: &mut dyn BitWrite) {
+            value.ser(writer);
+        }
+
+        fn read(_: &mut BitReader) -> Result<u8, SerdeErr> {
+            Err(SerdeErr {})
+        }
+    }
+}
@@ -1,5 +1,15 @@
 mod auth;
+mod corrupt;
+mod loopback;
+mod payload;
+mod position;
 mod protocol;
+mod squad;
 
 pub use auth::Auth;
+pub use corrupt::Corrupt;
+pub use loopback::Loopback;
+pub use payload::Payload;
+pub use position::Position;
 pub use protocol::{Protocol, ProtocolKind};
+pub use squad::Squad;
@@ -0,0 +1,179 @@
+use std::{collections::HashSet, thread::sleep, time::Duration};
+
+use naia_client::{Client, ClientConfig, Event as ClientEvent, NaiaClientError};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    Event as ServerEvent, NaiaServerError, RoomKey, Server, ServerAddrs, ServerConfig, UserKey,
+};
+use naia_shared::{ChannelConfig, DefaultChannels, SharedConfig, SocketConfig};
+
+use crate::{position::Position, protocol::Protocol};
+
+pub type TestServer = Server<Protocol, Entity, DefaultChannels>;
+pub type TestClient = Client<Protocol, Entity, DefaultChannels>;
+pub type ServerEventResult =
+    Result<ServerEvent<Protocol, Entity, DefaultChannels>, NaiaServerError>;
+pub type ClientEventResult =
+    Result<ClientEvent<Protocol, Entity, DefaultChannels>, NaiaClientError>;
+
+const STEP_DURATION: Duration = Duration::from_millis(5);
+const MAX_STEPS: usize = 1000;
+
+/// A Server and a single Client connected to it over the loopback interface,
+/// stepped together so that tests can replicate through a real connection.
+/// Every Entity added to `room_key` is in scope for the Client, apart from
+/// those in `out_of_scope`.
+pub struct Loopback {
+    pub server: TestServer,
+    pub server_world: World<Protocol>,
+    pub client: TestClient,
+    pub client_world: World<Protocol>,
+    pub room_key: RoomKey,
+    pub user_key: UserKey,
+    pub out_of_scope: HashSet<Entity>,
+    pub server_events: Vec<ServerEventResult>,
+    pub client_events: Vec<ClientEventResult>,
+}
+
+impl Loopback {
+    /// Listens on the given port, which must be unique to the test, and
+    /// connects a Client to it
+    pub fn connect(port: u16, server_config: ServerConfig) -> Self {
+        let shared_config = SharedConfig::new(
+            SocketConfig::new(None, None),
+            ChannelConfig::default(),
+            Some(Duration::from_millis(20)),
+            None,
+        );
+
+        let server_config = ServerConfig {
+            require_auth: false,
+            ..server_config
+        };
+        let mut server = TestServer::new(&server_config, &shared_config);
+        let session_addr = format!("127.0.0.1:{}", port);
+        server.listen(&ServerAddrs::new(
+            session_addr.parse().unwrap(),
+            format!("127.0.0.1:{}", port + 1).parse().unwrap(),
+            &format!("http://127.0.0.1:{}", port + 1),
+        ));
+        let room_key = server.make_room().key();
+
+        // heartbeats keep the connection alive for tests with a short timeout
+        let mut client_config = ClientConfig::default();
+        client_config.connection.heartbeat_interval = Duration::from_millis(50);
+        let mut client = TestClient::new(&client_config, &shared_config);
+        client.connect(&format!("http://{}", session_addr));
+
+        let mut server_world = World::default();
+        let mut client_world = World::default();
+        let mut user_key = None;
+        for _ in 0..MAX_STEPS {
+            for event in server.receive(server_world.proxy_mut()) {
+                if let Ok(ServerEvent::Connection(connected_user_key)) = event {
+                    server.room_mut(&room_key).add_user(&connected_user_key);
+                    user_key = Some(connected_user_key);
+                }
+            }
+            server.send_all_updates(server_world.proxy());
+            client.receive(client_world.proxy_mut());
+            if user_key.is_some() && client.is_connected() {
+                break;
+            }
+            sleep(STEP_DURATION);
+        }
+
+        Self {
+            server,
+            server_world,
+            client,
+            client_world,
+            room_key,
+            user_key: user_key.expect("Client never connected"),
+            out_of_scope: HashSet::new(),
+            server_events: Vec::new(),
+            client_events: Vec::new(),
+        }
+    }
+
+    /// Spawns an Entity on the Server with a Position, in the Room
+    pub fn spawn_position(&mut self, x: i16, y: i16) -> Entity {
+        let entity = self
+            .server
+            .spawn_entity(self.server_world.proxy_mut())
+            .insert_component(Position::new(x, y))
+            .id();
+        self.server.room_mut(&self.room_key).add_entity(&entity);
+        entity
+    }
+
+    /// Takes the Entity out of the Client's scope, or puts it back
+    pub fn set_in_scope(&mut self, entity: &Entity, in_scope: bool) {
+        if in_scope {
+            self.out_of_scope.remove(entity);
+        } else {
+            self.out_of_scope.insert(*entity);
+        }
+        self.server.recheck_entity_scope(entity);
+    }
+
+    /// Steps until the Client has spawned an Entity, returning the Client's
+    /// copy of it
+    pub fn wait_for_client_spawn(&mut self) -> Option<Entity> {
+        let already_spawned = self.client_spawned_entities().len();
+        if !self.step_until(|loopback| loopback.client_spawned_entities().len() > already_spawned) {
+            return None;
+        }
+        self.client_spawned_entities().last().copied()
+    }
+
+    fn client_spawned_entities(&self) -> Vec<Entity> {
+        self.client_events
+            .iter()
+            .filter_map(|event| match event {
+                Ok(ClientEvent::SpawnEntity(entity)) => Some(*entity),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Receives on both ends, updates scopes, and sends on both ends
+    pub fn step(&mut self) {
+        self.server_events
+            .extend(self.server.receive(self.server_world.proxy_mut()));
+
+        for (_, user_key, entity) in self.server.scope_checks() {
+            if self.out_of_scope.contains(&entity) {
+                self.server.user_scope(&user_key).exclude(&entity);
+            } else {
+                self.server.user_scope(&user_key).include(&entity);
+            }
+        }
+        self.server.send_all_updates(self.server_world.proxy());
+
+        if self.client.is_connected() {
+            self.client_events
+                .extend(self.client.receive(self.client_world.proxy_mut()));
+        }
+
+        sleep(STEP_DURATION);
+    }
+
+    /// Steps until the condition holds, returning whether it ever did
+    pub fn step_until(&mut self, mut condition: impl FnMut(&mut Self) -> bool) -> bool {
+        for _ in 0..MAX_STEPS {
+            self.step();
+            if condition(self) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Steps for the given number of times, whatever happens
+    pub fn step_for(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use naia_shared::{Property, Replicate};
+
+#[derive(Replicate)]
+#[protocol_path = "crate::protocol::Protocol"]
+pub struct Payload {
+    pub bytes: Property<Vec<u8>>,
+}
+
+impl Payload {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Payload::new_complete(bytes)
+    }
+}
@@ -1,8 +1,12 @@
 use naia_shared::Protocolize;
 
-use super::auth::Auth;
+use super::{auth::Auth, corrupt::Corrupt, payload::Payload, position::Position, squad::Squad};
 
 #[derive(Protocolize)]
 pub enum Protocol {
     Auth(Auth),
+    Position(Position),
+    Payload(Payload),
+    Squad(Squad),
+    Corrupt(Corrupt),
 }
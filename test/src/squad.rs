@@ -0,0 +1,7 @@
+use naia_shared::{EntityListProperty, Replicate};
+
+#[derive(Replicate)]
+#[protocol_path = "crate::protocol::Protocol"]
+pub struct Squad {
+    pub members: EntityListProperty,
+}
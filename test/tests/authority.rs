@@ -0,0 +1,105 @@
+use naia_client::Event as ClientEvent;
+use naia_demo_world::Entity;
+use naia_server::{Event, ServerConfig};
+use naia_shared::{WorldMutType, WorldRefType};
+use naia_test::{Loopback, Payload, Position, ProtocolKind};
+
+fn server_x(loopback: &Loopback, entity: &Entity) -> i16 {
+    *loopback
+        .server_world
+        .proxy()
+        .component::<Position>(entity)
+        .unwrap()
+        .x
+}
+
+fn server_bytes(loopback: &Loopback, entity: &Entity) -> Vec<u8> {
+    (*loopback
+        .server_world
+        .proxy()
+        .component::<Payload>(entity)
+        .unwrap()
+        .bytes)
+        .clone()
+}
+
+#[test]
+fn component_authority_only_accepts_updates_to_that_component() {
+    let mut loopback = Loopback::connect(14411, ServerConfig::default());
+
+    let entity = loopback.spawn_position(1, 2);
+    loopback
+        .server
+        .entity_mut(loopback.server_world.proxy_mut(), &entity)
+        .insert_component(Payload::new(vec![1]));
+    let client_entity = loopback.wait_for_client_spawn().unwrap();
+    assert!(loopback.step_until(|loopback| {
+        loopback
+            .client_world
+            .proxy()
+            .has_component::<Payload>(&client_entity)
+    }));
+
+    let user_key = loopback.user_key;
+    loopback
+        .server
+        .entity_mut(loopback.server_world.proxy_mut(), &entity)
+        .give_component_authority::<Position>(&user_key);
+    assert_eq!(
+        loopback.server.component_authority::<Position>(&entity),
+        Some(user_key)
+    );
+    assert_eq!(
+        loopback.server.component_authority::<Payload>(&entity),
+        None
+    );
+
+    assert!(loopback.step_until(|loopback| {
+        loopback.client_events.iter().any(|event| {
+            matches!(
+                event,
+                Ok(ClientEvent::ComponentAuthorityGranted(
+                    _,
+                    ProtocolKind::Position
+                ))
+            )
+        })
+    }));
+    assert!(loopback
+        .client
+        .has_component_authority::<Position>(&client_entity));
+    assert!(!loopback
+        .client
+        .has_component_authority::<Payload>(&client_entity));
+    assert!(!loopback.client.has_authority(&client_entity));
+
+    // the Client changes both Components, but only has authority over one
+    {
+        let mut world = loopback.client_world.proxy_mut();
+        *world.component_mut::<Position>(&client_entity).unwrap().x = 7;
+        *world
+            .component_mut::<Payload>(&client_entity)
+            .unwrap()
+            .bytes = vec![9];
+    }
+
+    assert!(loopback.step_until(|loopback| server_x(loopback, &entity) == 7));
+    loopback.step_for(20);
+    assert_eq!(server_bytes(&loopback, &entity), vec![1]);
+
+    loopback
+        .server
+        .entity_mut(loopback.server_world.proxy_mut(), &entity)
+        .take_component_authority::<Position>();
+
+    assert!(loopback.step_until(|loopback| {
+        !loopback
+            .client
+            .has_component_authority::<Position>(&client_entity)
+    }));
+    assert!(loopback.server_events.iter().any(|event| matches!(
+        event,
+        Ok(Event::ComponentAuthorityRevoked(revoked_user_key, revoked_entity, ProtocolKind::Position))
+            if *revoked_user_key == user_key && *revoked_entity == entity
+    )));
+}
@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use naia_server::{EntityOwner, Event, NaiaServerError, ServerConfig};
+use naia_shared::WorldRefType;
+use naia_test::{Corrupt, Loopback, Position};
+
+#[test]
+fn client_entities_pass_to_server_on_disconnect() {
+    let mut server_config = ServerConfig::default();
+    server_config.connection.disconnection_timeout_duration = Duration::from_millis(500);
+    let mut loopback = Loopback::connect(14301, server_config);
+
+    loopback
+        .client
+        .spawn_entity(loopback.client_world.proxy_mut())
+        .insert_component(Position::new(3, 4));
+
+    assert!(loopback.step_until(|loopback| {
+        loopback
+            .server_events
+            .iter()
+            .any(|event| matches!(event, Ok(Event::SpawnEntity(_, _))))
+    }));
+    let server_entity = loopback
+        .server_events
+        .iter()
+        .find_map(|event| match event {
+            Ok(Event::SpawnEntity(_, entity)) => Some(*entity),
+            _ => None,
+        })
+        .unwrap();
+    let user_key = loopback.user_key;
+    assert!(loopback.server.entity_owner(&server_entity) == Some(EntityOwner::Client(user_key)));
+
+    // the Server times the Client out once it stops hearing from it
+    loopback.client.disconnect();
+
+    assert!(loopback.step_until(|loopback| {
+        loopback
+            .server_events
+            .iter()
+            .any(|event| matches!(event, Ok(Event::EntityOrphaned(_, _))))
+    }));
+    assert!(loopback.server_events.iter().any(|event| matches!(
+        event,
+        Ok(Event::EntityOrphaned(orphaned_user_key, entity))
+            if *orphaned_user_key == user_key && *entity == server_entity
+    )));
+    assert!(loopback.server.entity_owner(&server_entity) == Some(EntityOwner::Server));
+}
+
+#[test]
+fn client_sending_malformed_component_is_disconnected() {
+    let mut loopback = Loopback::connect(14391, ServerConfig::default());
+
+    // the Server can't read the Component back, so the Client can't be trusted
+    loopback
+        .client
+        .spawn_entity(loopback.client_world.proxy_mut())
+        .insert_component(Corrupt::new(7));
+
+    let user_key = loopback.user_key;
+    assert!(loopback.step_until(|loopback| {
+        loopback.server_events.iter().any(|event| {
+            matches!(event, Ok(Event::Disconnection(disconnected_user_key, _))
+                if *disconnected_user_key == user_key)
+        })
+    }));
+    assert!(!loopback
+        .server_events
+        .iter()
+        .any(|event| matches!(event, Ok(Event::SpawnEntity(_, _)))));
+}
+
+#[test]
+fn client_entities_over_limit_are_not_spawned() {
+    let server_config = ServerConfig {
+        max_client_entities: Some(1),
+        ..ServerConfig::default()
+    };
+    let mut loopback = Loopback::connect(14401, server_config);
+
+    for x in 0..2 {
+        loopback
+            .client
+            .spawn_entity(loopback.client_world.proxy_mut())
+            .insert_component(Position::new(x, 0));
+    }
+
+    let user_key = loopback.user_key;
+    assert!(loopback.step_until(|loopback| {
+        loopback.server_events.iter().any(|event| {
+            matches!(event, Err(NaiaServerError::ClientEntitiesExhausted(limited_user_key))
+                if *limited_user_key == user_key)
+        })
+    }));
+    loopback.step_for(10);
+
+    let spawned_count = loopback
+        .server_events
+        .iter()
+        .filter(|event| matches!(event, Ok(Event::SpawnEntity(_, _))))
+        .count();
+    assert_eq!(spawned_count, 1);
+    assert_eq!(loopback.server_world.proxy().entities().len(), 1);
+}
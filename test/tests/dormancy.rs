@@ -0,0 +1,43 @@
+use naia_server::ServerConfig;
+use naia_shared::WorldRefType;
+use naia_test::{Loopback, Position};
+
+fn client_x(loopback: &Loopback) -> Option<i16> {
+    let world = loopback.client_world.proxy();
+    world.entities().iter().find_map(|entity| {
+        world
+            .component::<Position>(entity)
+            .map(|position| *position.x)
+    })
+}
+
+#[test]
+fn dormant_entity_changes_flush_on_wake() {
+    let mut loopback = Loopback::connect(14361, ServerConfig::default());
+
+    let entity = loopback.spawn_position(1, 2);
+    assert!(loopback.step_until(|loopback| client_x(loopback) == Some(1)));
+
+    loopback
+        .server
+        .entity_mut(loopback.server_world.proxy_mut(), &entity)
+        .set_dormant(true);
+    for x in 2..=5 {
+        *loopback
+            .server
+            .entity_mut(loopback.server_world.proxy_mut(), &entity)
+            .component::<Position>()
+            .unwrap()
+            .x = x;
+        loopback.step_for(10);
+    }
+
+    // no update reaches the Client while the Entity is dormant
+    assert_eq!(client_x(&loopback), Some(1));
+
+    loopback
+        .server
+        .entity_mut(loopback.server_world.proxy_mut(), &entity)
+        .set_dormant(false);
+    assert!(loopback.step_until(|loopback| client_x(loopback) == Some(5)));
+}
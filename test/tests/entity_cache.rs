@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use naia_server::ServerConfig;
+use naia_test::{Auth, Loopback, Position};
+
+#[test]
+fn component_changes_while_hidden_arrive_on_reveal() {
+    let server_config = ServerConfig {
+        entity_cache_duration: Some(Duration::from_secs(30)),
+        ..Default::default()
+    };
+    let mut loopback = Loopback::connect(14311, server_config);
+
+    let server_entity = loopback.spawn_position(1, 2);
+    let client_entity = loopback.wait_for_client_spawn().unwrap();
+
+    // leaving scope hides the Client's copy rather than despawning it
+    loopback.set_in_scope(&server_entity, false);
+    assert!(loopback.step_until(|loopback| loopback.client.entity_is_hidden(&client_entity)));
+
+    let mut server_entity_mut = loopback
+        .server
+        .entity_mut(loopback.server_world.proxy_mut(), &server_entity);
+    server_entity_mut.insert_component(Auth::new("charlie", "12345"));
+    server_entity_mut.remove_component::<Position>();
+
+    // changes made while out of scope are held back from the hidden copy
+    loopback.step_for(20);
+    let client_entity_ref = loopback
+        .client
+        .entity(loopback.client_world.proxy(), &client_entity);
+    assert!(!client_entity_ref.has_component::<Auth>());
+    assert!(client_entity_ref.has_component::<Position>());
+    assert!(loopback.client.entity_is_hidden(&client_entity));
+
+    loopback.set_in_scope(&server_entity, true);
+    assert!(loopback.step_until(|loopback| {
+        let client_entity_ref = loopback
+            .client
+            .entity(loopback.client_world.proxy(), &client_entity);
+        !loopback.client.entity_is_hidden(&client_entity)
+            && client_entity_ref.has_component::<Auth>()
+            && !client_entity_ref.has_component::<Position>()
+    }));
+}
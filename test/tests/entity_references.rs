@@ -0,0 +1,44 @@
+use naia_server::ServerConfig;
+use naia_shared::WorldRefType;
+use naia_test::{Loopback, Squad};
+
+fn client_squad_size(loopback: &Loopback) -> Option<usize> {
+    let world = loopback.client_world.proxy();
+    world.entities().iter().find_map(|entity| {
+        world
+            .component::<Squad>(entity)
+            .map(|squad| squad.members.get(&loopback.client).len())
+    })
+}
+
+#[test]
+fn entity_list_member_arrives_when_it_enters_scope() {
+    let mut loopback = Loopback::connect(14371, ServerConfig::default());
+
+    let visible_member = loopback.spawn_position(1, 2);
+    let hidden_member = loopback.spawn_position(3, 4);
+    loopback.set_in_scope(&hidden_member, false);
+
+    let mut squad = Squad::new_complete();
+    squad
+        .members
+        .set(&loopback.server, &[visible_member, hidden_member]);
+    let squad_entity = loopback
+        .server
+        .spawn_entity(loopback.server_world.proxy_mut())
+        .insert_component(squad)
+        .id();
+    loopback
+        .server
+        .room_mut(&loopback.room_key)
+        .add_entity(&squad_entity);
+
+    // the member out of scope is left out
+    assert!(loopback.step_until(|loopback| client_squad_size(loopback) == Some(1)));
+    loopback.step_for(10);
+    assert_eq!(client_squad_size(&loopback), Some(1));
+
+    // and sent once it enters scope, without the Squad having changed
+    loopback.set_in_scope(&hidden_member, true);
+    assert!(loopback.step_until(|loopback| client_squad_size(loopback) == Some(2)));
+}
@@ -0,0 +1,50 @@
+use naia_client::Event as ClientEvent;
+use naia_server::ServerConfig;
+use naia_shared::DefaultChannels;
+use naia_test::{Loopback, Payload, Protocol};
+
+#[test]
+fn message_larger_than_a_packet_arrives_whole() {
+    let mut loopback = Loopback::connect(14341, ServerConfig::default());
+
+    let bytes: Vec<u8> = (0..5000).map(|index| (index % 251) as u8).collect();
+    let user_key = loopback.user_key;
+    loopback.server.send_message(
+        &user_key,
+        DefaultChannels::OrderedReliable,
+        &Payload::new(bytes.clone()),
+    );
+
+    assert!(loopback.step_until(|loopback| {
+        loopback.client_events.iter().any(|event| match event {
+            Ok(ClientEvent::Message(_, Protocol::Payload(payload))) => *payload.bytes == bytes,
+            _ => false,
+        })
+    }));
+}
+
+#[test]
+#[should_panic(expected = "Message is too large for this Channel")]
+fn server_message_over_max_size_panics() {
+    let mut loopback = Loopback::connect(14351, ServerConfig::default());
+
+    // the default max_message_size is 1 MiB
+    let message = Payload::new(vec![0; 2 * 1024 * 1024]);
+    let user_key = loopback.user_key;
+
+    loopback
+        .server
+        .send_message(&user_key, DefaultChannels::UnorderedReliable, &message);
+}
+
+#[test]
+#[should_panic(expected = "Message is too large for this Channel")]
+fn client_message_over_max_size_panics() {
+    let mut loopback = Loopback::connect(14381, ServerConfig::default());
+
+    let message = Payload::new(vec![0; 2 * 1024 * 1024]);
+
+    loopback
+        .client
+        .send_message(DefaultChannels::UnorderedReliable, &message);
+}
@@ -0,0 +1,82 @@
+use std::{thread::sleep, time::Duration};
+
+use naia_client::ReplayPlayer;
+use naia_demo_world::{Entity, World};
+use naia_server::ServerConfig;
+use naia_shared::{DefaultChannels, WorldRefType};
+use naia_test::{Loopback, Position, Protocol};
+
+type TestPlayer = ReplayPlayer<Protocol, Entity, DefaultChannels>;
+
+fn positions(world: &World<Protocol>) -> Vec<(i16, i16)> {
+    let world = world.proxy();
+    let mut positions: Vec<(i16, i16)> = world
+        .entities()
+        .iter()
+        .filter_map(|entity| {
+            world
+                .component::<Position>(entity)
+                .map(|position| (*position.x, *position.y))
+        })
+        .collect();
+    positions.sort_unstable();
+    positions
+}
+
+#[test]
+fn recording_started_partway_through_plays_back() {
+    let mut loopback = Loopback::connect(14321, ServerConfig::default());
+
+    let kept_entity = loopback.spawn_position(1, 2);
+    let despawned_entity = loopback.spawn_position(3, 4);
+    assert!(loopback.step_until(|loopback| positions(&loopback.client_world).len() == 2));
+
+    loopback
+        .client
+        .start_recording(loopback.client_world.proxy());
+
+    loopback
+        .server
+        .entity_mut(loopback.server_world.proxy_mut(), &despawned_entity)
+        .despawn();
+    *loopback
+        .server
+        .entity_mut(loopback.server_world.proxy_mut(), &kept_entity)
+        .component::<Position>()
+        .unwrap()
+        .x = 10;
+    loopback.spawn_position(5, 6);
+
+    let expected = vec![(5, 6), (10, 2)];
+    assert!(loopback.step_until(|loopback| positions(&loopback.client_world) == expected));
+    let replay = loopback.client.stop_recording().unwrap();
+
+    // Entities spawned before recording started are part of the replay
+    let mut player = TestPlayer::new(&replay).unwrap();
+    let mut world = World::<Protocol>::default();
+    player.set_speed(100.0);
+    player.play();
+    while !player.is_finished() {
+        for event in player.update(world.proxy_mut()) {
+            assert!(event.is_ok());
+        }
+        sleep(Duration::from_millis(1));
+    }
+
+    assert_eq!(positions(&world), expected);
+}
+
+#[test]
+fn truncated_replay_is_rejected() {
+    let mut loopback = Loopback::connect(14331, ServerConfig::default());
+
+    loopback
+        .client
+        .start_recording(loopback.client_world.proxy());
+    loopback.spawn_position(1, 2);
+    assert!(loopback.wait_for_client_spawn().is_some());
+    let replay = loopback.client.stop_recording().unwrap();
+
+    assert!(TestPlayer::new(&replay).is_ok());
+    assert!(TestPlayer::new(&replay[..replay.len() - 1]).is_err());
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use naia_demo_world::{Entity, World};
+use naia_server::{Server, ServerConfig, SnapshotError};
+use naia_shared::{ChannelConfig, DefaultChannels, SharedConfig, SocketConfig};
+use naia_test::{Position, Protocol};
+
+type TestServer = Server<Protocol, Entity, DefaultChannels>;
+
+fn new_server() -> TestServer {
+    let shared_config = SharedConfig::new(
+        SocketConfig::new(None, None),
+        ChannelConfig::default(),
+        Some(Duration::from_millis(20)),
+        None,
+    );
+    TestServer::new(&ServerConfig::default(), &shared_config)
+}
+
+fn take_snapshot() -> Vec<u8> {
+    let mut server = new_server();
+    let mut world = World::<Protocol>::default();
+
+    let room_key = server.make_room().key();
+    for index in 0..3 {
+        let entity = server
+            .spawn_entity(world.proxy_mut())
+            .insert_component(Position::new(index, -index))
+            .id();
+        server.room_mut(&room_key).add_entity(&entity);
+    }
+    server.spawn_entity(world.proxy_mut());
+
+    server.snapshot(world.proxy())
+}
+
+#[test]
+fn snapshot_restore_round_trip() {
+    let snapshot = take_snapshot();
+
+    let mut server = new_server();
+    let mut world = World::<Protocol>::default();
+    let restored = server
+        .restore_snapshot(world.proxy_mut(), &snapshot)
+        .unwrap();
+
+    assert_eq!(restored.entities.len(), 4);
+    assert_eq!(restored.rooms.len(), 1);
+    assert_eq!(server.room(&restored.rooms[0]).entities_count(), 3);
+
+    let mut positions: Vec<(i16, i16)> = restored
+        .entities
+        .iter()
+        .filter_map(|entity| {
+            server
+                .entity(world.proxy(), entity)
+                .component::<Position>()
+                .map(|position| (*position.x, *position.y))
+        })
+        .collect();
+    positions.sort();
+    assert_eq!(positions, vec![(0, 0), (1, -1), (2, -2)]);
+}
+
+#[test]
+fn restore_rejects_bad_snapshots() {
+    let snapshot = take_snapshot();
+
+    let mut server = new_server();
+    let mut world = World::<Protocol>::default();
+
+    assert!(matches!(
+        server.restore_snapshot(world.proxy_mut(), b"not a snapshot"),
+        Err(SnapshotError::NotASnapshot)
+    ));
+
+    let mut old_version = snapshot.clone();
+    old_version[4] = 0;
+    assert!(matches!(
+        server.restore_snapshot(world.proxy_mut(), &old_version),
+        Err(SnapshotError::UnsupportedVersion(0))
+    ));
+
+    let truncated = &snapshot[..snapshot.len() - 1];
+    assert!(matches!(
+        server.restore_snapshot(world.proxy_mut(), truncated),
+        Err(SnapshotError::Corrupt)
+    ));
+
+    let mut corrupt = snapshot.clone();
+    let last = corrupt.len() - 1;
+    corrupt[last] ^= 0xFF;
+    assert!(matches!(
+        server.restore_snapshot(world.proxy_mut(), &corrupt),
+        Err(SnapshotError::Corrupt)
+    ));
+
+    // nothing was restored along the way
+    assert!(server.entities(world.proxy()).is_empty());
+    assert_eq!(server.rooms_count(), 0);
+}